@@ -1,7 +1,13 @@
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 use geo::simplify::{Simplify, SimplifyIdx};
 use geo::simplify_vw::{SimplifyVw, SimplifyVwIdx, SimplifyVwPreserve};
 use geo_types::LineString;
+use rdp::api::{simplify_rdp_idx, simplify_vw_idx};
+use rdp::{
+    drop_float_array, drop_float_array_batch, drop_pooled_float_array, rdp_pool_free, rdp_pool_new,
+    simplify_rdp_batch_ffi, simplify_rdp_ffi, simplify_rdp_pooled_ffi, ExternalArray,
+};
+use std::ffi::c_void;
 
 fn bench_rdp(c: &mut Criterion) {
     c.bench_function("bench_rdp", |b| {
@@ -63,6 +69,19 @@ fn bench_rdp_long_idx(c: &mut Criterion) {
     });
 }
 
+fn bench_rdp_long_batched(c: &mut Criterion) {
+    let points: Vec<[f64; 2]> = include!("../src/mk_route_long.rs");
+    let ls: LineString<f64> = points.clone().into();
+    let mut group = c.benchmark_group("bench_rdp_long_batched");
+    group.bench_function("scalar", |b| {
+        b.iter(|| ls.simplify_idx(&0.001));
+    });
+    group.bench_function("batched", |b| {
+        b.iter(|| simplify_rdp_idx(&points, 0.001));
+    });
+    group.finish();
+}
+
 fn bench_visvalingam_long(c: &mut Criterion) {
     c.bench_function("bench_visvalingam_long", |b| {
         let points = include!("../src/mk_route_long.rs");
@@ -83,6 +102,20 @@ fn bench_visvalingam_long_idx(c: &mut Criterion) {
     });
 }
 
+// Copying the input into a LineString for geo, as the idx FFI functions used to, against
+// simplifying the borrowed coordinates
+fn bench_visvalingam_long_idx_borrowed(c: &mut Criterion) {
+    let points: Vec<[f64; 2]> = include!("../src/mk_route_long.rs");
+    let mut group = c.benchmark_group("bench_visvalingam_long_idx_borrowed");
+    group.bench_function("copied", |b| {
+        b.iter(|| LineString::from(points.to_vec()).simplify_vw_idx(&0.0000075));
+    });
+    group.bench_function("borrowed", |b| {
+        b.iter(|| simplify_vw_idx(&points, 0.0000075));
+    });
+    group.finish();
+}
+
 fn bench_visvalingamp_long(c: &mut Criterion) {
     c.bench_function("bench_visvalingamp_long", |b| {
         let points = include!("../src/mk_route_long.rs");
@@ -93,6 +126,46 @@ fn bench_visvalingamp_long(c: &mut Criterion) {
     });
 }
 
+fn bench_rdp_batch(c: &mut Criterion) {
+    let points: Vec<[f64; 2]> = include!("../src/mk_route_long.rs");
+    let mut group = c.benchmark_group("bench_rdp_batch");
+    for count in [1, 16, 128] {
+        let arrays: Vec<ExternalArray> = (0..count)
+            .map(|_| ExternalArray {
+                data: points.as_ptr() as *const c_void,
+                len: points.len(),
+            })
+            .collect();
+        group.bench_with_input(BenchmarkId::from_parameter(count), &arrays, |b, arrays| {
+            b.iter(|| unsafe {
+                let batch = simplify_rdp_batch_ffi(arrays.as_ptr(), arrays.len(), 0.001);
+                drop_float_array_batch(batch, arrays.len());
+            });
+        });
+    }
+    group.finish();
+}
+
+fn bench_rdp_pooled(c: &mut Criterion) {
+    let points: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+    let coords = ExternalArray {
+        data: points.as_ptr() as *const c_void,
+        len: points.len(),
+    };
+    let mut group = c.benchmark_group("bench_rdp_pooled");
+    group.bench_function("unpooled", |b| {
+        b.iter(|| drop_float_array(simplify_rdp_ffi(coords, 0.001)));
+    });
+    group.bench_function("pooled", |b| {
+        let pool = rdp_pool_new();
+        b.iter(|| unsafe {
+            drop_pooled_float_array(pool, simplify_rdp_pooled_ffi(pool, coords, 0.001));
+        });
+        unsafe { rdp_pool_free(pool) };
+    });
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_rdp,
@@ -101,8 +174,12 @@ criterion_group!(
     bench_visvalingam_idx,
     bench_rdp_long,
     bench_rdp_long_idx,
+    bench_rdp_long_batched,
     bench_visvalingam_long,
     bench_visvalingam_long_idx,
-    bench_visvalingamp_long
+    bench_visvalingam_long_idx_borrowed,
+    bench_visvalingamp_long,
+    bench_rdp_batch,
+    bench_rdp_pooled
 );
 criterion_main!(benches);
@@ -1,4 +1,4 @@
-/* Generated with cbindgen:0.20.0 */
+/* Generated with cbindgen:0.26.0 */
 
 /* Warning, this file is autogenerated by cbindgen. Don't modify this manually. */
 
@@ -7,9 +7,144 @@
 #include <stdint.h>
 #include <stdlib.h>
 
+/**
+ * Simplification algorithms which can be selected when calling [`simplify_ffi`](fn.simplify_ffi.html)
+ */
+typedef enum Algorithm {
+    /**
+     * Ramer–Douglas–Peucker
+     */
+    Rdp = 0,
+    /**
+     * Visvalingam-Whyatt
+     */
+    Visvalingam = 1,
+    /**
+     * Topology-preserving Visvalingam-Whyatt
+     */
+    VisvalingamPreserve = 2,
+} Algorithm;
+
+/**
+ * The order of the values in each pair of geographic coordinates passed to
+ * [`simplify_rdp_geodesic_ffi`](fn.simplify_rdp_geodesic_ffi.html)
+ */
+typedef enum CoordOrder {
+    /**
+     * (longitude, latitude), as in GeoJSON and WKT
+     */
+    LonLat = 0,
+    /**
+     * (latitude, longitude), as in many GPS formats
+     */
+    LatLon = 1,
+} CoordOrder;
+
+/**
+ * The element types of the arrays returned by this library, for use with
+ * [`internal_array_byte_size`](fn.internal_array_byte_size.html)
+ */
+typedef enum ElementKind {
+    /**
+     * Pairs of `double`s, as freed by [`drop_float_array`](fn.drop_float_array.html)
+     */
+    F64Pair = 0,
+    /**
+     * `size_t`s, as freed by [`drop_usize_array`](fn.drop_usize_array.html)
+     */
+    Usize = 1,
+    /**
+     * `uint32_t`s, as freed by [`drop_u32_array`](fn.drop_u32_array.html)
+     */
+    U32 = 2,
+    /**
+     * Bytes, as freed by [`drop_u8_array`](fn.drop_u8_array.html), or by `drop_byte_array` with the
+     * `wkb` feature
+     */
+    U8 = 3,
+} ElementKind;
+
+/**
+ * Error codes returned as part of an [`FfiResult`](struct.FfiResult.html)
+ *
+ * Every function records a more detailed, human-readable description of a failure, which can be
+ * retrieved using [`rdp_last_error_message`](fn.rdp_last_error_message.html).
+ */
+typedef enum FfiError {
+    /**
+     * The input was valid, and the accompanying array contains the result
+     */
+    Success = 0,
+    /**
+     * The `data` field of the input array was a null pointer
+     */
+    NullPointer = 1,
+    /**
+     * The `len` field of the input array was 0
+     */
+    ZeroLength = 2,
+    /**
+     * The input array contained at least one NaN or infinite coordinate
+     */
+    NonFiniteCoordinate = 3,
+    /**
+     * The caller-provided output array was too small to hold the result
+     */
+    BufferTooSmall = 4,
+    /**
+     * Simplification panicked, so no result could be produced
+     */
+    Panic = 5,
+    /**
+     * A retained index was too large to be represented in the output's index type
+     */
+    IndexOverflow = 6,
+} FfiError;
+
+/**
+ * How Visvalingam-Whyatt chooses which of several points whose triangles have equal areas to
+ * remove first, when calling [`simplify_visvalingam_tiebreak_ffi`](fn.simplify_visvalingam_tiebreak_ffi.html)
+ *
+ * A point becomes a candidate for removal at the start of simplification, and again each time
+ * one of its neighbours is removed.
+ */
+typedef enum TieBreak {
+    /**
+     * The point which became a candidate earliest
+     */
+    First = 0,
+    /**
+     * The point which became a candidate most recently
+     */
+    Last = 1,
+    /**
+     * The point which appears earliest in the input
+     */
+    LowestIndex = 2,
+} TieBreak;
+
+/**
+ * A pool of output buffers, used by [`simplify_rdp_pooled_ffi`](fn.simplify_rdp_pooled_ffi.html)
+ *
+ * Create one using [`rdp_pool_new`](fn.rdp_pool_new.html). A pool isn't thread-safe: callers must
+ * not use the same pool from multiple threads concurrently.
+ */
+typedef struct RdpPool RdpPool;
+
+/**
+ * An in-progress streaming RDP simplification
+ *
+ * Create one using [`rdp_stream_new`](fn.rdp_stream_new.html).
+ */
+typedef struct RdpStream RdpStream;
+
 /**
  * A C-compatible `struct` originating **inside** Rust
  * used for passing arrays across the FFI boundary
+ *
+ * Each array must be freed exactly once, using the drop function documented by the function which
+ * returned it. In debug builds, freeing an array twice, or using a drop function for a different
+ * element type, panics instead of corrupting the heap.
  */
 typedef struct InternalArray {
     void *data;
@@ -26,14 +161,288 @@ typedef struct ExternalArray {
 } ExternalArray;
 
 /**
- * FFI wrapper for RDP, returning simplified geometry **coordinates**
+ * A function receiving the progress of a batch, for use with
+ * [`simplify_rdp_batch_progress_ffi`](fn.simplify_rdp_batch_progress_ffi.html)
+ *
+ * It receives the number of geometries simplified so far and the number in the batch, followed by
+ * the caller's `user` pointer.
+ */
+typedef void (*ProgressCallback)(size_t done, size_t total, void *user);
+
+/**
+ * A function which allocates the memory for arrays returned by this library, such as `malloc`
+ *
+ * It's called with a size in bytes, which is never 0, and must return a pointer to memory which is
+ * suitably aligned for any type, or a null pointer if the allocation fails.
+ */
+typedef void *(*AllocCallback)(size_t size);
+
+/**
+ * A function which frees memory allocated by an [`AllocCallback`], such as `free`
+ */
+typedef void (*FreeCallback)(void *data);
+
+/**
+ * A C-compatible `struct` returned by the `_checked` FFI functions
+ *
+ * If `error` is anything other than `Success`, `array` is empty: its `data` field is a null pointer
+ * and its `len` field is 0. It is nevertheless safe to pass it to the relevant drop function.
+ */
+typedef struct FfiResult {
+    struct InternalArray array;
+    enum FfiError error;
+} FfiResult;
+
+/**
+ * A function receiving retained points, for use with
+ * [`simplify_rdp_foreach_ffi`](fn.simplify_rdp_foreach_ffi.html)
+ *
+ * It receives the coordinates of a retained point, followed by the caller's `user` pointer.
+ */
+typedef void (*PointCallback)(double x, double y, void *user);
+
+/**
+ * A point-to-segment distance function, for use with
+ * [`simplify_rdp_custom_ffi`](fn.simplify_rdp_custom_ffi.html)
+ *
+ * It receives the coordinates of the segment's start (`ax`, `ay`) and end (`bx`, `by`), followed
+ * by those of the point (`px`, `py`), and must return the point's distance from the segment.
+ */
+typedef double (*DistanceCallback)(double ax,
+                                   double ay,
+                                   double bx,
+                                   double by,
+                                   double px,
+                                   double py);
+
+/**
+ * FFI wrapper for RDP, simplifying a batch of geometries and returning their **coordinates**
+ *
+ * Callers must pass three arguments:
+ *
+ * - a pointer to the first of `count` contiguous [Structs](struct.ExternalArray.html), each of which
+ *   describes a LineString in the same way as [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html)
+ * - `count`, the number of geometries in the batch. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance, which is applied to every geometry
+ *
+ * The returned pointer refers to `count` contiguous `InternalArray`s, in the same order as the input.
+ * A geometry which fails validation (see [`simplify_rdp_checked_ffi`](fn.simplify_rdp_checked_ffi.html))
+ * produces an empty array in its slot. If `arrays` is a null pointer or `count` is 0, a null pointer
+ * is returned.
+ *
+ * Implementations calling this function **must** call [`drop_float_array_batch`](fn.drop_float_array_batch.html)
+ * with the returned pointer and the original `count`, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray *simplify_rdp_batch_ffi(const struct ExternalArray *arrays,
+                                             size_t count,
+                                             double precision);
+
+/**
+ * FFI wrapper for RDP, simplifying a batch of geometries, reporting progress, and returning their **coordinates**
+ *
+ * Callers must pass five arguments:
+ *
+ * - a pointer to the first of `count` contiguous [Structs](struct.ExternalArray.html), each of which
+ *   describes a LineString in the same way as [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html)
+ * - `count`, the number of geometries in the batch. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance, which is applied to every geometry
+ * - a [`ProgressCallback`](type.ProgressCallback.html), which is called every 256 geometries, and
+ *   once every geometry has been simplified. It may be null
+ * - a void pointer, which is passed to each call of the callback unchanged. It may be null
+ *
+ * The result is the same as that of [`simplify_rdp_batch_ffi`](fn.simplify_rdp_batch_ffi.html).
+ * Geometries which fail validation count towards the progress, so the last call of the callback
+ * has `done` equal to `total`. If the `rayon` feature is enabled, the callback may be called from
+ * any of rayon's threads, but calls are serialised: it's never called by more than one thread at a
+ * time, and `done` increases with each call. The callback should return quickly, as simplification
+ * waits for it.
+ *
+ * Implementations calling this function **must** call [`drop_float_array_batch`](fn.drop_float_array_batch.html)
+ * with the returned pointer and the original `count`, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray *simplify_rdp_batch_progress_ffi(const struct ExternalArray *arrays,
+                                                      size_t count,
+                                                      double precision,
+                                                      ProgressCallback progress,
+                                                      void *user);
+
+/**
+ * FFI wrapper for Visvalingam-Whyatt, simplifying a batch of geometries and returning their **coordinates**
+ *
+ * Callers must pass three arguments:
+ *
+ * - a pointer to the first of `count` contiguous [Structs](struct.ExternalArray.html), each of which
+ *   describes a LineString in the same way as [`simplify_visvalingam_ffi`](fn.simplify_visvalingam_ffi.html)
+ * - `count`, the number of geometries in the batch. Its type must be `size_t`
+ * - a double-precision `float` for the epsilon, which is applied to every geometry
+ *
+ * The returned pointer refers to `count` contiguous `InternalArray`s, in the same order as the input.
+ * A geometry which fails validation produces an empty array in its slot.
+ * If `arrays` is a null pointer or `count` is 0, a null pointer is returned.
+ *
+ * Implementations calling this function **must** call [`drop_float_array_batch`](fn.drop_float_array_batch.html)
+ * with the returned pointer and the original `count`, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray *simplify_visvalingam_batch_ffi(const struct ExternalArray *arrays,
+                                                     size_t count,
+                                                     double precision);
+
+/**
+ * FFI wrapper for RDP, simplifying a batch of geometries and returning their **indices** in a
+ * single packed array
+ *
+ * Callers must pass four arguments:
+ *
+ * - a pointer to the first of `count` contiguous [Structs](struct.ExternalArray.html), each of which
+ *   describes a LineString in the same way as [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html)
+ * - `count`, the number of geometries in the batch. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance, which is applied to every geometry
+ * - a pointer to an [`InternalArray`](struct.InternalArray.html), which receives the offsets
+ *
+ * The returned `Array` contains the `size_t` indices retained from every geometry, concatenated
+ * in input order. The offsets array contains `count + 1` `size_t` values: the indices retained
+ * from geometry `i` are the elements from `offsets[i]` up to (but not including) `offsets[i + 1]`.
+ * A geometry which fails validation retains no indices, so its slice is empty.
+ *
+ * If `arrays` or `offsets_out` is a null pointer, or `count` is 0, an empty array is returned,
+ * and `offsets_out` (if it isn't null) receives an empty array.
+ *
+ * Implementations calling this function **must** call [`drop_usize_array`](fn.drop_usize_array.html)
+ * with both the returned `Array` and the offsets, in order to free the memory they allocate.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses raw pointers which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_idx_packed_ffi(const struct ExternalArray *arrays,
+                                                 size_t count,
+                                                 double precision,
+                                                 struct InternalArray *offsets_out);
+
+/**
+ * FFI wrapper for RDP, simplifying a batch of geometries which share boundaries and returning their **coordinates**
+ *
+ * Callers must pass four arguments:
+ *
+ * - a pointer to the first of `count` contiguous [Structs](struct.ExternalArray.html), each of which
+ *   describes a LineString in the same way as [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html)
+ * - a pointer to the first of `count` contiguous [Structs](struct.ExternalArray.html), each of which
+ *   describes an array of `size_t` indices of the vertices of the corresponding LineString which
+ *   must be retained, such as the ends of boundaries shared with other geometries. An entry with a
+ *   null `data` pointer pins no vertices, and out-of-bounds indices are ignored
+ * - `count`, the number of geometries in the batch. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance, which is applied to every geometry
+ *
+ * Each geometry is split at its pinned vertices, and each section is simplified independently of
+ * its direction, so a boundary pinned at both ends in every geometry containing it is simplified
+ * identically in all of them. See [`api::simplify_rdp_shared`](api/fn.simplify_rdp_shared.html).
+ *
+ * The returned pointer refers to `count` contiguous `InternalArray`s, in the same order as the input.
+ * A geometry which fails validation produces an empty array in its slot. If either pointer is null
+ * or `count` is 0, a null pointer is returned.
+ *
+ * Implementations calling this function **must** call [`drop_float_array_batch`](fn.drop_float_array_batch.html)
+ * with the returned pointer and the original `count`, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses raw pointers which could contain arbitrary data
+ */
+struct InternalArray *simplify_rdp_shared_ffi(const struct ExternalArray *arrays,
+                                              const struct ExternalArray *shared,
+                                              size_t count,
+                                              double precision);
+
+/**
+ * FFI wrapper for RDP, simplifying the components of a MultiLineString and returning their **coordinates**
  *
  * Callers must pass two arguments:
  *
  * - a [Struct](struct.Array.html) with two fields:
- *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
- *     - `len`, the length of the array being passed. Its type must be `size_t`
- * - a double-precision `float` for the tolerance
+ *     - `data`, a pointer to an array of [Structs](struct.ExternalArray.html), each of which
+ *       describes a component LineString in the same way as [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html)
+ *     - `len`, the number of components. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance, which is applied to every component
+ *
+ * The returned `Array` has the same structure as the input: its `data` field points to `len`
+ * `InternalArray`s, one per component, in the same order as the input. Components are never
+ * dropped or merged, even if they simplify to two points: a component which fails validation
+ * produces an empty array in its slot.
+ *
+ * Implementations calling this function **must** call [`drop_multi_float_array`](fn.drop_multi_float_array.html)
+ * with the returned `Array`, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_multi_ffi(struct ExternalArray components,
+                                            double precision);
+
+/**
+ * Free memory which has been allocated across the FFI boundary by:
+ * - simplify_rdp_multi_ffi
+ * - simplify_rdp_polygon_ffi
+ *
+ * Each component array is freed, followed by the outer array.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+void drop_multi_float_array(struct InternalArray arr);
+
+/**
+ * Free memory which has been allocated across the FFI boundary by:
+ * - simplify_rdp_batch_ffi
+ * - simplify_rdp_batch_progress_ffi
+ * - simplify_visvalingam_batch_ffi
+ * - simplify_rdp_shared_ffi
+ *
+ * `count` **must** be the value which was passed to the simplification function.
+ * Each array in the batch is freed, followed by the batch itself.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+void drop_float_array_batch(struct InternalArray *arrays,
+                            size_t count);
+
+/**
+ * Set the tolerance used by [`simplify_rdp_default_ffi`](fn.simplify_rdp_default_ffi.html)
+ *
+ * The default is 0.0, which retains every point. NaN and infinite values are ignored.
+ * This function can safely be called from multiple threads.
+ */
+void rdp_set_default_precision(double precision);
+
+/**
+ * Get the tolerance used by [`simplify_rdp_default_ffi`](fn.simplify_rdp_default_ffi.html)
+ */
+double rdp_get_default_precision(void);
+
+/**
+ * FFI wrapper for RDP using the default tolerance, returning simplified geometry **coordinates**
+ *
+ * Callers must pass a [Struct](struct.Array.html) with two fields:
+ *
+ * - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ * - `len`, the length of the array being passed. Its type must be `size_t`
+ *
+ * The tolerance is set using [`rdp_set_default_precision`](fn.rdp_set_default_precision.html).
  *
  * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
  * with the returned `Array` pointer, in order to free the memory it allocates.
@@ -42,38 +451,128 @@ typedef struct ExternalArray {
  *
  * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
  */
-struct InternalArray simplify_rdp_ffi(struct ExternalArray coords,
-                                      double precision);
+struct InternalArray simplify_rdp_default_ffi(struct ExternalArray coords);
 
 /**
- * FFI wrapper for RDP, returning simplified geometry **indices**
+ * Get a description of the most recent failure on the calling thread
+ *
+ * When a function returns an empty or null result, or an error code, because its input was
+ * invalid or simplification panicked, it records a human-readable description of the reason,
+ * such as a null pointer, a NaN coordinate (and its position), or a WKT or GeoJSON parsing error.
+ * This function returns it as a nul-terminated, UTF-8 string, or a null pointer if no failure has
+ * been recorded on the calling thread.
+ *
+ * Descriptions are recorded per thread, so failures on other threads don't affect the result.
+ * Successful calls don't clear the description, so it should only be retrieved after a failure.
+ *
+ * The string is owned by this library, and **must not** be freed. It remains valid until the next
+ * failure on the same thread, so callers wishing to keep it should copy it.
+ */
+const char *rdp_last_error_message(void);
+
+/**
+ * Get the version of this library, and of the [geo](https://crates.io/crates/geo) crate it was
+ * compiled against, such as `0.12.11 (geo 0.28)`
+ *
+ * The string is nul-terminated, static, and owned by this library, so it **must not** be freed.
+ */
+const char *rdp_version(void);
+
+/**
+ * Report whether an optional feature was compiled into this library
+ *
+ * Callers must pass a pointer to a nul-terminated string naming a feature: `rayon`, `geojson`,
+ * `wkb`, `serde`, or `f32`, for the single-precision functions such as
+ * [`simplify_rdp_ffi_f32`](fn.simplify_rdp_ffi_f32.html), which are always available.
+ *
+ * Returns 1 if the feature is available, and 0 if it isn't, or if the name is a null pointer or
+ * isn't recognised, so hosts which load this library dynamically can probe its capabilities
+ * before calling functions which may not exist.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+int rdp_has_feature(const char *name);
+
+/**
+ * Get the number of bytes of memory owned by an array returned by this library
+ *
+ * Callers must pass two arguments:
+ *
+ * - a pointer to an `Array` returned by this library, which hasn't been freed
+ * - an `int` for the array's element type: see [`ElementKind`](enum.ElementKind.html)
+ *
+ * The size is the array's `len` multiplied by the size of its element type, so hosts can account
+ * for the memory before freeing it. 0 is returned if the pointer is null, or the element type
+ * isn't valid.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+size_t internal_array_byte_size(const struct InternalArray *arr,
+                                int element_kind);
+
+/**
+ * Allocate the arrays returned by this library using the host's allocator
  *
  * Callers must pass two arguments:
  *
+ * - an [`AllocCallback`](type.AllocCallback.html), such as `malloc`
+ * - a [`FreeCallback`](type.FreeCallback.html) which frees the memory it allocates, such as `free`
+ *
+ * Afterwards, the `data` of each returned array is allocated by `alloc_fn`, so the host can free
+ * it directly using its own allocator, rather than by calling the array's drop function (which
+ * calls `free_fn`). The arrays of arrays returned by the batch functions, and each array in them,
+ * are allocated in the same way. Empty arrays have a null `data` pointer, and nothing is
+ * allocated for them. Strings, and arrays borrowed from an [`RdpPool`](struct.RdpPool.html), are
+ * unaffected. If `alloc_fn` fails, an empty array is returned. The debug-build checks for arrays
+ * which are freed twice, or by the wrong drop function, don't apply to host-allocated arrays.
+ *
+ * The allocator can only be set once, and **must** be set before any function returning an array
+ * is called, so that no array is freed by a different allocator from the one which allocated it.
+ * The callbacks may be called from any thread, so they must be thread-safe.
+ *
+ * Returns 1 if the allocator was set, or 0 if either callback is null, or it's too late to set it.
+ */
+int rdp_set_allocator(AllocCallback alloc_fn,
+                      FreeCallback free_fn);
+
+/**
+ * FFI wrapper for all simplification algorithms, returning simplified geometry **coordinates**
+ *
+ * Callers must pass three arguments:
+ *
  * - a [Struct](struct.Array.html) with two fields:
  *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
  *     - `len`, the length of the array being passed. Its type must be `size_t`
- * - a double-precision `float` for the tolerance
+ * - a double-precision `float` for the tolerance or epsilon
+ * - an `int` selecting the [`Algorithm`](enum.Algorithm.html): RDP (0), Visvalingam-Whyatt (1),
+ *   or topology-preserving Visvalingam-Whyatt (2). Any other value returns an empty array.
  *
- * Implementations calling this function **must** call [`drop_usize_array`](fn.drop_usize_array.html)
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
  * with the returned `Array` pointer, in order to free the memory it allocates.
  *
  * # Safety
  *
  * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
  */
-struct InternalArray simplify_rdp_idx_ffi(struct ExternalArray coords,
-                                          double precision);
+struct InternalArray simplify_ffi(struct ExternalArray coords,
+                                  double precision,
+                                  int algorithm);
 
 /**
- * FFI wrapper for Visvalingam-Whyatt, returning simplified geometry **coordinates**
+ * FFI wrapper for RDP, returning simplified geometry **coordinates**
  *
  * Callers must pass two arguments:
  *
  * - a [Struct](struct.Array.html) with two fields:
  *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
  *     - `len`, the length of the array being passed. Its type must be `size_t`
- * - a double-precision `float` for the epsilon
+ * - a double-precision `float` for the tolerance
+ *
+ * Simplification doesn't recurse, so its stack usage doesn't depend on the shape of the line.
  *
  * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
  * with the returned `Array` pointer, in order to free the memory it allocates.
@@ -82,68 +581,2436 @@ struct InternalArray simplify_rdp_idx_ffi(struct ExternalArray coords,
  *
  * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
  */
-struct InternalArray simplify_visvalingam_ffi(struct ExternalArray coords,
-                                              double precision);
+struct InternalArray simplify_rdp_ffi(struct ExternalArray coords,
+                                      double precision);
 
 /**
- * FFI wrapper for Visvalingam-Whyatt, returning simplified geometry **indices**
+ * FFI wrapper for RDP, returning simplified geometry **coordinates** as a flat array
  *
  * Callers must pass two arguments:
  *
  * - a [Struct](struct.Array.html) with two fields:
  *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
  *     - `len`, the length of the array being passed. Its type must be `size_t`
- * - a double-precision `float` for the epsilon
+ * - a double-precision `float` for the tolerance
  *
- * Implementations calling this function **must** call [`drop_usize_array`](fn.drop_usize_array.html)
+ * The points are the same as those returned by [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html),
+ * but the returned `Array`'s `data` field points to interleaved `double`s: `[x0, y0, x1, y1, ...]`,
+ * and its `len` field is the number of `double`s, which is twice the number of points.
+ *
+ * Implementations calling this function **must** call [`drop_flat_f64_array`](fn.drop_flat_f64_array.html)
  * with the returned `Array` pointer, in order to free the memory it allocates.
+ * [`drop_float_array`](fn.drop_float_array.html) **must not** be used for this purpose.
  *
  * # Safety
  *
  * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
  */
-struct InternalArray simplify_visvalingam_idx_ffi(struct ExternalArray coords,
-                                                  double precision);
+struct InternalArray simplify_rdp_flat_ffi(struct ExternalArray coords,
+                                           double precision);
 
 /**
- * FFI wrapper for topology-preserving Visvalingam-Whyatt, returning simplified geometry **coordinates**.
+ * FFI wrapper for RDP, taking and returning x and y coordinates as separate arrays
  *
- * Callers must pass two arguments:
+ * Callers must pass four arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of double-precision `float` x coordinates
+ *     - `len`, the number of x coordinates. Its type must be `size_t`
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of double-precision `float` y coordinates
+ *     - `len`, the number of y coordinates, which must equal the number of x coordinates
+ * - a double-precision `float` for the tolerance
+ * - a pointer to an [`InternalArray`](struct.InternalArray.html), into which the y coordinates of
+ *   the retained points are written. A null pointer is ignored.
+ *
+ * The points are the same as those returned by [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html) for
+ * the interleaved coordinates: the returned `Array` holds their x coordinates, and the array
+ * written to `ys_out` holds their y coordinates. If either input is invalid, or their lengths
+ * differ, both arrays are empty.
+ *
+ * Implementations calling this function **must** call [`drop_flat_f64_array`](fn.drop_flat_f64_array.html)
+ * with both the returned `Array` and the y coordinates, in order to free the memory they allocate.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses raw pointers which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_xy_ffi(struct ExternalArray xs,
+                                         struct ExternalArray ys,
+                                         double precision,
+                                         struct InternalArray *ys_out);
+
+/**
+ * FFI wrapper for cancellable RDP, returning simplified geometry **coordinates**
+ *
+ * Callers must pass three arguments:
  *
  * - a [Struct](struct.Array.html) with two fields:
  *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
  *     - `len`, the length of the array being passed. Its type must be `size_t`
- * - a double-precision `float` for the epsilon
+ * - a double-precision `float` for the tolerance
+ * - a pointer to an `int` flag, which another thread sets to a non-zero value to cancel the
+ *   simplification. It must be written atomically, for instance as a C11 `atomic_int`, and must
+ *   remain valid until this function returns. A null pointer means the simplification can't be
+ *   cancelled
+ *
+ * The flag is checked before each segment is simplified, so a long simplification stops soon after
+ * it is set, and an empty array is returned. Input with fewer than 3 points is returned without
+ * checking the flag.
  *
  * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
  * with the returned `Array` pointer, in order to free the memory it allocates.
  *
  * # Safety
  *
- * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ * This function is unsafe because it accesses raw pointers which could contain arbitrary data
  */
-struct InternalArray simplify_visvalingamp_ffi(struct ExternalArray coords,
-                                               double precision);
+struct InternalArray simplify_rdp_cancellable_ffi(struct ExternalArray coords,
+                                                  double precision,
+                                                  const int *cancel);
 
 /**
- * Free memory which has been allocated across the FFI boundary by:
- * - simplify_rdp_ffi
- * - simplify_visvalingam_ffi
- * - simplify_visvalingamp_ffi
+ * FFI wrapper for RDP, returning simplified geometry **indices**
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ *
+ * The indices are `size_t` values, so their width depends on the target: 4 bytes on 32-bit
+ * targets, and 8 bytes on 64-bit targets. Bindings which assume a fixed width should use
+ * [`simplify_rdp_idx_u32_ffi`](fn.simplify_rdp_idx_u32_ffi.html) instead.
+ *
+ * Implementations calling this function **must** call [`drop_usize_array`](fn.drop_usize_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
  *
  * # Safety
  *
  * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
  */
-void drop_float_array(struct InternalArray arr);
+struct InternalArray simplify_rdp_idx_ffi(struct ExternalArray coords,
+                                          double precision);
 
 /**
- * Free memory which has been allocated across the FFI boundary by:
- * - simplify_rdp_idx_ffi
- * - simplify_visvalingam_idx_ffi
+ * FFI wrapper for RDP, returning simplified geometry **indices** as 32-bit unsigned integers
  *
- * # Safety
+ * Callers must pass two arguments:
  *
- * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
- */
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ *
+ * Unlike [`simplify_rdp_idx_ffi`](fn.simplify_rdp_idx_ffi.html), the indices are `uint32_t`
+ * values on every target. If any retained index is greater than `UINT32_MAX`, the returned
+ * [`FfiResult`](struct.FfiResult.html) contains an empty array and an `IndexOverflow` error.
+ * Invalid input is reported in the same way as by
+ * [`simplify_rdp_idx_checked_ffi`](fn.simplify_rdp_idx_checked_ffi.html).
+ *
+ * Implementations calling this function **must** call [`drop_u32_array`](fn.drop_u32_array.html)
+ * with the returned `array` field, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct FfiResult simplify_rdp_idx_u32_ffi(struct ExternalArray coords,
+                                          double precision);
+
+/**
+ * FFI wrapper for RDP, returning the **indices** of the points removed by simplification
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ *
+ * The indices are sorted, and are the complement of those returned by
+ * [`simplify_rdp_idx_ffi`](fn.simplify_rdp_idx_ffi.html): together, they contain every index of
+ * the input exactly once. If the input is invalid, an empty array is returned.
+ *
+ * Implementations calling this function **must** call [`drop_usize_array`](fn.drop_usize_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_removed_idx_ffi(struct ExternalArray coords,
+                                                  double precision);
+
+/**
+ * FFI wrapper for RDP, returning the number of points removed from each simplified segment
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ *
+ * There is one count for each segment of the line returned by
+ * [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html), in order, so the counts show where points were
+ * removed, and sum to the number of points removed. Input with fewer than two points has no
+ * segments, and produces an empty array, as does invalid input.
+ *
+ * Implementations calling this function **must** call [`drop_usize_array`](fn.drop_usize_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_segment_stats_ffi(struct ExternalArray coords,
+                                                    double precision);
+
+/**
+ * FFI wrapper for RDP on a time series, returning simplified geometry **coordinates**
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point (x, y) pairs, whose x coordinates,
+ *       such as times, are strictly increasing: `[[1.0, 2.0], [2.0, 1.5], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ *
+ * Distances are measured vertically, between each point and the value interpolated at its x
+ * coordinate by the segment's ends, rather than perpendicular to the segment as by
+ * [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html), so the tolerance is in the units of y alone. This
+ * suits charts, whose axes have unrelated units. The output's x coordinates are strictly increasing.
+ * If the input's x coordinates aren't strictly increasing, or the input is otherwise invalid, an
+ * empty array is returned.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_series_ffi(struct ExternalArray coords,
+                                             double precision);
+
+/**
+ * FFI wrapper for RDP, returning a **mask** of the points retained by simplification
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ *
+ * The returned `Array`'s `data` field points to `uint8_t` values, one per input point, which are
+ * 1 for the points returned by [`simplify_rdp_idx_ffi`](fn.simplify_rdp_idx_ffi.html), and 0 for
+ * the rest. Columnar callers can use it to filter the points' attributes in a single operation.
+ * If the input is invalid, an empty array is returned.
+ *
+ * Implementations calling this function **must** call [`drop_u8_array`](fn.drop_u8_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_mask_ffi(struct ExternalArray coords,
+                                           double precision);
+
+/**
+ * FFI wrapper for RDP, simplifying part of a line, and returning geometry **coordinates**
+ *
+ * Callers must pass four arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a `size_t` for the index of the first point to simplify
+ * - a `size_t` for the index of the last point to simplify
+ * - a double-precision `float` for the tolerance
+ *
+ * Only the points from `start` to `end` (inclusive) are simplified, in the same way as
+ * [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html), and the points before and after them are
+ * returned unchanged, so an editor can re-simplify just the part of a line being edited. Passing
+ * `0` and `len - 1` simplifies the whole line. If `start` is greater than `end`, or `end` is out
+ * of bounds, an empty array is returned.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_range_ffi(struct ExternalArray coords,
+                                            size_t start,
+                                            size_t end,
+                                            double precision);
+
+/**
+ * FFI wrapper for RDP, joining two lines and returning the simplified geometry **coordinates**
+ *
+ * Callers must pass three arguments:
+ *
+ * - two [Structs](struct.Array.html), each with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ *
+ * The points of `b` are appended to those of `a`, and the joined line is simplified as one, in
+ * the same way as [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html). If the last point of `a` is
+ * equal to the first point of `b`, such as at the junction of two adjacent segments of a route, it
+ * appears once in the joined line, and is removed if it's within the tolerance. If either input is
+ * invalid, an empty array is returned.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses raw pointers which could contain arbitrary data
+ */
+struct InternalArray merge_simplify_rdp_ffi(struct ExternalArray a,
+                                            struct ExternalArray b,
+                                            double precision);
+
+/**
+ * FFI wrapper for RDP, limiting the length of simplified segments, and returning simplified
+ * geometry **coordinates**
+ *
+ * Callers must pass three arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ * - a double-precision `float` for the maximum length of a segment of the simplified line
+ *
+ * The line is simplified as by [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html), which can replace a
+ * long, gentle curve by a single segment. Then, each segment which is longer than
+ * `max_segment_length` is split by restoring the removed point closest to its midpoint, until
+ * every segment is short enough, or no removed points remain between its ends. Segments of the
+ * input which are longer than `max_segment_length` therefore can't be split.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_max_segment_ffi(struct ExternalArray coords,
+                                                  double precision,
+                                                  double max_segment_length);
+
+/**
+ * FFI wrapper for RDP, guaranteeing a minimum sampling density, and returning simplified geometry
+ * **coordinates**
+ *
+ * Callers must pass three arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ * - a `size_t` for the maximum number of input points a simplified segment may skip
+ *
+ * The line is simplified as by [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html). Then, each segment
+ * which skips more than `max_span` input points is split by restoring every `max_span + 1`th
+ * removed point along it, which bounds the interpolation error in index space. A `max_span` of 0
+ * retains every point.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_density_ffi(struct ExternalArray coords,
+                                              double precision,
+                                              size_t max_span);
+
+/**
+ * FFI wrapper for RDP, returning simplified geometry **coordinates** and the number of removed points
+ *
+ * Callers must pass three arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ * - a pointer to a `size_t`, into which the number of points removed by simplification is written.
+ *   If the input is invalid, 0 is written. A null pointer is ignored.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_stats_ffi(struct ExternalArray coords,
+                                            double precision,
+                                            size_t *removed_out);
+
+/**
+ * FFI wrapper for RDP, returning simplified geometry **coordinates** and their bounding box
+ *
+ * Callers must pass three arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ * - a pointer to an array of four double-precision `float`s, into which the bounding box of the
+ *   simplified line is written as `[min_x, min_y, max_x, max_y]`. If the returned array is empty,
+ *   NaN is written. A null pointer is ignored.
+ *
+ * The bounding box is that of the returned coordinates, not of the input: if simplification
+ * removes an interior point at the edge of the input's bounding box, the simplified line's bounding
+ * box is slightly smaller. The endpoints are always retained, so it always contains them.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses raw pointers which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_bbox_ffi(struct ExternalArray coords,
+                                           double precision,
+                                           double (*bbox_out)[4]);
+
+/**
+ * FFI wrapper for RDP, returning the convex hull of the simplified geometry's **coordinates**
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ *
+ * The input is simplified as by [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html), and the convex hull
+ * of the retained points is computed by geo. It's returned as a closed, counter-clockwise ring,
+ * whose vertices are a subset of the retained points, for use in broad-phase culling. If fewer
+ * than three distinct points are retained, they can't form a ring, so the retained points are
+ * returned instead.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_hull_ffi(struct ExternalArray coords,
+                                           double precision);
+
+/**
+ * FFI wrapper for RDP, returning simplified geometry **coordinates** and the length of the line
+ * before and after simplification
+ *
+ * Callers must pass four arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ * - a pointer to a double-precision `float`, into which the Euclidean length of the input is written
+ * - a pointer to a double-precision `float`, into which the Euclidean length of the simplified line
+ *   is written
+ *
+ * The simplified line's vertices are a subset of the input's, so its length never exceeds the
+ * input's. If the input is invalid, NaN is written to both. Null pointers are ignored.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses raw pointers which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_length_ffi(struct ExternalArray coords,
+                                             double precision,
+                                             double *orig_len_out,
+                                             double *simp_len_out);
+
+/**
+ * FFI wrapper for RDP with bounded work, returning simplified geometry **coordinates**
+ *
+ * Callers must pass four arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ * - a `size_t` for the maximum number of point-to-segment distances to compute
+ * - a pointer to an `int`, into which 1 is written if simplification stopped early, and 0
+ *   otherwise. A null pointer is ignored.
+ *
+ * RDP does O(n²) work for pathological input, so this gives latency-sensitive callers a hard
+ * ceiling. Segments are split in order of decreasing distance, so if `max_work` is reached, the
+ * most significant points have already been retained, and the result is a coarser (but valid)
+ * simplification. Otherwise, the result is identical to that of
+ * [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html). If the input is invalid, an empty array is
+ * returned, and 0 is written.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses raw pointers which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_bounded_ffi(struct ExternalArray coords,
+                                              double precision,
+                                              size_t max_work,
+                                              int *truncated_out);
+
+/**
+ * FFI wrapper for RDP, returning simplified geometry **coordinates** and their **indices**
+ *
+ * Callers must pass three arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ * - a pointer to an [`InternalArray`](struct.InternalArray.html), into which the indices of the
+ *   retained points are written. A null pointer is ignored.
+ *
+ * The geometry is only simplified once: the returned coordinates are those of the input at the
+ * written indices, in the same order. If the input is invalid, both arrays are empty.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array`, and [`drop_usize_array`](fn.drop_usize_array.html) with the indices,
+ * in order to free the memory they allocate.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses raw pointers which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_both_ffi(struct ExternalArray coords,
+                                           double precision,
+                                           struct InternalArray *idx_out);
+
+/**
+ * FFI wrapper for RDP, returning simplified geometry **coordinates** after discarding
+ * non-finite input points
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ *
+ * Unlike [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html), which returns an empty array if any
+ * coordinate is NaN or infinite, this function drops every point with a non-finite coordinate
+ * (e.g. a dropped GPS fix) and simplifies the remaining points, in their original order.
+ * If no finite points remain, an empty array is returned.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_clean_ffi(struct ExternalArray coords,
+                                            double precision);
+
+/**
+ * FFI wrapper for RDP with a tolerance relative to the line's extent, returning simplified geometry **coordinates**
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance, as a fraction of the length of the diagonal of
+ *   the line's bounding box
+ *
+ * A fraction of 0.005 simplifies each line with a tolerance of 0.5% of its extent, so features of
+ * very different sizes are simplified consistently: scaling a line doesn't change which of its
+ * points are retained.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_relative_ffi(struct ExternalArray coords,
+                                               double fraction);
+
+/**
+ * FFI wrapper for RDP, returning at most a given number of simplified geometry **coordinates**
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a `size_t` for the maximum number of points to return. Values below 2 are treated as 2, so
+ *   the endpoints are always retained
+ *
+ * The smallest tolerance producing no more than the requested number of points is used. Input
+ * which is already short enough is returned unchanged.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_max_points_ffi(struct ExternalArray coords,
+                                                 size_t max_points);
+
+/**
+ * FFI wrapper for RDP, retaining approximately a given fraction of the simplified geometry's
+ * **coordinates**
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the fraction of points to retain, which is clamped to (0, 1]
+ *
+ * The tolerance is found by bisection, and the result whose length is closest to the fraction of
+ * the input's points (but never fewer than 2) is returned, so a ratio of 0.2 keeps about 20% of the
+ * points. Input which is already short enough is returned unchanged.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_target_ratio_ffi(struct ExternalArray coords,
+                                                   double keep_ratio);
+
+/**
+ * FFI wrapper for RDP and Visvalingam-Whyatt, returning whichever simplified geometry has fewer
+ * **coordinates**
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the maximum distance of any input point from the output
+ *
+ * The line is simplified as by [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html), and as by
+ * [`simplify_visvalingam_ffi`](fn.simplify_visvalingam_ffi.html) with the largest area (found by
+ * bisection) which keeps every input point within `tolerance` of the output. The result with fewer
+ * points is returned, or the RDP result if they're the same length.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_best_ffi(struct ExternalArray coords,
+                                       double tolerance);
+
+/**
+ * FFI wrapper for RDP, returning simplified geometry **coordinates** which fit in a given number of bytes
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a `size_t` for the maximum size of the returned coordinates, in bytes
+ *
+ * Each returned point occupies 16 bytes, so this is equivalent to calling
+ * [`simplify_rdp_max_points_ffi`](fn.simplify_rdp_max_points_ffi.html) with `max_bytes / 16`
+ * points: the smallest tolerance which fits the budget is used. Input which already fits is
+ * returned unchanged. A budget too small to hold the line's endpoints produces an empty array.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_byte_budget_ffi(struct ExternalArray coords,
+                                                  size_t max_bytes);
+
+/**
+ * FFI wrapper for RDP, returning at least a given number of simplified geometry **coordinates**
+ *
+ * Callers must pass three arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ * - a `size_t` for the minimum number of points to return
+ *
+ * If the tolerance alone would retain fewer points than the minimum, the removed points which are
+ * farthest from the simplified line are restored until the minimum is met. Input which has no more
+ * points than the minimum is returned unchanged.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_floor_ffi(struct ExternalArray coords,
+                                            double precision,
+                                            size_t min_points);
+
+/**
+ * FFI wrapper for direction-independent RDP, returning simplified geometry **coordinates**
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ *
+ * Simplifying the reversed input produces the reversed output, so edges shared between adjacent
+ * polygons remain identical after simplification. Results can differ from those of
+ * [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html) when points are equally distant from a segment.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_symmetric_ffi(struct ExternalArray coords,
+                                                double precision);
+
+/**
+ * FFI wrapper for direction-independent RDP, returning simplified geometry **coordinates**, optionally
+ * in reverse order
+ *
+ * Callers must pass three arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ * - an `int` flag: if it's non-zero, the coordinates are returned last-to-first
+ *
+ * The input is simplified as by [`simplify_rdp_symmetric_ffi`](fn.simplify_rdp_symmetric_ffi.html),
+ * and the result is reversed in place, so callers requiring the opposite orientation don't have to
+ * reverse a copy. The reversed result is exactly the reverse of the forward result, and as the
+ * simplification doesn't depend on direction, it's also identical to the result of simplifying
+ * the reversed input.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_reverse_ffi(struct ExternalArray coords,
+                                              double precision,
+                                              int reverse);
+
+/**
+ * FFI wrapper for radial-distance thinning followed by RDP, returning simplified geometry **coordinates**
+ *
+ * Callers must pass three arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the radial tolerance
+ * - a double-precision `float` for the RDP tolerance
+ *
+ * Points within the radial tolerance of the previously retained point are removed first, and the
+ * remaining points are then simplified using RDP. This removes clusters of near-duplicate points,
+ * which are common in noisy GPS tracks, and which RDP would otherwise retain.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_radial_ffi(struct ExternalArray coords,
+                                             double radial_tol,
+                                             double rdp_tol);
+
+/**
+ * FFI wrapper for RDP after snapping to a grid, returning simplified geometry **coordinates**
+ *
+ * Callers must pass three arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ * - a double-precision `float` for the size of the grid's cells
+ *
+ * Each coordinate is rounded to the nearest multiple of the grid size, consecutive duplicates are
+ * removed, and the result is simplified as by [`simplify_rdp_symmetric_ffi`](fn.simplify_rdp_symmetric_ffi.html).
+ * Boundaries shared by the features of a coverage snap to identical coordinates, so they remain
+ * coincident after simplification, even if features traverse them in opposite directions.
+ * See [`api::simplify_rdp_snapped`](api/fn.simplify_rdp_snapped.html). A grid size which isn't a
+ * finite number greater than zero disables snapping.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_snap_grid_ffi(struct ExternalArray coords,
+                                            double precision,
+                                            double grid_size);
+
+/**
+ * Remove consecutive duplicate points from a line, returning the remaining **coordinates**
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the distance within which points are duplicates
+ *
+ * A point is removed if it's within the given distance of the last point which wasn't, so input
+ * whose points are all duplicates produces a single point, and a distance of 0 removes only exact
+ * duplicates. This is intended for cleaning lines before simplification: runs of duplicate points
+ * are common after snapping, and add work without adding detail.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray dedupe_coords_ffi(struct ExternalArray coords,
+                                       double epsilon);
+
+/**
+ * FFI wrapper for RDP followed by Chaikin smoothing, returning smoothed geometry **coordinates**
+ *
+ * Callers must pass three arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ * - a `size_t` for the number of iterations of smoothing
+ *
+ * The line is simplified as by [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html), then each
+ * iteration of smoothing replaces every corner with two points, roughly doubling the number of
+ * points, so only a few iterations are normally needed. The first and last points of an open line
+ * are unchanged, but a closed line is smoothed as a ring. Zero iterations are equivalent to
+ * `simplify_rdp_ffi`.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_then_smooth_ffi(struct ExternalArray coords,
+                                              double precision,
+                                              size_t iterations);
+
+/**
+ * FFI wrapper for RDP with per-axis scaling, returning simplified geometry **coordinates**
+ *
+ * Callers must pass four arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ * - a double-precision `float` by which x coordinates are multiplied
+ * - a double-precision `float` by which y coordinates are multiplied
+ *
+ * Distances are measured after scaling, so the tolerance is in the scaled units. The retained
+ * coordinates are copied from the input rather than unscaled, so the returned endpoints are
+ * bit-identical to the input's, and can be used as join keys. For longitude and latitude in degrees, passing the
+ * cosine of the line's latitude as the x scale accounts for degrees of longitude shrinking away
+ * from the equator.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_scaled_ffi(struct ExternalArray coords,
+                                             double tolerance,
+                                             double x_scale,
+                                             double y_scale);
+
+/**
+ * FFI wrapper for RDP, retaining vertices at tile boundaries, and returning simplified geometry **coordinates**
+ *
+ * Callers must pass three arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of double-precision `float` x coordinates of vertical
+ *       boundaries, such as tile edges
+ *     - `len`, the number of boundaries. Its type must be `size_t`
+ *
+ * Wherever the line crosses a boundary, the vertex nearest to it is retained, so lines which
+ * continue into an adjacent tile share a vertex at the tile edge, and renderers don't produce
+ * cracks there. The line is simplified between the retained vertices in the same way as
+ * [`simplify_rdp_symmetric_ffi`](fn.simplify_rdp_symmetric_ffi.html). If the boundary array is
+ * empty or a null pointer, no vertices are retained in this way.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses raw pointers which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_boundaries_ffi(struct ExternalArray coords,
+                                                 double precision,
+                                                 struct ExternalArray boundaries);
+
+/**
+ * FFI wrapper for RDP with a tolerance for each vertex, returning simplified geometry **coordinates**
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of double-precision `float` tolerances, one per point
+ *     - `len`, the number of tolerances, which must equal the number of points
+ *
+ * `tolerances[i]` is the distance by which point `i` (the start of the line's `i`th segment) may
+ * deviate from the simplified line, so regions with high tolerances (such as sparse regions) are
+ * simplified aggressively, while regions with low tolerances keep their detail. Each segment is
+ * split at the farthest of the points which deviate from it by more than their tolerance, so
+ * equal tolerances produce the same result as [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html). A
+ * point whose tolerance isn't greater than zero is always retained, and the endpoints' tolerances
+ * are ignored, as they are always retained.
+ *
+ * If either input is invalid, or the number of tolerances doesn't equal the number of points, an
+ * empty array is returned.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses raw pointers which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_vartol_ffi(struct ExternalArray coords,
+                                             struct ExternalArray tolerances);
+
+/**
+ * FFI wrapper for RDP, retaining the extreme vertices, and returning simplified geometry **coordinates**
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ *
+ * The vertices with the smallest and largest x and y coordinates are always retained, so charts
+ * and sparklines keep their highest and lowest points, even where
+ * [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html) would remove them. These are four vertices at
+ * most, and fewer if one vertex has more than one extreme coordinate. If several vertices share an
+ * extreme coordinate, the first is retained. The line is simplified between the retained vertices
+ * in the same way as [`simplify_rdp_symmetric_ffi`](fn.simplify_rdp_symmetric_ffi.html).
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_extrema_ffi(struct ExternalArray coords,
+                                              double precision);
+
+/**
+ * FFI wrapper for RDP, retaining sharp corners, and returning simplified geometry **coordinates**
+ *
+ * Callers must pass three arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ * - a double-precision `float` for the minimum angle, in degrees
+ *
+ * Each vertex's angle is computed from the two input segments meeting at it: it's 180 for a vertex
+ * on a straight line, and 90 for a right-angled corner. Vertices whose angle is less than the
+ * minimum are always retained, however aggressive the tolerance, so semantically important turns
+ * such as street corners survive. The line is simplified between the retained vertices in the same
+ * way as [`simplify_rdp_symmetric_ffi`](fn.simplify_rdp_symmetric_ffi.html). See
+ * [`api::simplify_rdp_corners`](api/fn.simplify_rdp_corners.html).
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_corners_ffi(struct ExternalArray coords,
+                                              double precision,
+                                              double min_angle_deg);
+
+/**
+ * FFI wrapper for RDP, retaining vertices either side of long gaps in time, and returning
+ * simplified geometry **coordinates**
+ *
+ * Callers must pass four arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of double-precision `float` times, in seconds since
+ *       the epoch, one per point
+ *     - `len`, the number of times, which must equal the number of points
+ * - a double-precision `float` for the tolerance
+ * - a double-precision `float` for the longest gap in seconds which may be simplified away
+ *
+ * Wherever consecutive points are more than `max_gap_s` seconds apart, such as either side of a
+ * stop in a GPS track, both are retained, even if they're geometrically redundant. The line is
+ * simplified between the retained points in the same way as
+ * [`simplify_rdp_symmetric_ffi`](fn.simplify_rdp_symmetric_ffi.html).
+ *
+ * If either input is invalid, or the number of times doesn't equal the number of points, an
+ * empty array is returned.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses raw pointers which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_timed_ffi(struct ExternalArray coords,
+                                            struct ExternalArray times,
+                                            double precision,
+                                            double max_gap_s);
+
+/**
+ * FFI wrapper for RDP, always retaining marked points, and returning simplified geometry
+ * **coordinates**
+ *
+ * Callers must pass three arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of `uint8_t` flags, one per point
+ *     - `len`, the number of flags, which must equal the number of points
+ * - a double-precision `float` for the tolerance
+ *
+ * Every point whose flag is non-zero, such as a junction or a labelled point, is retained. The
+ * line is simplified independently between the retained points, in the same way as
+ * [`simplify_rdp_symmetric_ffi`](fn.simplify_rdp_symmetric_ffi.html).
+ *
+ * If either input is invalid, or the number of flags doesn't equal the number of points, an
+ * empty array is returned.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses raw pointers which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_pinned_ffi(struct ExternalArray coords,
+                                             struct ExternalArray pinned,
+                                             double precision);
+
+/**
+ * FFI wrapper for RDP, returning the number of points retained for each of several tolerances
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of double-precision `float` tolerances
+ *     - `len`, the number of tolerances. Its type must be `size_t`
+ *
+ * The result is an array of `size_t` point counts, one for each tolerance, in the same order. The
+ * tolerances needn't be sorted. Each count is the length of the array
+ * [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html) would return for that tolerance, but the
+ * geometry is only simplified once. If either input is invalid, an empty array is returned.
+ *
+ * Implementations calling this function **must** call [`drop_usize_array`](fn.drop_usize_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses raw pointers which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_sweep_ffi(struct ExternalArray coords,
+                                            struct ExternalArray tolerances);
+
+/**
+ * FFI wrapper for RDP, returning the significance of each interior point
+ *
+ * Callers must pass a [Struct](struct.Array.html) with two fields:
+ *
+ * - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ * - `len`, the length of the array being passed. Its type must be `size_t`
+ *
+ * The result is an array of `[index, significance]` pairs of double-precision `float`s, one for
+ * each point apart from the first and last, in order of decreasing significance. A point's
+ * significance is the tolerance below which [`simplify_rdp_idx_ffi`](fn.simplify_rdp_idx_ffi.html)
+ * retains it, so for any positive tolerance, the points retained are the endpoints and the
+ * indices of the leading pairs whose significance is greater than the tolerance. This lets
+ * interactive applications reveal or hide detail without simplifying again.
+ * See [`api::rdp_significance`](api/fn.rdp_significance.html). Indices are exact for lines with
+ * fewer than 2<sup>53</sup> points.
+ *
+ * If the input is invalid, or has fewer than three points, an empty array is returned.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray rdp_significance_ffi(struct ExternalArray coords);
+
+/**
+ * FFI wrapper for RDP, returning simplified geometry **coordinates** at several levels of detail
+ *
+ * Callers must pass three arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of double-precision `float` tolerances, one per level
+ *     - `len`, the number of tolerances. Its type must be `size_t`
+ * - a pointer to an [`InternalArray`](struct.InternalArray.html), which receives the offsets
+ *
+ * The returned `Array` contains the coordinates retained at every tolerance, concatenated in the
+ * order of the tolerances. The offsets array contains one more `size_t` value than there are
+ * tolerances: the coordinates retained at tolerance `i` are the elements from `offsets[i]` up to
+ * (but not including) `offsets[i + 1]`. Each level is identical to the result of
+ * [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html) with its tolerance, but the line is only traversed
+ * once, and each level is a subset of every level with a lower tolerance.
+ *
+ * If either input is invalid, or `offsets_out` is a null pointer, an empty array is returned,
+ * and `offsets_out` (if it isn't null) receives an empty array.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array`, and [`drop_usize_array`](fn.drop_usize_array.html) with the offsets,
+ * in order to free the memory they allocate.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses raw pointers which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_lod_ffi(struct ExternalArray coords,
+                                          struct ExternalArray tolerances,
+                                          struct InternalArray *offsets_out);
+
+/**
+ * FFI wrapper for RDP, writing simplified geometry **coordinates** into a caller-provided buffer
+ *
+ * Callers must pass four arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ * - a [Struct](struct.Array.html) describing the output buffer, with two fields:
+ *     - `data`, a void pointer to writable memory for `len` coordinate pairs
+ *     - `len`, the capacity of the buffer, in coordinate pairs
+ * - a pointer to a `size_t`, into which the number of retained points is written
+ *
+ * The return value is an [`FfiError`](enum.FfiError.html) code. If the output buffer is too small,
+ * `BufferTooSmall` is returned, nothing is written to the buffer, and the required capacity is
+ * written to `out_len`, so callers can retry with a larger buffer. If the input is invalid,
+ * 0 is written to `out_len`.
+ *
+ * No memory is allocated across the FFI boundary, so there is nothing to drop.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses raw pointers which could contain arbitrary data
+ */
+int simplify_rdp_into_ffi(struct ExternalArray coords,
+                          double precision,
+                          struct InternalArray out,
+                          size_t *out_len);
+
+/**
+ * FFI wrapper for RDP, serialising simplified geometry **coordinates** into a caller-provided
+ * byte buffer
+ *
+ * Callers must pass five arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ * - a pointer to writable memory, such as a memory-mapped file or shared memory. It needn't be
+ *   aligned
+ * - a `size_t` for the capacity of the buffer, in bytes
+ * - a pointer to a `size_t`, into which the number of bytes written is written
+ *
+ * The result is written in little-endian byte order, whatever the platform's:
+ *
+ * | Offset       | Size | Type  | Value                        |
+ * |--------------|------|-------|------------------------------|
+ * | 0            | 4    | `u32` | the number of points, `n`    |
+ * | 4 + 16i      | 8    | `f64` | the x coordinate of point i  |
+ * | 4 + 16i + 8  | 8    | `f64` | the y coordinate of point i  |
+ *
+ * so the result occupies `4 + 16n` bytes. The return value is an [`FfiError`](enum.FfiError.html)
+ * code. If the buffer is too small, `BufferTooSmall` is returned, nothing is written to the
+ * buffer, and the required capacity is written to `written`, so callers can retry with a larger
+ * buffer. If more points are retained than a `u32` can count, `IndexOverflow` is returned. If the
+ * input is invalid, 0 is written to `written`.
+ *
+ * No memory is allocated across the FFI boundary, so there is nothing to drop.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses raw pointers which could contain arbitrary data
+ */
+int simplify_rdp_write_ffi(struct ExternalArray coords,
+                           double precision,
+                           uint8_t *out_buf,
+                           size_t out_cap,
+                           size_t *written);
+
+/**
+ * FFI wrapper for Visvalingam-Whyatt, returning simplified geometry **coordinates**
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the epsilon
+ *
+ * The epsilon is an area: points are removed until every remaining point forms a triangle with its
+ * neighbours whose area is greater than the epsilon.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_visvalingam_ffi(struct ExternalArray coords,
+                                              double precision);
+
+/**
+ * FFI wrapper for Visvalingam-Whyatt with an epsilon relative to the line's length, returning
+ * simplified geometry **coordinates**
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the epsilon, as a fraction of the square of the line's length
+ *
+ * The line is simplified as by [`simplify_visvalingam_ffi`](fn.simplify_visvalingam_ffi.html), with
+ * an epsilon of `area_fraction` multiplied by the square of the line's total length. Areas scale
+ * with the square of a line's size, so scaling a line doesn't change which of its points are
+ * retained.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_vw_relative_ffi(struct ExternalArray coords,
+                                              double area_fraction);
+
+/**
+ * FFI wrapper for Visvalingam-Whyatt, returning simplified geometry **indices**
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the epsilon
+ *
+ * Implementations calling this function **must** call [`drop_usize_array`](fn.drop_usize_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_visvalingam_idx_ffi(struct ExternalArray coords,
+                                                  double precision);
+
+/**
+ * FFI wrapper for topology-preserving Visvalingam-Whyatt, returning simplified geometry **coordinates**.
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the epsilon
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_visvalingamp_ffi(struct ExternalArray coords,
+                                               double precision);
+
+/**
+ * FFI wrapper for topology-preserving Visvalingam-Whyatt, returning simplified geometry **indices**.
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the epsilon
+ *
+ * Whenever removing a point would make the simplified line cross itself, the point preceding it
+ * is removed too, which usually removes the crossing. The result may therefore differ from that
+ * of [`simplify_visvalingam_idx_ffi`](fn.simplify_visvalingam_idx_ffi.html), and may have more
+ * points. This is a heuristic rather than a guarantee: the first point is never removed, so a
+ * crossing introduced by removing the second point remains.
+ *
+ * Implementations calling this function **must** call [`drop_usize_array`](fn.drop_usize_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_visvalingamp_idx_ffi(struct ExternalArray coords,
+                                                   double precision);
+
+/**
+ * FFI wrapper for RDP, returning simplified geometry **coordinates** and an error code
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ *
+ * The input is validated before simplification: if `data` is a null pointer, `len` is 0, or any
+ * coordinate is NaN or infinite, the returned [`FfiResult`](struct.FfiResult.html) contains an empty
+ * array and a non-zero `error`.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `array` field, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct FfiResult simplify_rdp_checked_ffi(struct ExternalArray coords,
+                                          double precision);
+
+/**
+ * FFI wrapper for RDP, returning simplified geometry **indices** and an error code
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ *
+ * The input is validated before simplification: if `data` is a null pointer, `len` is 0, or any
+ * coordinate is NaN or infinite, the returned [`FfiResult`](struct.FfiResult.html) contains an empty
+ * array and a non-zero `error`.
+ *
+ * Implementations calling this function **must** call [`drop_usize_array`](fn.drop_usize_array.html)
+ * with the returned `array` field, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct FfiResult simplify_rdp_idx_checked_ffi(struct ExternalArray coords,
+                                              double precision);
+
+/**
+ * FFI wrapper for Visvalingam-Whyatt, returning simplified geometry **coordinates** and an error code
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the epsilon
+ *
+ * The input is validated before simplification: if `data` is a null pointer, `len` is 0, or any
+ * coordinate is NaN or infinite, the returned [`FfiResult`](struct.FfiResult.html) contains an empty
+ * array and a non-zero `error`.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `array` field, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct FfiResult simplify_visvalingam_checked_ffi(struct ExternalArray coords,
+                                                  double precision);
+
+/**
+ * FFI wrapper for Visvalingam-Whyatt, returning simplified geometry **indices** and an error code
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the epsilon
+ *
+ * The input is validated before simplification: if `data` is a null pointer, `len` is 0, or any
+ * coordinate is NaN or infinite, the returned [`FfiResult`](struct.FfiResult.html) contains an empty
+ * array and a non-zero `error`.
+ *
+ * Implementations calling this function **must** call [`drop_usize_array`](fn.drop_usize_array.html)
+ * with the returned `array` field, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct FfiResult simplify_visvalingam_idx_checked_ffi(struct ExternalArray coords,
+                                                      double precision);
+
+/**
+ * FFI wrapper for topology-preserving Visvalingam-Whyatt, returning simplified geometry **coordinates** and an error code
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the epsilon
+ *
+ * The input is validated before simplification: if `data` is a null pointer, `len` is 0, or any
+ * coordinate is NaN or infinite, the returned [`FfiResult`](struct.FfiResult.html) contains an empty
+ * array and a non-zero `error`.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `array` field, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct FfiResult simplify_visvalingamp_checked_ffi(struct ExternalArray coords,
+                                                   double precision);
+
+/**
+ * FFI wrapper for topology-preserving Visvalingam-Whyatt, returning simplified geometry **indices** and an error code
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the epsilon
+ *
+ * The input is validated before simplification: if `data` is a null pointer, `len` is 0, or any
+ * coordinate is NaN or infinite, the returned [`FfiResult`](struct.FfiResult.html) contains an empty
+ * array and a non-zero `error`.
+ *
+ * Implementations calling this function **must** call [`drop_usize_array`](fn.drop_usize_array.html)
+ * with the returned `array` field, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct FfiResult simplify_visvalingamp_idx_checked_ffi(struct ExternalArray coords,
+                                                       double precision);
+
+/**
+ * FFI wrapper for RDP, returning simplified 3D geometry **coordinates**
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point 3D point coordinates: `[[1.0, 2.0, 3.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ *
+ * Distances are computed using the x and y coordinates only; the z coordinate of each retained
+ * point is carried through to the output unchanged. Use
+ * [`simplify_rdp_3d_z_ffi`](fn.simplify_rdp_3d_z_ffi.html) if z should be considered.
+ *
+ * Implementations calling this function **must** call [`drop_float_array_3d`](fn.drop_float_array_3d.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_3d_ffi(struct ExternalArray coords,
+                                         double precision);
+
+/**
+ * FFI wrapper for RDP, returning simplified 3D geometry **coordinates**, taking z into account
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point 3D point coordinates: `[[1.0, 2.0, 3.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ *
+ * Unlike [`simplify_rdp_3d_ffi`](fn.simplify_rdp_3d_ffi.html), distances are computed in three
+ * dimensions, so points which deviate only in z can be retained.
+ *
+ * Implementations calling this function **must** call [`drop_float_array_3d`](fn.drop_float_array_3d.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_3d_z_ffi(struct ExternalArray coords,
+                                           double precision);
+
+/**
+ * FFI wrapper for RDP, passing each retained point to a callback
+ *
+ * Callers must pass four arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ * - a [`PointCallback`](type.PointCallback.html), which is called once for each retained point, in order
+ * - a void pointer, which is passed to each call of the callback unchanged. It may be null
+ *
+ * If the input is invalid or the callback is a null pointer, the callback isn't called.
+ * No memory is allocated across the FFI boundary, so there is nothing to drop.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+void simplify_rdp_foreach_ffi(struct ExternalArray coords,
+                              double precision,
+                              PointCallback sink,
+                              void *user);
+
+/**
+ * Compute the maximum deviation of a simplified geometry from the original
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) containing the original coordinates, with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a [Struct](struct.Array.html) containing the simplified coordinates, in the same format
+ *
+ * The result is the maximum distance of any original point from the simplified line. NaN is
+ * returned if either array is invalid or empty, or if the simplified array is longer than the
+ * original.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+double max_deviation_ffi(struct ExternalArray original,
+                         struct ExternalArray simplified);
+
+/**
+ * Check whether a simplified geometry is within a tolerance of the original
+ *
+ * Callers must pass three arguments:
+ *
+ * - a [Struct](struct.Array.html) containing the original coordinates, with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a [Struct](struct.Array.html) containing the simplified coordinates, in the same format
+ * - a double-precision `float` for the tolerance
+ *
+ * 1 is returned if every original point is within the tolerance of the simplified line, and 0
+ * otherwise. Checking stops at the first point which is too far away, so this is cheaper than
+ * comparing the result of [`max_deviation_ffi`](fn.max_deviation_ffi.html) with the tolerance.
+ * 0 is returned in the cases in which `max_deviation_ffi` returns NaN.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses raw pointers which could contain arbitrary data
+ */
+int within_tolerance_ffi(struct ExternalArray original,
+                         struct ExternalArray simplified,
+                         double tolerance);
+
+/**
+ * Check whether a geometry is already simplified at a tolerance
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ *
+ * 1 is returned if [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html) would return every point
+ * unchanged, and 0 otherwise. Checking stops at the first point which would be removed, so
+ * callers can cheaply skip redundant simplification. If the input is invalid, 0 is returned.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+int is_simplified_ffi(struct ExternalArray coords,
+                      double precision);
+
+/**
+ * Compute the symmetric Hausdorff distance between two geometries
+ *
+ * Callers must pass two [Structs](struct.Array.html), each with two fields:
+ *
+ * - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ * - `len`, the length of the array being passed. Its type must be `size_t`
+ *
+ * The result is the greater of the maximum distance of any vertex of either geometry from the
+ * other geometry's segments. Unlike [`max_deviation_ffi`](fn.max_deviation_ffi.html), it is
+ * symmetric, so it also accounts for simplified segments which stray from the original geometry.
+ * NaN is returned if either array is invalid or empty.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses raw pointers which could contain arbitrary data
+ */
+double hausdorff_distance_ffi(struct ExternalArray a,
+                              struct ExternalArray b);
+
+/**
+ * FFI wrapper for RDP using a caller-supplied distance metric, returning simplified geometry **coordinates**
+ *
+ * Callers must pass three arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ * - a [`DistanceCallback`](type.DistanceCallback.html), which is called to compute the distance of
+ *   each candidate point from the segment under consideration, in place of the Euclidean distance
+ *
+ * A point is retained if its distance is greater than the tolerance. If the callback is a null
+ * pointer, an empty array is returned.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_custom_ffi(struct ExternalArray coords,
+                                             double precision,
+                                             DistanceCallback metric);
+
+/**
+ * FFI wrapper for RDP, returning simplified single-precision geometry **coordinates**
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of single-precision point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a single-precision `float` for the tolerance
+ *
+ * Implementations calling this function **must** call [`drop_float_array_f32`](fn.drop_float_array_f32.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ * [`drop_float_array`](fn.drop_float_array.html) **must not** be used for this purpose.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_ffi_f32(struct ExternalArray coords,
+                                          float precision);
+
+/**
+ * FFI wrapper for Visvalingam-Whyatt, returning simplified single-precision geometry **coordinates**
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of single-precision point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a single-precision `float` for the epsilon
+ *
+ * Implementations calling this function **must** call [`drop_float_array_f32`](fn.drop_float_array_f32.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ * [`drop_float_array`](fn.drop_float_array.html) **must not** be used for this purpose.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_visvalingam_ffi_f32(struct ExternalArray coords,
+                                                  float precision);
+
+/**
+ * FFI wrapper for topology-preserving Visvalingam-Whyatt, returning simplified single-precision geometry **coordinates**
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of single-precision point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a single-precision `float` for the epsilon
+ *
+ * Implementations calling this function **must** call [`drop_float_array_f32`](fn.drop_float_array_f32.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ * [`drop_float_array`](fn.drop_float_array.html) **must not** be used for this purpose.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_visvalingamp_ffi_f32(struct ExternalArray coords,
+                                                   float precision);
+
+/**
+ * FFI wrapper for RDP, returning simplified integer geometry **coordinates**
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of 32-bit signed integer point coordinates: `[[1, 2], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ *
+ * This suits integer coordinate spaces, such as vector tiles. Distances are computed using
+ * double-precision `float`s, which represent every 32-bit integer exactly, and the returned
+ * coordinates are copied from the input, so they are never rounded. If `data` is a null pointer or
+ * `len` is 0, an empty array is returned.
+ *
+ * Implementations calling this function **must** call [`drop_i32_array`](fn.drop_i32_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ * [`drop_float_array`](fn.drop_float_array.html) **must not** be used for this purpose.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_i32_ffi(struct ExternalArray coords,
+                                          double precision);
+
+/**
+ * Free memory which has been allocated across the FFI boundary by:
+ * - simplify_ffi
+ * - simplify_rdp_ffi
+ * - simplify_rdp_cancellable_ffi
+ * - simplify_rdp_series_ffi
+ * - simplify_rdp_range_ffi
+ * - merge_simplify_rdp_ffi
+ * - simplify_rdp_max_segment_ffi
+ * - simplify_rdp_density_ffi
+ * - simplify_rdp_stats_ffi
+ * - simplify_rdp_bbox_ffi
+ * - simplify_rdp_hull_ffi
+ * - simplify_rdp_length_ffi
+ * - simplify_rdp_bounded_ffi
+ * - simplify_rdp_both_ffi (the coordinates)
+ * - simplify_rdp_clean_ffi
+ * - simplify_rdp_relative_ffi
+ * - simplify_rdp_max_points_ffi
+ * - simplify_rdp_target_ratio_ffi
+ * - simplify_best_ffi
+ * - simplify_rdp_byte_budget_ffi
+ * - simplify_rdp_floor_ffi
+ * - simplify_rdp_symmetric_ffi
+ * - simplify_rdp_reverse_ffi
+ * - simplify_rdp_radial_ffi
+ * - simplify_snap_grid_ffi
+ * - dedupe_coords_ffi
+ * - simplify_then_smooth_ffi
+ * - simplify_rdp_scaled_ffi
+ * - simplify_rdp_boundaries_ffi
+ * - simplify_rdp_vartol_ffi
+ * - simplify_rdp_extrema_ffi
+ * - simplify_rdp_corners_ffi
+ * - simplify_rdp_timed_ffi
+ * - simplify_rdp_pinned_ffi
+ * - rdp_significance_ffi
+ * - simplify_rdp_lod_ffi (the coordinates)
+ * - simplify_visvalingam_ffi
+ * - simplify_vw_relative_ffi
+ * - simplify_visvalingamp_ffi
+ * - simplify_rdp_checked_ffi (the `array` field)
+ * - simplify_visvalingam_checked_ffi (the `array` field)
+ * - simplify_visvalingamp_checked_ffi (the `array` field)
+ * - simplify_rdp_custom_ffi
+ * - simplify_rdp_default_ffi
+ * - simplify_rdp_geodesic_ffi
+ * - simplify_rdp_ring_ffi
+ * - simplify_ring_oriented_ffi
+ * - simplify_ring_area_delta_ffi
+ * - rdp_stream_finish
+ * - simplify_vw_weighted_ffi
+ * - simplify_visvalingam_tiebreak_ffi
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+void drop_float_array(struct InternalArray arr);
+
+/**
+ * Free memory which has been allocated across the FFI boundary by:
+ * - simplify_rdp_ffi_f32
+ * - simplify_visvalingam_ffi_f32
+ * - simplify_visvalingamp_ffi_f32
+ *
+ * This function **must not** be used to free arrays returned by the double-precision
+ * functions: use [`drop_float_array`](fn.drop_float_array.html) for those.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+void drop_float_array_f32(struct InternalArray arr);
+
+/**
+ * Free memory which has been allocated across the FFI boundary by:
+ * - simplify_rdp_3d_ffi
+ * - simplify_rdp_3d_z_ffi
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+void drop_float_array_3d(struct InternalArray arr);
+
+/**
+ * Free memory which has been allocated across the FFI boundary by:
+ * - simplify_rdp_i32_ffi
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+void drop_i32_array(struct InternalArray arr);
+
+/**
+ * Free memory which has been allocated across the FFI boundary by:
+ * - simplify_rdp_flat_ffi
+ * - simplify_rdp_xy_ffi (both the returned array and the y coordinates)
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+void drop_flat_f64_array(struct InternalArray arr);
+
+/**
+ * Free memory which has been allocated across the FFI boundary by:
+ * - simplify_rdp_idx_ffi
+ * - simplify_visvalingam_idx_ffi
+ * - simplify_visvalingamp_idx_ffi
+ * - simplify_rdp_idx_packed_ffi (both the returned array and the offsets)
+ * - simplify_rdp_sweep_ffi
+ * - simplify_rdp_removed_idx_ffi
+ * - simplify_rdp_segment_stats_ffi
+ * - simplify_rdp_both_ffi (the indices)
+ * - simplify_rdp_lod_ffi (the offsets)
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
 void drop_usize_array(struct InternalArray arr);
+
+/**
+ * Free memory which has been allocated across the FFI boundary by:
+ * - simplify_rdp_idx_u32_ffi
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+void drop_u32_array(struct InternalArray arr);
+
+/**
+ * Free memory which has been allocated across the FFI boundary by:
+ * - simplify_rdp_mask_ffi
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+void drop_u8_array(struct InternalArray arr);
+
+/**
+ * Free memory which has been allocated across the FFI boundary by:
+ * - simplify_vw_areas_ffi
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+void drop_double_array(struct InternalArray arr);
+
+/**
+ * FFI wrapper for RDP, accepting and returning [WKT](https://en.wikipedia.org/wiki/Well-known_text_representation_of_geometry)
+ *
+ * Callers must pass two arguments:
+ *
+ * - a pointer to a nul-terminated, UTF-8 string containing a WKT `LINESTRING`: `LINESTRING(1.0 2.0, ...)`
+ * - a double-precision `float` for the tolerance
+ *
+ * The simplified geometry is returned as a newly allocated, nul-terminated WKT string.
+ * If the input is a null pointer, isn't valid UTF-8, or isn't a valid WKT `LINESTRING`,
+ * a null pointer is returned.
+ *
+ * Implementations calling this function **must** call [`drop_cstring`](fn.drop_cstring.html)
+ * with the returned pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+char *simplify_rdp_wkt_ffi(const char *wkt,
+                           double precision);
+
+#if defined(RDP_GEOJSON)
+/**
+ * FFI wrapper for RDP, accepting and returning [GeoJSON](https://geojson.org)
+ *
+ * Callers must pass two arguments:
+ *
+ * - a pointer to a nul-terminated, UTF-8 string containing either a GeoJSON `LineString` geometry,
+ *   or a `Feature` whose geometry is a `LineString`
+ * - a double-precision `float` for the tolerance
+ *
+ * The result is returned as a newly allocated, nul-terminated GeoJSON string of the same kind as
+ * the input: a `Feature` input retains its properties. If the input is a null pointer, isn't
+ * valid GeoJSON, or doesn't contain a `LineString`, a null pointer is returned.
+ *
+ * This function is only available if the `geojson` feature is enabled.
+ *
+ * Implementations calling this function **must** call [`drop_cstring`](fn.drop_cstring.html)
+ * with the returned pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+char *simplify_rdp_geojson_ffi(const char *json,
+                               double precision);
+#endif
+
+#if defined(RDP_WKB)
+/**
+ * FFI wrapper for RDP, accepting and returning [WKB](https://libgeos.org/specifications/wkb/)
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to the bytes of a two-dimensional WKB `LineString`, in either byte order
+ *     - `len`, the number of bytes being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ *
+ * The simplified geometry is returned as the bytes of a WKB `LineString`, using the same byte
+ * order as the input. If the input isn't valid WKB, isn't a `LineString`, has Z or M
+ * coordinates, or has a NaN or infinite coordinate, an empty array is returned.
+ *
+ * This function is only available if the `wkb` feature is enabled.
+ *
+ * Implementations calling this function **must** call [`drop_byte_array`](fn.drop_byte_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_wkb_ffi(struct ExternalArray wkb,
+                                          double precision);
+#endif
+
+/**
+ * FFI wrapper for RDP, returning simplified geometry **coordinates** as CSV text
+ *
+ * Callers must pass three arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ * - a `bool`: if it's true, the text begins with an `x,y` header row
+ *
+ * The simplified geometry is returned as a newly allocated, nul-terminated string, with one
+ * `x,y` row per point, each terminated by `\n`. Coordinates are written with as many digits as
+ * are needed to parse them back to the same `double`s. Empty input produces only the header row
+ * (or an empty string, without it). If the input is otherwise invalid, a null pointer is returned.
+ *
+ * Implementations calling this function **must** call [`drop_cstring`](fn.drop_cstring.html)
+ * with the returned pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+char *simplify_rdp_csv_ffi(struct ExternalArray coords,
+                           double precision,
+                           bool header);
+
+/**
+ * Free memory which has been allocated across the FFI boundary by:
+ * - simplify_rdp_wkt_ffi
+ * - simplify_rdp_geojson_ffi
+ * - simplify_rdp_csv_ffi
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+void drop_cstring(char *s);
+
+#if defined(RDP_WKB)
+/**
+ * Free memory which has been allocated across the FFI boundary by:
+ * - simplify_rdp_wkb_ffi
+ *
+ * This function is only available if the `wkb` feature is enabled.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+void drop_byte_array(struct InternalArray arr);
+#endif
+
+/**
+ * FFI wrapper for RDP on geographic coordinates, returning simplified geometry **coordinates**
+ *
+ * Callers must pass three arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates in degrees:
+ *       `[[-0.70, 52.22], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance, in **metres**
+ * - an `int` declaring the order of each pair of coordinates, as a [`CoordOrder`](enum.CoordOrder.html):
+ *   `0` for (longitude, latitude), or `1` for (latitude, longitude)
+ *
+ * Distances between points and segments are computed on a sphere, using the haversine formula.
+ * Longitudes needn't be continuous: a line crossing the antimeridian, from 179.9 to -179.9, is
+ * measured along its short segment, just as if its longitudes had been unwrapped to 180.1.
+ * Nothing about the coordinates reveals their order, so declaring the wrong one isn't detected:
+ * longitudes are treated as latitudes and vice versa, and distances are computed between the
+ * wrong places, producing incorrect results. If `coord_order` isn't a valid `CoordOrder` value, an
+ * empty array is returned. The retained coordinates are copied from the input, in their original
+ * order, so the returned endpoints are bit-identical to the input's.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_geodesic_ffi(struct ExternalArray coords,
+                                               double tolerance_m,
+                                               int coord_order);
+
+/**
+ * Create a new pool of output buffers for [`simplify_rdp_pooled_ffi`](fn.simplify_rdp_pooled_ffi.html)
+ *
+ * Implementations calling this function **must** call [`rdp_pool_free`](fn.rdp_pool_free.html)
+ * with the returned pointer, in order to free the memory it allocates.
+ */
+struct RdpPool *rdp_pool_new(void);
+
+/**
+ * FFI wrapper for RDP, returning simplified geometry **coordinates** in a buffer drawn from a pool
+ *
+ * Callers must pass three arguments:
+ *
+ * - a pointer returned by [`rdp_pool_new`](fn.rdp_pool_new.html)
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ *
+ * If the pool pointer is null or the input is invalid, an empty array is returned.
+ *
+ * Implementations calling this function **must** call
+ * [`drop_pooled_float_array`](fn.drop_pooled_float_array.html) with the same pool and the returned
+ * `Array`, in order to return its buffer to the pool. It **must not** be passed to
+ * [`drop_float_array`](fn.drop_float_array.html).
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses raw pointers which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_pooled_ffi(struct RdpPool *pool,
+                                             struct ExternalArray coords,
+                                             double precision);
+
+/**
+ * Return memory which has been allocated across the FFI boundary by:
+ * - simplify_rdp_pooled_ffi
+ *
+ * to the pool it was drawn from, so it can be reused.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses raw pointers which could contain arbitrary data
+ */
+void drop_pooled_float_array(struct RdpPool *pool,
+                             struct InternalArray arr);
+
+/**
+ * Free a pool created by [`rdp_pool_new`](fn.rdp_pool_new.html)
+ *
+ * Any arrays drawn from the pool which haven't been returned to it are also freed, and must not be
+ * used afterwards.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+void rdp_pool_free(struct RdpPool *pool);
+
+/**
+ * FFI wrapper for RDP on a closed ring, returning simplified geometry **coordinates**
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance
+ *
+ * The input is treated as a closed ring: if its first and last coordinates differ, it is closed
+ * by appending its first coordinate. The output's first and last coordinates are always identical,
+ * and it always has at least 4 points: if simplification would leave fewer, the (closed) input is
+ * returned. If the closed input has fewer than 4 points, it can't form a valid ring, and an empty
+ * array is returned.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_ring_ffi(struct ExternalArray coords,
+                                           double precision);
+
+/**
+ * FFI wrapper for RDP on a closed ring, returning simplified geometry **coordinates** with a given
+ * orientation
+ *
+ * Callers must pass three arguments:
+ *
+ * - a [Struct](struct.Array.html) describing the ring, in the same way as for
+ *   [`simplify_rdp_ring_ffi`](fn.simplify_rdp_ring_ffi.html)
+ * - a double-precision `float` for the tolerance
+ * - an `int`: if it's non-zero, the output is wound counter-clockwise, as OGC requires of exterior
+ *   rings. Otherwise, it's wound clockwise, as OGC requires of interior rings
+ *
+ * The ring is simplified as by [`simplify_rdp_ring_ffi`](fn.simplify_rdp_ring_ffi.html), then
+ * reversed if its winding order, as determined by geo, doesn't match the requested one. A ring
+ * which encloses no area has no winding order, and is returned as it is. If the input can't form a
+ * valid ring, an empty array is returned.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_ring_oriented_ffi(struct ExternalArray coords,
+                                                double precision,
+                                                int want_ccw);
+
+/**
+ * FFI wrapper for RDP on a closed ring, returning simplified geometry **coordinates** and the
+ * change in area
+ *
+ * Callers must pass three arguments:
+ *
+ * - a [Struct](struct.Array.html) describing the ring, in the same way as for
+ *   [`simplify_rdp_ring_ffi`](fn.simplify_rdp_ring_ffi.html)
+ * - a double-precision `float` for the tolerance
+ * - a pointer to a `double`, into which the area of the simplified ring minus the area of the
+ *   (closed) input is written. A null pointer is ignored.
+ *
+ * The ring is simplified as by [`simplify_rdp_ring_ffi`](fn.simplify_rdp_ring_ffi.html). Areas are
+ * computed by geo, and are unsigned, so the difference doesn't depend on the ring's orientation:
+ * it's negative if simplification lost area, and positive if it gained area, such as by filling
+ * in a notch. The difference is in the square of the coordinates' units. If the input can't form a
+ * valid ring, an empty array is returned, and NaN is written.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses raw pointers which could contain arbitrary data
+ */
+struct InternalArray simplify_ring_area_delta_ffi(struct ExternalArray coords,
+                                                  double precision,
+                                                  double *area_delta_out);
+
+/**
+ * Compute the area of the symmetric difference between a closed ring and its RDP simplification
+ *
+ * Callers must pass two arguments:
+ *
+ * - a [Struct](struct.Array.html) describing the ring, in the same way as for
+ *   [`simplify_rdp_ring_ffi`](fn.simplify_rdp_ring_ffi.html)
+ * - a double-precision `float` for the tolerance
+ *
+ * The ring is simplified as by [`simplify_rdp_ring_ffi`](fn.simplify_rdp_ring_ffi.html), and the
+ * result is the area covered by exactly one of the (closed) input and the simplified ring, computed
+ * using geo's boolean operations. Unlike the change in area reported by
+ * [`simplify_ring_area_delta_ffi`](fn.simplify_ring_area_delta_ffi.html), gains and losses don't
+ * cancel out, so it measures how far the simplified ring strays from the input. The area is in the
+ * square of the coordinates' units.
+ *
+ * Boolean operations require valid polygons, so NaN is returned if either ring intersects itself,
+ * as can happen when simplification moves an edge across another part of the ring. Checking this
+ * compares every pair of segments, so the cost is quadratic in the number of points. NaN is also
+ * returned if the input can't form a valid ring.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+double ring_symdiff_area_ffi(struct ExternalArray coords,
+                             double precision);
+
+/**
+ * FFI wrapper for RDP on a polygon with holes, returning simplified geometry **coordinates**
+ *
+ * Callers must pass three arguments:
+ *
+ * - a [Struct](struct.Array.html) describing the exterior ring, in the same way as for
+ *   [`simplify_rdp_ring_ffi`](fn.simplify_rdp_ring_ffi.html)
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a pointer to an array of [Structs](struct.ExternalArray.html), each of which
+ *       describes an interior ring in the same way as the exterior
+ *     - `len`, the number of interior rings. Its type must be `size_t`
+ * - a double-precision `float` for the tolerance, which is applied to every ring
+ *
+ * Each ring is simplified as by [`simplify_rdp_ring_ffi`](fn.simplify_rdp_ring_ffi.html), so a
+ * ring which would collapse to fewer than 4 points is returned unchanged. The returned `Array`'s
+ * `data` field points to `len` `InternalArray`s: the exterior, followed by the interior rings in
+ * the same order as the input, so the number of rings is preserved. An interior ring which can't
+ * form a valid ring produces an empty array in its slot. If the exterior can't form a valid ring,
+ * an empty `Array` is returned. A null interior ring pointer is treated as a polygon without holes.
+ *
+ * Implementations calling this function **must** call [`drop_multi_float_array`](fn.drop_multi_float_array.html)
+ * with the returned `Array`, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses raw pointers which could contain arbitrary data
+ */
+struct InternalArray simplify_rdp_polygon_ffi(struct ExternalArray exterior,
+                                              struct ExternalArray interiors,
+                                              double precision);
+
+/**
+ * Create a new streaming RDP simplification
+ *
+ * Callers must pass a double-precision `float` for the tolerance.
+ *
+ * Points are added using [`rdp_stream_push`](fn.rdp_stream_push.html), and the simplified line is
+ * retrieved using [`rdp_stream_finish`](fn.rdp_stream_finish.html). Decisions made at the boundaries
+ * of the stream's internal windows may differ from those made when simplifying the whole line
+ * using [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html), but every input point remains within the
+ * tolerance of the output.
+ *
+ * Implementations calling this function **must** call [`rdp_stream_free`](fn.rdp_stream_free.html)
+ * with the returned pointer, in order to free the memory it allocates.
+ */
+struct RdpStream *rdp_stream_new(double precision);
+
+/**
+ * Add a chunk of points to a streaming RDP simplification
+ *
+ * Callers must pass two arguments:
+ *
+ * - a pointer returned by [`rdp_stream_new`](fn.rdp_stream_new.html)
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ *
+ * The return value is an [`FfiError`](enum.FfiError.html) code. If it is anything other than
+ * `Success`, the chunk has not been added.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses raw pointers which could contain arbitrary data
+ */
+int rdp_stream_push(struct RdpStream *stream,
+                    struct ExternalArray chunk);
+
+/**
+ * Finish a streaming RDP simplification, returning simplified geometry **coordinates**
+ *
+ * Callers must pass a pointer returned by [`rdp_stream_new`](fn.rdp_stream_new.html). The stream is
+ * left empty, and can be reused. If the pointer is null, an empty array is returned.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray rdp_stream_finish(struct RdpStream *stream);
+
+/**
+ * Free a streaming RDP simplification created by [`rdp_stream_new`](fn.rdp_stream_new.html)
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+void rdp_stream_free(struct RdpStream *stream);
+
+/**
+ * Compute the effective Visvalingam-Whyatt area of each interior point of a geometry
+ *
+ * Callers must pass a [Struct](struct.Array.html) with two fields:
+ *
+ * - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ * - `len`, the length of the array being passed. Its type must be `size_t`
+ *
+ * The result is an array of double-precision `float`s, containing the effective area of each
+ * point apart from the first and last, in input order. The endpoints are always retained, so they
+ * have no entry. Simplifying with an epsilon using [`simplify_visvalingam_idx_ffi`](fn.simplify_visvalingam_idx_ffi.html)
+ * retains the endpoints, and the points whose effective area is greater than the epsilon,
+ * so the areas can be used to choose an epsilon empirically.
+ *
+ * If the input is invalid, or has fewer than three points, an empty array is returned.
+ *
+ * Implementations calling this function **must** call [`drop_double_array`](fn.drop_double_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_vw_areas_ffi(struct ExternalArray coords);
+
+/**
+ * FFI wrapper for weighted Visvalingam-Whyatt, returning simplified geometry **coordinates**
+ *
+ * Callers must pass three arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of double-precision `float` weights, one per point
+ *     - `len`, the number of weights, which must equal the number of points
+ * - a double-precision `float` for the epsilon
+ *
+ * Each point's triangle area is multiplied by its weight before it is compared with the epsilon
+ * and with the areas of other points, so a point with a high weight (such as a labelled bend) is
+ * retained even if its area is small. A weight of 1 leaves a point's area unchanged, so weighting
+ * every point by 1 produces the same result as [`simplify_visvalingam_ffi`](fn.simplify_visvalingam_ffi.html).
+ * Weights should be non-negative. The endpoints' weights are ignored, as they are always retained.
+ *
+ * If either input is invalid, or the number of weights doesn't equal the number of points, an
+ * empty array is returned.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses raw pointers which could contain arbitrary data
+ */
+struct InternalArray simplify_vw_weighted_ffi(struct ExternalArray coords,
+                                              struct ExternalArray weights,
+                                              double epsilon);
+
+/**
+ * FFI wrapper for Visvalingam-Whyatt with a tie-break policy, returning simplified geometry **coordinates**
+ *
+ * Callers must pass three arguments:
+ *
+ * - a [Struct](struct.Array.html) with two fields:
+ *     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+ *     - `len`, the length of the array being passed. Its type must be `size_t`
+ * - a double-precision `float` for the epsilon
+ * - an `int` selecting the [`TieBreak`](enum.TieBreak.html) policy: first (0), last (1), or
+ *   lowest index (2)
+ *
+ * Points are removed in order of increasing triangle area, as in [`simplify_visvalingam_ffi`](fn.simplify_visvalingam_ffi.html),
+ * which removes points whose areas are equal in an unspecified order. Lines often contain several
+ * points with equal (usually zero) areas, such as repeated points, and removing one can change its
+ * neighbours' areas, so the order affects which are retained. Here, it's chosen by the policy, so
+ * results are deterministic. If `tie_break` isn't a valid `TieBreak` value, an empty array is returned.
+ *
+ * Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+ * with the returned `Array` pointer, in order to free the memory it allocates.
+ *
+ * # Safety
+ *
+ * This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+ */
+struct InternalArray simplify_visvalingam_tiebreak_ffi(struct ExternalArray coords,
+                                                       double epsilon,
+                                                       int tie_break);
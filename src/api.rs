@@ -0,0 +1,1877 @@
+//! A safe Rust API for in-process consumers
+//!
+//! These functions accept and return plain coordinate slices and `Vec`s, so Rust callers don't have
+//! to deal with raw pointers, or depend on geo directly. The RDP functions use the crate's own
+//! implementation, and are available without the `std` feature. The Visvalingam-Whyatt functions
+//! require it: [`simplify_vw_idx`] borrows the coordinates, and the others are thin wrappers around
+//! geo's simplification traits.
+//!
+//! The [`Simplifier`] trait wraps a choice of algorithm and its tolerance in a value, so it can be
+//! passed around, e.g. as a `Box<dyn Simplifier>`.
+//!
+//! ```
+//! let coords = [[0.0, 0.0], [5.0, 4.0], [11.0, 5.5], [17.3, 3.2], [27.8, 0.1]];
+//! assert_eq!(rdp::api::simplify_rdp_idx(&coords, 1.0), vec![0, 1, 2, 4]);
+//! ```
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use geo::simplify_vw::{SimplifyVw, SimplifyVwPreserve};
+#[cfg(feature = "std")]
+use geo::ChaikinSmoothing;
+#[cfg(feature = "std")]
+use geo::{CoordFloat, LineString};
+use num_traits::Float;
+
+#[cfg(feature = "std")]
+use crate::douglas_peucker::{rdp_indices, vertical_distance};
+use crate::douglas_peucker::{
+    rdp_indices_batched, rdp_indices_bounded, rdp_indices_floor, rdp_indices_pinned,
+    rdp_indices_symmetric, rdp_indices_variable, rdp_retains_all, rdp_thresholds, segment_distance,
+};
+#[cfg(feature = "std")]
+use crate::visvalingam::{vw_indices, vw_preserve_indices};
+
+// Lines with fewer points than this can't be simplified, so every algorithm returns them unchanged
+#[cfg(feature = "std")]
+pub(crate) const MIN_POINTS: usize = 3;
+
+// Apply a simplification to a line, returning lines which are too short to simplify unchanged.
+// Every geo-backed simplification goes through here, as geo's RDP panics on single-point input
+#[cfg(feature = "std")]
+pub(crate) fn simplify_with<T, F>(ls: LineString<T>, simplify: F) -> LineString<T>
+where
+    T: CoordFloat,
+    F: FnOnce(&LineString<T>) -> LineString<T>,
+{
+    if ls.0.len() < MIN_POINTS {
+        ls
+    } else {
+        simplify(&ls)
+    }
+}
+
+#[cfg(feature = "std")]
+fn linestring(coords: &[[f64; 2]]) -> LineString<f64> {
+    coords.to_vec().into()
+}
+
+#[cfg(feature = "std")]
+fn coords(ls: LineString<f64>) -> Vec<[f64; 2]> {
+    ls.into_iter().map(Into::into).collect()
+}
+
+// The indices of the points retained by RDP, using the same distance metric as geo
+fn rdp(coords_in: &[[f64; 2]], tolerance: f64) -> Vec<usize> {
+    rdp_indices_batched(coords_in, tolerance)
+}
+
+/// Simplify a line using the Ramer–Douglas–Peucker algorithm, returning the retained **coordinates**
+pub fn simplify_rdp(coords_in: &[[f64; 2]], tolerance: f64) -> Vec<[f64; 2]> {
+    rdp(coords_in, tolerance)
+        .into_iter()
+        .map(|idx| coords_in[idx])
+        .collect()
+}
+
+/// Thin a line by radial distance, returning the retained **coordinates**
+///
+/// Points are retained if they are farther than `tolerance` from the last retained point, starting
+/// with the first point. The last point is always retained, so the line's endpoints are unchanged.
+/// This is much cheaper than RDP, and removes clusters of near-duplicate points, such as those
+/// recorded by a stationary GPS receiver.
+pub fn simplify_radial(coords_in: &[[f64; 2]], tolerance: f64) -> Vec<[f64; 2]> {
+    let [first, interior @ .., last] = coords_in else {
+        return coords_in.to_vec();
+    };
+    let mut retained = Vec::from([*first]);
+    for &point in interior {
+        let previous = retained[retained.len() - 1];
+        let (dx, dy) = (point[0] - previous[0], point[1] - previous[1]);
+        if dx * dx + dy * dy > tolerance * tolerance {
+            retained.push(point);
+        }
+    }
+    retained.push(*last);
+    retained
+}
+
+/// Remove consecutive duplicate points from a line, returning the retained **coordinates**
+///
+/// A point is removed if it's within `epsilon` of the last retained point, starting with the
+/// first point, so a line whose points are all duplicates collapses to a single point. An
+/// `epsilon` of 0 removes only exact duplicates. Unlike [`simplify_radial`], the last point isn't
+/// always retained.
+pub fn dedupe_coords(coords_in: &[[f64; 2]], epsilon: f64) -> Vec<[f64; 2]> {
+    let mut retained: Vec<[f64; 2]> = Vec::with_capacity(coords_in.len());
+    for &point in coords_in {
+        match retained.last() {
+            Some(previous)
+                if Float::hypot(point[0] - previous[0], point[1] - previous[1]) <= epsilon => {}
+            _ => retained.push(point),
+        }
+    }
+    retained
+}
+
+/// Simplify a line by radial distance, then using the Ramer–Douglas–Peucker algorithm, returning
+/// the retained **coordinates**
+///
+/// The line is first thinned using [`simplify_radial`] with `radial_tolerance`, then simplified
+/// using [`simplify_rdp`] with `rdp_tolerance`. For noisy, densely sampled input, the first stage
+/// removes jitter which RDP would otherwise retain, and reduces the number of points RDP has to
+/// consider.
+pub fn simplify_rdp_radial(
+    coords_in: &[[f64; 2]],
+    radial_tolerance: f64,
+    rdp_tolerance: f64,
+) -> Vec<[f64; 2]> {
+    simplify_rdp(&simplify_radial(coords_in, radial_tolerance), rdp_tolerance)
+}
+
+/// Snap a line to a grid, then simplify it using the Ramer–Douglas–Peucker algorithm, returning
+/// the retained **coordinates**
+///
+/// Each coordinate is rounded to the nearest multiple of `grid_size`, consecutive duplicates
+/// produced by snapping are removed using [`dedupe_coords`], and the result is simplified using
+/// [`simplify_rdp_symmetric`]. Copies of a boundary shared by several features, which differ by
+/// less than half a grid cell, snap to identical coordinates, and as the simplification doesn't
+/// depend on direction, they remain identical after simplification, whichever direction each
+/// feature traverses them in. A `grid_size` which isn't a finite number greater than zero
+/// disables snapping.
+pub fn simplify_rdp_snapped(
+    coords_in: &[[f64; 2]],
+    tolerance: f64,
+    grid_size: f64,
+) -> Vec<[f64; 2]> {
+    if !(grid_size > 0.0 && grid_size.is_finite()) {
+        return simplify_rdp_symmetric(&dedupe_coords(coords_in, 0.0), tolerance);
+    }
+    let snap = |value: f64| Float::round(value / grid_size) * grid_size;
+    let snapped: Vec<[f64; 2]> = coords_in.iter().map(|&[x, y]| [snap(x), snap(y)]).collect();
+    simplify_rdp_symmetric(&dedupe_coords(&snapped, 0.0), tolerance)
+}
+
+/// Simplify a line using the Ramer–Douglas–Peucker algorithm, measuring distances after scaling
+/// each axis, and returning the retained **coordinates**
+///
+/// x coordinates are multiplied by `x_scale`, and y coordinates by `y_scale`, before the line is
+/// simplified, so `tolerance` is in the scaled units. The retained coordinates are copied from the
+/// input rather than unscaled, so they're bit-identical to it: in particular, the endpoints can be
+/// used as join keys in a network. This handles data whose axes use different units: for longitude and latitude in
+/// degrees, an `x_scale` of the cosine of the line's latitude makes a degree of longitude
+/// comparable to a degree of latitude.
+pub fn simplify_rdp_scaled(
+    coords_in: &[[f64; 2]],
+    tolerance: f64,
+    x_scale: f64,
+    y_scale: f64,
+) -> Vec<[f64; 2]> {
+    let scaled: Vec<[f64; 2]> = coords_in
+        .iter()
+        .map(|&[x, y]| [x * x_scale, y * y_scale])
+        .collect();
+    rdp(&scaled, tolerance)
+        .into_iter()
+        .map(|idx| coords_in[idx])
+        .collect()
+}
+
+/// The bounding box of a line, as `[min_x, min_y, max_x, max_y]`, or `None` for an empty line
+pub fn bounding_box(coords_in: &[[f64; 2]]) -> Option<[f64; 4]> {
+    if coords_in.is_empty() {
+        return None;
+    }
+    let (min, max) = coords_in.iter().fold(
+        ([f64::INFINITY; 2], [f64::NEG_INFINITY; 2]),
+        |(min, max), c| {
+            (
+                [min[0].min(c[0]), min[1].min(c[1])],
+                [max[0].max(c[0]), max[1].max(c[1])],
+            )
+        },
+    );
+    Some([min[0], min[1], max[0], max[1]])
+}
+
+// The length of the diagonal of the line's bounding box, which is 0 for an empty line
+fn diagonal(coords_in: &[[f64; 2]]) -> f64 {
+    bounding_box(coords_in).map_or(0.0, |[min_x, min_y, max_x, max_y]| {
+        Float::hypot(max_x - min_x, max_y - min_y)
+    })
+}
+
+/// Simplify a line using the Ramer–Douglas–Peucker algorithm, with a tolerance relative to the
+/// line's extent, returning the retained **coordinates**
+///
+/// The tolerance is `fraction` multiplied by the length of the diagonal of the line's bounding
+/// box, so 0.005 is 0.5% of the line's extent. Scaling a line therefore doesn't change which of
+/// its points are retained, so small and large features are simplified consistently.
+pub fn simplify_rdp_relative(coords_in: &[[f64; 2]], fraction: f64) -> Vec<[f64; 2]> {
+    simplify_rdp(coords_in, fraction * diagonal(coords_in))
+}
+
+/// Simplify a line using the Ramer–Douglas–Peucker algorithm, returning at most `max_points`
+/// **coordinates**
+///
+/// The smallest tolerance which produces no more than `max_points` points is found by bisection.
+/// `max_points` is clamped to a minimum of 2, so the endpoints are always retained, and a line which
+/// is already short enough is returned unchanged.
+pub fn simplify_rdp_max_points(coords_in: &[[f64; 2]], max_points: usize) -> Vec<[f64; 2]> {
+    let max_points = max_points.max(2);
+    if coords_in.len() <= max_points {
+        return coords_in.to_vec();
+    }
+    let (_, best) = bracket_point_count(coords_in, max_points);
+    best.into_iter().map(|idx| coords_in[idx]).collect()
+}
+
+/// Simplify a line using the Ramer–Douglas–Peucker algorithm, retaining approximately `keep_ratio`
+/// of its **coordinates**
+///
+/// `keep_ratio` is clamped to (0, 1], and the target is that fraction of the input's points,
+/// rounded, but never fewer than 2. The tolerance is found by bisection, as in
+/// [`simplify_rdp_max_points`], and the result whose length is closest to the target is returned.
+/// Removing one more point can remove several, so the result isn't always exactly the target.
+pub fn simplify_rdp_target_ratio(coords_in: &[[f64; 2]], keep_ratio: f64) -> Vec<[f64; 2]> {
+    let keep_ratio = if keep_ratio > 0.0 {
+        keep_ratio.min(1.0)
+    } else {
+        f64::MIN_POSITIVE
+    };
+    let target = (Float::round(keep_ratio * coords_in.len() as f64) as usize).max(2);
+    if coords_in.len() <= target {
+        return coords_in.to_vec();
+    }
+    let (over, under) = bracket_point_count(coords_in, target);
+    let best = if over.len() - target < target - under.len() {
+        over
+    } else {
+        under
+    };
+    best.into_iter().map(|idx| coords_in[idx]).collect()
+}
+
+// Bisect the tolerance, returning the indices of the shortest simplification retaining more than
+// `max_points` points, and of the longest retaining no more than `max_points`. `coords_in` must
+// contain more than `max_points` points
+fn bracket_point_count(coords_in: &[[f64; 2]], max_points: usize) -> (Vec<usize>, Vec<usize>) {
+    // No point is further from a segment than the diagonal of the line's bounding box, so this
+    // tolerance retains only the endpoints
+    let mut lo = 0.0;
+    let mut hi = (2.0 * diagonal(coords_in)).max(1.0);
+    let mut over: Vec<usize> = (0..coords_in.len()).collect();
+    let mut under = rdp(coords_in, hi);
+    for _ in 0..64 {
+        let mid = lo + (hi - lo) / 2.0;
+        if mid <= lo || mid >= hi {
+            break;
+        }
+        let candidate = rdp(coords_in, mid);
+        if candidate.len() <= max_points {
+            hi = mid;
+            under = candidate;
+        } else {
+            lo = mid;
+            over = candidate;
+        }
+    }
+    (over, under)
+}
+
+/// The number of points retained by the Ramer–Douglas–Peucker algorithm for each of `tolerances`
+///
+/// The counts are in the same order as the tolerances, which needn't be sorted. Each count is the
+/// length [`simplify_rdp`] would return for that tolerance, but the line is only traversed once,
+/// so this is much cheaper than simplifying with every tolerance.
+pub fn simplify_rdp_sweep(coords_in: &[[f64; 2]], tolerances: &[f64]) -> Vec<usize> {
+    let mut thresholds = rdp_thresholds(coords_in.len(), |start, end, point| {
+        segment_distance(coords_in[point], coords_in[start], coords_in[end])
+    });
+    thresholds.sort_unstable_by(f64::total_cmp);
+    tolerances
+        .iter()
+        .map(|&tolerance| {
+            // a tolerance which isn't greater than zero retains every point
+            if tolerance <= 0.0 {
+                coords_in.len()
+            } else {
+                coords_in.len() - thresholds.partition_point(|&t| t <= tolerance)
+            }
+        })
+        .collect()
+}
+
+/// The significance of each interior point of a line to the Ramer–Douglas–Peucker algorithm, as
+/// `(index, significance)` pairs, in order of decreasing significance
+///
+/// A point's significance is the tolerance below which it's retained: the distance at which it
+/// splits its segment, or the significance of the split which produced the segment, if that is
+/// smaller, as a point can't be retained unless its segment exists. [`simplify_rdp_idx`] retains
+/// the endpoints and exactly the points whose significance is greater than a positive tolerance,
+/// so a prefix of the result reproduces the simplification at any tolerance. Points with equal
+/// significance are in index order. The endpoints are always retained, so they have no entry.
+pub fn rdp_significance(coords_in: &[[f64; 2]]) -> Vec<(usize, f64)> {
+    let thresholds = rdp_thresholds(coords_in.len(), |start, end, point| {
+        segment_distance(coords_in[point], coords_in[start], coords_in[end])
+    });
+    let mut significance: Vec<(usize, f64)> = thresholds
+        .into_iter()
+        .enumerate()
+        .filter(|(_, threshold)| threshold.is_finite())
+        .collect();
+    significance.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    significance
+}
+
+/// Simplify a line using the Ramer–Douglas–Peucker algorithm at each of `tolerances`, returning the
+/// retained **coordinates** for each
+///
+/// Each result is identical to that of [`simplify_rdp`] with the same tolerance, but the line is
+/// only traversed once. RDP splits the line at the same points whatever the tolerance, so the
+/// result for a higher tolerance is always a subset of the result for a lower one, which suits
+/// levels of detail such as the zoom levels of a tile pyramid.
+pub fn simplify_rdp_lod(coords_in: &[[f64; 2]], tolerances: &[f64]) -> Vec<Vec<[f64; 2]>> {
+    let thresholds = rdp_thresholds(coords_in.len(), |start, end, point| {
+        segment_distance(coords_in[point], coords_in[start], coords_in[end])
+    });
+    tolerances
+        .iter()
+        .map(|&tolerance| {
+            coords_in
+                .iter()
+                .zip(&thresholds)
+                // a tolerance which isn't greater than zero retains every point
+                .filter(|(_, &threshold)| tolerance <= 0.0 || threshold > tolerance)
+                .map(|(coord, _)| *coord)
+                .collect()
+        })
+        .collect()
+}
+
+/// Simplify a line using the Ramer–Douglas–Peucker algorithm, returning at least `min_points`
+/// **coordinates**
+///
+/// If the tolerance alone would retain fewer than `min_points` points, the most distant of the
+/// removed points are restored, in order of decreasing distance, until there are enough. A line
+/// with no more than `min_points` points is returned unchanged.
+pub fn simplify_rdp_floor(
+    coords_in: &[[f64; 2]],
+    tolerance: f64,
+    min_points: usize,
+) -> Vec<[f64; 2]> {
+    rdp_indices_floor(
+        coords_in.len(),
+        tolerance,
+        min_points,
+        |start, end, point| segment_distance(coords_in[point], coords_in[start], coords_in[end]),
+    )
+    .into_iter()
+    .map(|idx| coords_in[idx])
+    .collect()
+}
+
+/// Simplify a line using the Ramer–Douglas–Peucker algorithm, computing at most `max_work` distances,
+/// and returning the retained **coordinates**
+///
+/// RDP computes the distance of every point in a segment from it before splitting it, so it does
+/// O(n²) work in the worst case. Here, segments are split in order of decreasing distance, and a
+/// segment which would take the number of distances computed past `max_work` isn't split. The
+/// second value is `true` if this happened, in which case the result is a coarser simplification
+/// which retains the most significant points. Otherwise, the result is identical to that of
+/// [`simplify_rdp`].
+pub fn simplify_rdp_bounded(
+    coords_in: &[[f64; 2]],
+    tolerance: f64,
+    max_work: usize,
+) -> (Vec<[f64; 2]>, bool) {
+    let (indices, truncated) =
+        rdp_indices_bounded(coords_in.len(), tolerance, max_work, |start, end, point| {
+            segment_distance(coords_in[point], coords_in[start], coords_in[end])
+        });
+    (
+        indices.into_iter().map(|idx| coords_in[idx]).collect(),
+        truncated,
+    )
+}
+
+/// Simplify a line using the Ramer–Douglas–Peucker algorithm, returning the retained **coordinates**
+///
+/// Unlike [`simplify_rdp`], the result doesn't depend on the line's direction: simplifying the
+/// reversed line produces the reversed result. This keeps edges shared between adjacent polygons
+/// identical after simplification, whichever direction each polygon traverses them in.
+pub fn simplify_rdp_symmetric(coords_in: &[[f64; 2]], tolerance: f64) -> Vec<[f64; 2]> {
+    rdp_indices_symmetric(coords_in, tolerance)
+        .into_iter()
+        .map(|idx| coords_in[idx])
+        .collect()
+}
+
+/// Simplify lines which share boundaries using the Ramer–Douglas–Peucker algorithm, returning the
+/// retained **coordinates** of each
+///
+/// `shared` holds the indices of the vertices of each line which must be retained, such as the
+/// points where a boundary shared with an adjacent polygon begins and ends. Lines without a
+/// corresponding entry in `shared` have no pinned vertices. Each line is split at its pinned
+/// vertices, and each section is simplified independently, and independently of its direction, as
+/// in [`simplify_rdp_symmetric`]. A boundary which is pinned at both ends in every line containing
+/// it is therefore simplified identically in all of them, so adjacent polygons don't develop gaps
+/// or overlaps.
+pub fn simplify_rdp_shared(
+    lines: &[&[[f64; 2]]],
+    shared: &[&[usize]],
+    tolerance: f64,
+) -> Vec<Vec<[f64; 2]>> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(line, coords_in)| {
+            let pinned = shared.get(line).copied().unwrap_or_default();
+            rdp_indices_pinned(coords_in, tolerance, pinned)
+                .into_iter()
+                .map(|idx| coords_in[idx])
+                .collect()
+        })
+        .collect()
+}
+
+/// Simplify a line using the Ramer–Douglas–Peucker algorithm, retaining a vertex at each crossing
+/// of a vertical boundary, and returning the retained **coordinates**
+///
+/// `boundaries` holds the x coordinates of vertical lines, such as tile edges. Wherever a segment
+/// of the line crosses (or touches) one of them, whichever of its endpoints is nearer the boundary
+/// is retained, and the line is simplified between the retained vertices as in
+/// [`simplify_rdp_shared`]. Lines which are clipped to adjacent tiles therefore share vertices at
+/// the tiles' common edge, so renderers don't produce cracks along it.
+pub fn simplify_rdp_boundaries(
+    coords_in: &[[f64; 2]],
+    tolerance: f64,
+    boundaries: &[f64],
+) -> Vec<[f64; 2]> {
+    let pinned: Vec<usize> = coords_in
+        .windows(2)
+        .enumerate()
+        .flat_map(|(idx, segment)| {
+            let (a, b) = (segment[0][0], segment[1][0]);
+            boundaries
+                .iter()
+                .filter(move |&&x| (a - x) * (b - x) <= 0.0)
+                .map(move |&x| {
+                    if Float::abs(a - x) <= Float::abs(b - x) {
+                        idx
+                    } else {
+                        idx + 1
+                    }
+                })
+        })
+        .collect();
+    rdp_indices_pinned(coords_in, tolerance, &pinned)
+        .into_iter()
+        .map(|idx| coords_in[idx])
+        .collect()
+}
+
+/// Simplify a line using the Ramer–Douglas–Peucker algorithm, retaining the vertices with the
+/// smallest and largest x and y coordinates, and returning the retained **coordinates**
+///
+/// Charts and sparklines must show a series' highest and lowest values, even when they're within
+/// the tolerance of their neighbours. Up to four vertices are retained (fewer if one vertex has more
+/// than one of the extreme values), and the line is simplified between the retained vertices as in
+/// [`simplify_rdp_shared`]. If several vertices share an extreme value, the first is retained.
+pub fn simplify_rdp_extrema(coords_in: &[[f64; 2]], tolerance: f64) -> Vec<[f64; 2]> {
+    let bbox = bounding_box(coords_in).unwrap_or_default();
+    let pinned: Vec<usize> = [(0, bbox[0]), (1, bbox[1]), (0, bbox[2]), (1, bbox[3])]
+        .into_iter()
+        .filter_map(|(axis, extreme)| coords_in.iter().position(|c| c[axis] == extreme))
+        .collect();
+    rdp_indices_pinned(coords_in, tolerance, &pinned)
+        .into_iter()
+        .map(|idx| coords_in[idx])
+        .collect()
+}
+
+/// Simplify a line using the Ramer–Douglas–Peucker algorithm, retaining sharp corners, and
+/// returning the retained **coordinates**
+///
+/// A vertex's angle is the angle, in degrees, between the input segments meeting at it: 180 for a
+/// vertex on a straight line, 90 for a right-angled corner, and close to 0 for a hairpin. Vertices
+/// whose angle is less than `min_angle_deg`, such as street corners, are always retained, however
+/// close they are to their neighbours, and the line is simplified between the retained vertices as
+/// in [`simplify_rdp_shared`]. A vertex which coincides with a neighbour has no angle, so it isn't
+/// retained for this reason. A `min_angle_deg` which isn't greater than zero retains no corners.
+pub fn simplify_rdp_corners(
+    coords_in: &[[f64; 2]],
+    tolerance: f64,
+    min_angle_deg: f64,
+) -> Vec<[f64; 2]> {
+    let pinned: Vec<usize> = coords_in
+        .windows(3)
+        .enumerate()
+        .filter(|(_, triple)| {
+            let [previous, vertex, next] = [triple[0], triple[1], triple[2]];
+            let a = [previous[0] - vertex[0], previous[1] - vertex[1]];
+            let b = [next[0] - vertex[0], next[1] - vertex[1]];
+            if a == [0.0, 0.0] || b == [0.0, 0.0] {
+                return false;
+            }
+            let cross = a[0] * b[1] - a[1] * b[0];
+            let dot = a[0] * b[0] + a[1] * b[1];
+            Float::to_degrees(Float::atan2(Float::abs(cross), dot)) < min_angle_deg
+        })
+        .map(|(idx, _)| idx + 1)
+        .collect();
+    rdp_indices_pinned(coords_in, tolerance, &pinned)
+        .into_iter()
+        .map(|idx| coords_in[idx])
+        .collect()
+}
+
+/// Simplify a timestamped line using the Ramer–Douglas–Peucker algorithm, retaining the vertices
+/// on either side of long gaps in time, and returning the retained **coordinates**
+///
+/// `times` holds the time of each vertex, such as seconds since the epoch. Wherever consecutive
+/// vertices are more than `max_gap` apart in time, such as either side of a stop in a GPS track,
+/// both are retained, even if they're geometrically redundant. The line is simplified between the
+/// retained vertices as in [`simplify_rdp_shared`]. Times beyond the end of the line are ignored.
+pub fn simplify_rdp_timed(
+    coords_in: &[[f64; 2]],
+    times: &[f64],
+    tolerance: f64,
+    max_gap: f64,
+) -> Vec<[f64; 2]> {
+    let pinned: Vec<usize> = times
+        .windows(2)
+        .enumerate()
+        .filter(|(_, pair)| pair[1] - pair[0] > max_gap)
+        .flat_map(|(idx, _)| [idx, idx + 1])
+        .collect();
+    rdp_indices_pinned(coords_in, tolerance, &pinned)
+        .into_iter()
+        .map(|idx| coords_in[idx])
+        .collect()
+}
+
+/// Simplify a line using the Ramer–Douglas–Peucker algorithm, always retaining the vertices marked
+/// in `pinned`, and returning the retained **coordinates**
+///
+/// `pinned` holds a flag for each vertex, such as a junction or a labelled point, which must never
+/// be removed. The line is simplified independently between the pinned vertices, as in
+/// [`simplify_rdp_shared`]. Flags beyond the end of the line are ignored.
+pub fn simplify_rdp_pinned(
+    coords_in: &[[f64; 2]],
+    pinned: &[bool],
+    tolerance: f64,
+) -> Vec<[f64; 2]> {
+    let pinned: Vec<usize> = pinned
+        .iter()
+        .enumerate()
+        .filter(|(_, &pin)| pin)
+        .map(|(idx, _)| idx)
+        .collect();
+    rdp_indices_pinned(coords_in, tolerance, &pinned)
+        .into_iter()
+        .map(|idx| coords_in[idx])
+        .collect()
+}
+
+/// Simplify part of a line using the Ramer–Douglas–Peucker algorithm, returning the **coordinates**
+/// of the whole line
+///
+/// Only the points from index `start` to index `end` (inclusive) are simplified, as in
+/// [`simplify_rdp`]: the points before and after them are copied unchanged. `None` is returned if
+/// `start` is greater than `end`, or `end` is out of bounds.
+pub fn simplify_rdp_range(
+    coords_in: &[[f64; 2]],
+    start: usize,
+    end: usize,
+    tolerance: f64,
+) -> Option<Vec<[f64; 2]>> {
+    if start > end || end >= coords_in.len() {
+        return None;
+    }
+    let mut simplified = coords_in[..start].to_vec();
+    simplified.extend(simplify_rdp(&coords_in[start..=end], tolerance));
+    simplified.extend_from_slice(&coords_in[end + 1..]);
+    Some(simplified)
+}
+
+/// Simplify a line using the Ramer–Douglas–Peucker algorithm, then split long segments, returning
+/// the retained **coordinates**
+///
+/// RDP can replace a long, gentle curve with a single segment, whose chord error is visible when
+/// it's rendered at a large scale. Here, each simplified segment which is longer than
+/// `max_segment_length` is split by restoring the removed point closest to its midpoint, until
+/// every segment is short enough, or no removed points remain between its ends. Segments of the
+/// input which are longer than `max_segment_length` therefore can't be split.
+pub fn simplify_rdp_max_segment(
+    coords_in: &[[f64; 2]],
+    tolerance: f64,
+    max_segment_length: f64,
+) -> Vec<[f64; 2]> {
+    let simplified = rdp(coords_in, tolerance);
+    let mut retained = Vec::with_capacity(simplified.len());
+    for pair in simplified.windows(2) {
+        let mut pending = vec![(pair[0], pair[1])];
+        while let Some((start, end)) = pending.pop() {
+            let [a, b] = [coords_in[start], coords_in[end]];
+            if end - start < 2 || Float::hypot(b[0] - a[0], b[1] - a[1]) <= max_segment_length {
+                retained.push(start);
+                continue;
+            }
+            let midpoint = [(a[0] + b[0]) / 2.0, (a[1] + b[1]) / 2.0];
+            let split = (start + 1..end)
+                .min_by(|&i, &j| {
+                    let distance = |idx: usize| {
+                        let c = coords_in[idx];
+                        Float::hypot(c[0] - midpoint[0], c[1] - midpoint[1])
+                    };
+                    distance(i).total_cmp(&distance(j))
+                })
+                .unwrap_or(start);
+            pending.push((split, end));
+            pending.push((start, split));
+        }
+    }
+    retained.extend(simplified.last());
+    retained.into_iter().map(|idx| coords_in[idx]).collect()
+}
+
+/// Simplify a line using the Ramer–Douglas–Peucker algorithm, then restore points to guarantee a
+/// minimum sampling density, returning the retained **coordinates**
+///
+/// Each simplified segment which skips more than `max_span` points of the input is split by
+/// restoring every `max_span + 1`th removed point along it, so no segment of the result skips more
+/// than `max_span` input points. A `max_span` of 0 therefore retains every point.
+pub fn simplify_rdp_density(
+    coords_in: &[[f64; 2]],
+    tolerance: f64,
+    max_span: usize,
+) -> Vec<[f64; 2]> {
+    let simplified = rdp(coords_in, tolerance);
+    let step = max_span.saturating_add(1);
+    let mut retained = Vec::with_capacity(simplified.len());
+    for pair in simplified.windows(2) {
+        retained.extend((pair[0]..pair[1]).step_by(step));
+    }
+    retained.extend(simplified.last());
+    retained.into_iter().map(|idx| coords_in[idx]).collect()
+}
+
+/// Join two lines, and simplify the result using the Ramer–Douglas–Peucker algorithm, returning
+/// the retained **coordinates**
+///
+/// If the last point of `a` is equal to the first point of `b`, it appears once in the joined line.
+/// The joined line is simplified as one, so the junction is removed if it's within the tolerance,
+/// which wouldn't be the case if the lines were simplified separately and then joined.
+pub fn merge_simplify_rdp(a: &[[f64; 2]], b: &[[f64; 2]], tolerance: f64) -> Vec<[f64; 2]> {
+    let skip = usize::from(a.last().is_some() && a.last() == b.first());
+    let joined: Vec<[f64; 2]> = a.iter().chain(&b[skip..]).copied().collect();
+    simplify_rdp(&joined, tolerance)
+}
+
+/// Simplify a line using the Ramer–Douglas–Peucker algorithm, with a separate tolerance for each
+/// vertex, and returning the retained **coordinates**
+///
+/// `tolerances[i]` is the distance by which vertex `i` (the start of the line's `i`th segment) may
+/// deviate from the simplified line, so regions with high tolerances are simplified aggressively,
+/// while regions with low tolerances keep their detail. Each segment is split at the farthest of
+/// the vertices which deviate from it by more than their tolerance, so equal tolerances produce
+/// the same result as [`simplify_rdp`]. A vertex whose tolerance isn't greater than zero is always
+/// retained, and the endpoints' tolerances are ignored, as they are always retained.
+///
+/// # Panics
+///
+/// If `tolerances` is shorter than `coords_in`
+pub fn simplify_rdp_variable(coords_in: &[[f64; 2]], tolerances: &[f64]) -> Vec<[f64; 2]> {
+    rdp_indices_variable(&tolerances[..coords_in.len()], |start, end, point| {
+        segment_distance(coords_in[point], coords_in[start], coords_in[end])
+    })
+    .into_iter()
+    .map(|idx| coords_in[idx])
+    .collect()
+}
+
+/// Simplify a line of integer coordinates using the Ramer–Douglas–Peucker algorithm, returning the
+/// retained **coordinates**
+///
+/// Every `i32` is exactly representable as an `f64`, so distances are computed in floating point
+/// without rounding the input, and the result is identical to that of [`simplify_rdp`] on the
+/// converted coordinates. The retained coordinates are copied from the input, so they are exact:
+/// unlike simplifying in floating point and rounding the result, no rounding errors or duplicate
+/// points are introduced.
+pub fn simplify_rdp_i32(coords_in: &[[i32; 2]], tolerance: f64) -> Vec<[i32; 2]> {
+    let converted: Vec<[f64; 2]> = coords_in
+        .iter()
+        .map(|&[x, y]| [f64::from(x), f64::from(y)])
+        .collect();
+    rdp(&converted, tolerance)
+        .into_iter()
+        .map(|idx| coords_in[idx])
+        .collect()
+}
+
+/// Simplify a line using the Ramer–Douglas–Peucker algorithm, returning the retained **indices**
+pub fn simplify_rdp_idx(coords_in: &[[f64; 2]], tolerance: f64) -> Vec<usize> {
+    rdp(coords_in, tolerance)
+}
+
+/// Simplify a line using the Ramer–Douglas–Peucker algorithm, returning the **indices** of the
+/// removed points
+///
+/// The indices are sorted, and are the complement of those returned by [`simplify_rdp_idx`].
+pub fn simplify_rdp_removed_idx(coords_in: &[[f64; 2]], tolerance: f64) -> Vec<usize> {
+    let mut retained = rdp(coords_in, tolerance).into_iter().peekable();
+    (0..coords_in.len())
+        .filter(|&idx| retained.next_if_eq(&idx).is_none())
+        .collect()
+}
+
+/// Simplify a line using the Ramer–Douglas–Peucker algorithm, returning the number of points
+/// removed from each segment of the simplified line
+///
+/// There is one count for each segment, in order, so the counts sum to the number of points
+/// removed. A line with fewer than two points has no segments.
+pub fn simplify_rdp_segment_stats(coords_in: &[[f64; 2]], tolerance: f64) -> Vec<usize> {
+    rdp(coords_in, tolerance)
+        .windows(2)
+        .map(|pair| pair[1] - pair[0] - 1)
+        .collect()
+}
+
+/// Simplify a time series using the Ramer–Douglas–Peucker algorithm, returning the retained
+/// **coordinates**
+///
+/// The x coordinates must be strictly increasing, such as the times of (time, value) pairs.
+/// Distances are measured vertically, between each point and the value interpolated at its x
+/// coordinate by the segment's ends, rather than perpendicular to the segment, so the tolerance is
+/// in the units of the values alone, however the axes are scaled. The retained points are a
+/// subsequence of the input, so their x coordinates are strictly increasing too.
+#[cfg(feature = "std")]
+pub fn simplify_rdp_series(coords_in: &[[f64; 2]], tolerance: f64) -> Vec<[f64; 2]> {
+    let increasing = |coords: &[[f64; 2]]| coords.windows(2).all(|pair| pair[0][0] < pair[1][0]);
+    debug_assert!(
+        increasing(coords_in),
+        "x coordinates must be strictly increasing"
+    );
+    let simplified: Vec<[f64; 2]> = rdp_indices(coords_in.len(), tolerance, |start, end, point| {
+        vertical_distance(coords_in[point], coords_in[start], coords_in[end])
+    })
+    .into_iter()
+    .map(|idx| coords_in[idx])
+    .collect();
+    debug_assert!(increasing(&simplified));
+    simplified
+}
+
+/// Simplify a line using the Ramer–Douglas–Peucker algorithm, then smooth it using Chaikin's
+/// algorithm, returning the resulting **coordinates**
+///
+/// Each of the `iterations` of smoothing replaces every corner with two points a quarter of the
+/// way along its segments, roughly doubling the number of points. The first and last points of an
+/// open line are unchanged, but a closed line is smoothed as a ring, including the corner at its
+/// first point. Zero iterations are equivalent to [`simplify_rdp`].
+#[cfg(feature = "std")]
+pub fn simplify_then_smooth(
+    coords_in: &[[f64; 2]],
+    tolerance: f64,
+    iterations: usize,
+) -> Vec<[f64; 2]> {
+    coords(linestring(&simplify_rdp(coords_in, tolerance)).chaikin_smoothing(iterations))
+}
+
+/// Simplify a line using the Ramer–Douglas–Peucker algorithm, returning a **mask** of the retained
+/// points
+///
+/// The mask has the same length as the input, and is `true` at the indices returned by
+/// [`simplify_rdp_idx`].
+pub fn simplify_rdp_mask(coords_in: &[[f64; 2]], tolerance: f64) -> Vec<bool> {
+    let mut mask = vec![false; coords_in.len()];
+    for idx in rdp(coords_in, tolerance) {
+        mask[idx] = true;
+    }
+    mask
+}
+
+/// Simplify a line using both the Ramer–Douglas–Peucker and Visvalingam-Whyatt algorithms,
+/// returning whichever retains fewer **coordinates**
+///
+/// Both results deviate from the input by no more than `tolerance`, as measured by
+/// [`within_tolerance`]. Visvalingam-Whyatt's tolerance is an area, so the largest area which keeps
+/// its result within `tolerance` is found by bisection. If both retain the same number of points,
+/// the Ramer–Douglas–Peucker result is returned.
+#[cfg(feature = "std")]
+pub fn simplify_best(coords_in: &[[f64; 2]], tolerance: f64) -> Vec<[f64; 2]> {
+    let mut best = simplify_rdp(coords_in, tolerance);
+    if best.len() <= 2 {
+        return best;
+    }
+    // No triangle formed by the line's points is larger than the square of its bounding box's
+    // diagonal, so this area removes every point but the endpoints
+    let mut lo = 0.0;
+    let mut hi = Float::powi(diagonal(coords_in), 2).max(1.0);
+    for _ in 0..64 {
+        let mid = lo + (hi - lo) / 2.0;
+        if mid <= lo || mid >= hi {
+            break;
+        }
+        let candidate = simplify_vw(coords_in, mid);
+        if within_tolerance(coords_in, &candidate, tolerance) {
+            lo = mid;
+            if candidate.len() < best.len() {
+                best = candidate;
+            }
+        } else {
+            hi = mid;
+        }
+    }
+    best
+}
+
+/// Simplify a line using the Visvalingam-Whyatt algorithm, returning the retained **coordinates**
+#[cfg(feature = "std")]
+pub fn simplify_vw(coords_in: &[[f64; 2]], epsilon: f64) -> Vec<[f64; 2]> {
+    coords(simplify_with(linestring(coords_in), |ls| {
+        ls.simplify_vw(&epsilon)
+    }))
+}
+
+/// Simplify a line using the Visvalingam-Whyatt algorithm, with an epsilon relative to the line's
+/// length, returning the retained **coordinates**
+///
+/// The epsilon is `area_fraction` multiplied by the square of the line's total length. Areas scale
+/// with the square of a line's size, so scaling a line doesn't change which of its points are
+/// retained.
+#[cfg(feature = "std")]
+pub fn simplify_vw_relative(coords_in: &[[f64; 2]], area_fraction: f64) -> Vec<[f64; 2]> {
+    let length: f64 = coords_in
+        .windows(2)
+        .map(|pair| Float::hypot(pair[1][0] - pair[0][0], pair[1][1] - pair[0][1]))
+        .sum();
+    simplify_vw(coords_in, area_fraction * length * length)
+}
+
+/// Simplify a line using the Visvalingam-Whyatt algorithm, returning the retained **indices**
+#[cfg(feature = "std")]
+pub fn simplify_vw_idx(coords_in: &[[f64; 2]], epsilon: f64) -> Vec<usize> {
+    vw_indices(coords_in, epsilon)
+}
+
+/// Simplify a line using a topology-preserving variant of the Visvalingam-Whyatt algorithm,
+/// returning the retained **coordinates**
+#[cfg(feature = "std")]
+pub fn simplify_vw_preserve(coords_in: &[[f64; 2]], epsilon: f64) -> Vec<[f64; 2]> {
+    coords(simplify_with(linestring(coords_in), |ls| {
+        ls.simplify_vw_preserve(&epsilon)
+    }))
+}
+
+/// Simplify a line using a topology-preserving variant of the Visvalingam-Whyatt algorithm,
+/// returning the retained **indices**
+///
+/// Whenever removing a point would make the simplified line cross itself, the point preceding it
+/// is removed too, which usually removes the crossing. Removal stops early if too few points
+/// would remain to do this, so the result may have more points than [`simplify_vw_idx`] returns.
+/// This is a heuristic rather than a guarantee: the first point is never removed, so a crossing
+/// introduced by removing the second point remains.
+#[cfg(feature = "std")]
+pub fn simplify_vw_preserve_idx(coords_in: &[[f64; 2]], epsilon: f64) -> Vec<usize> {
+    vw_preserve_indices(coords_in, epsilon)
+}
+
+/// The maximum distance of any point of `original` from the `simplified` line
+///
+/// This can be used to check that a simplification respected its tolerance. `None` is returned if
+/// either line is empty, or if `simplified` has more points than `original`, since it can't then be
+/// a simplification of it.
+pub fn max_deviation(original: &[[f64; 2]], simplified: &[[f64; 2]]) -> Option<f64> {
+    if original.is_empty() || simplified.is_empty() || simplified.len() > original.len() {
+        return None;
+    }
+    Some(directed_deviation(original, simplified))
+}
+
+/// Whether every point of `original` is within `tolerance` of the `simplified` line
+///
+/// This is equivalent to comparing [`max_deviation`] with the tolerance, but stops at the first
+/// point which is too far away, so it's cheaper as a post-condition check. `false` is returned in
+/// the cases in which [`max_deviation`] returns `None`.
+pub fn within_tolerance(original: &[[f64; 2]], simplified: &[[f64; 2]], tolerance: f64) -> bool {
+    if original.is_empty() || simplified.is_empty() || simplified.len() > original.len() {
+        return false;
+    }
+    original
+        .iter()
+        .all(|point| line_distance(*point, simplified) <= tolerance)
+}
+
+/// Whether simplifying a line using the Ramer–Douglas–Peucker algorithm would retain every point
+///
+/// This is equivalent to comparing the length of the result of [`simplify_rdp`] with the input's,
+/// but stops as soon as a point which would be removed is found, and allocates no result. Lines
+/// with fewer than three points are always simplified.
+pub fn is_simplified(coords_in: &[[f64; 2]], tolerance: f64) -> bool {
+    rdp_retains_all(coords_in, tolerance)
+}
+
+/// The symmetric Hausdorff distance between two lines
+///
+/// This is the greater of the maximum distance of any vertex of `a` from the line `b`, and the
+/// maximum distance of any vertex of `b` from the line `a`. Distances are measured to the nearest
+/// point on the other line's segments, rather than to its nearest vertex, so densely sampled
+/// lines aren't penalised for not sharing vertices. `None` is returned if either line is empty.
+pub fn hausdorff_distance(a: &[[f64; 2]], b: &[[f64; 2]]) -> Option<f64> {
+    if a.is_empty() || b.is_empty() {
+        return None;
+    }
+    Some(directed_deviation(a, b).max(directed_deviation(b, a)))
+}
+
+/// A line simplification algorithm, together with its tolerance
+pub trait Simplifier {
+    /// Simplify a line, returning the retained **coordinates**
+    fn simplify(&self, coords_in: &[[f64; 2]]) -> Vec<[f64; 2]>;
+}
+
+/// Simplification using the Ramer–Douglas–Peucker algorithm, as by [`simplify_rdp`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RdpSimplifier {
+    pub tolerance: f64,
+}
+
+impl Simplifier for RdpSimplifier {
+    fn simplify(&self, coords_in: &[[f64; 2]]) -> Vec<[f64; 2]> {
+        simplify_rdp(coords_in, self.tolerance)
+    }
+}
+
+/// Simplification using the Visvalingam-Whyatt algorithm, as by [`simplify_vw`]
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VwSimplifier {
+    pub epsilon: f64,
+}
+
+#[cfg(feature = "std")]
+impl Simplifier for VwSimplifier {
+    fn simplify(&self, coords_in: &[[f64; 2]]) -> Vec<[f64; 2]> {
+        simplify_vw(coords_in, self.epsilon)
+    }
+}
+
+/// Simplification using the topology-preserving variant of the Visvalingam-Whyatt algorithm, as
+/// by [`simplify_vw_preserve`]
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VwPreserveSimplifier {
+    pub epsilon: f64,
+}
+
+#[cfg(feature = "std")]
+impl Simplifier for VwPreserveSimplifier {
+    fn simplify(&self, coords_in: &[[f64; 2]]) -> Vec<[f64; 2]> {
+        simplify_vw_preserve(coords_in, self.epsilon)
+    }
+}
+
+// The distance of `point` from the nearest segment of the (non-empty) line `to`
+fn line_distance(point: [f64; 2], to: &[[f64; 2]]) -> f64 {
+    match to {
+        [only] => segment_distance(point, *only, *only),
+        _ => to
+            .windows(2)
+            .map(|segment| segment_distance(point, segment[0], segment[1]))
+            .fold(f64::INFINITY, f64::min),
+    }
+}
+
+// The maximum distance of any vertex of `from` from the (non-empty) line `to`
+fn directed_deviation(from: &[[f64; 2]], to: &[[f64; 2]]) -> f64 {
+    from.iter()
+        .map(|point| line_distance(*point, to))
+        .fold(0.0, f64::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RDP_INPUT: [[f64; 2]; 5] = [
+        [0.0, 0.0],
+        [5.0, 4.0],
+        [11.0, 5.5],
+        [17.3, 3.2],
+        [27.8, 0.1],
+    ];
+    const VW_INPUT: [[f64; 2]; 5] = [
+        [5.0, 2.0],
+        [3.0, 8.0],
+        [6.0, 20.0],
+        [7.0, 25.0],
+        [10.0, 10.0],
+    ];
+
+    #[test]
+    fn test_rdp() {
+        assert_eq!(
+            simplify_rdp(&RDP_INPUT, 1.0),
+            vec![[0.0, 0.0], [5.0, 4.0], [11.0, 5.5], [27.8, 0.1]]
+        );
+        assert_eq!(simplify_rdp_idx(&RDP_INPUT, 1.0), vec![0, 1, 2, 4]);
+    }
+    #[test]
+    fn test_radial() {
+        // a stationary receiver records a cluster of fixes around (5, 0)
+        let track = [
+            [0.0, 0.0],
+            [5.0, 0.0],
+            [5.1, 0.4],
+            [4.8, -0.4],
+            [5.2, 0.3],
+            [10.0, 0.0],
+        ];
+        assert_eq!(
+            simplify_radial(&track, 1.0),
+            vec![[0.0, 0.0], [5.0, 0.0], [10.0, 0.0]]
+        );
+        // RDP alone retains the jitter, as it's farther than the tolerance from the line
+        let rdp = simplify_rdp(&track, 0.2);
+        let combined = simplify_rdp_radial(&track, 1.0, 0.2);
+        assert_eq!(combined, vec![[0.0, 0.0], [10.0, 0.0]]);
+        assert!(combined.len() < rdp.len());
+        // the last point is retained, even if it's close to the last retained point
+        assert_eq!(
+            simplify_radial(&[[0.0, 0.0], [5.0, 0.0], [5.1, 0.0]], 1.0),
+            vec![[0.0, 0.0], [5.0, 0.0], [5.1, 0.0]]
+        );
+        assert_eq!(
+            simplify_radial(&[[0.0, 0.0], [0.1, 0.0]], 1.0),
+            vec![[0.0, 0.0], [0.1, 0.0]]
+        );
+        assert_eq!(simplify_radial(&[[0.0, 0.0]], 1.0), vec![[0.0, 0.0]]);
+        assert!(simplify_radial(&[], 1.0).is_empty());
+    }
+    #[test]
+    fn test_dedupe_coords() {
+        // snapping has produced runs of identical and near-identical points
+        let line = [
+            [0.0, 0.0],
+            [0.0, 0.0],
+            [1.0, 1.0],
+            [1.0, 1.0],
+            [1.0, 1.0],
+            [1.05, 1.0],
+            [2.0, 0.0],
+        ];
+        assert_eq!(
+            dedupe_coords(&line, 0.0),
+            vec![[0.0, 0.0], [1.0, 1.0], [1.05, 1.0], [2.0, 0.0]]
+        );
+        assert_eq!(
+            dedupe_coords(&line, 0.1),
+            vec![[0.0, 0.0], [1.0, 1.0], [2.0, 0.0]]
+        );
+        // non-consecutive duplicates are retained
+        assert_eq!(
+            dedupe_coords(&[[0.0, 0.0], [1.0, 0.0], [0.0, 0.0]], 0.0),
+            vec![[0.0, 0.0], [1.0, 0.0], [0.0, 0.0]]
+        );
+        assert_eq!(dedupe_coords(&[[3.0, 4.0]; 5], 0.0), vec![[3.0, 4.0]]);
+        assert_eq!(
+            dedupe_coords(&[[3.0, 4.0], [3.01, 4.0], [3.0, 4.01]], 0.1),
+            vec![[3.0, 4.0]]
+        );
+        assert!(dedupe_coords(&[], 0.0).is_empty());
+    }
+    #[test]
+    fn test_rdp_removed_idx() {
+        assert_eq!(simplify_rdp_removed_idx(&RDP_INPUT, 1.0), vec![3]);
+        assert!(simplify_rdp_removed_idx(&RDP_INPUT, 0.0).is_empty());
+        assert!(simplify_rdp_removed_idx(&[], 1.0).is_empty());
+    }
+    #[test]
+    fn test_rdp_segment_stats() {
+        assert_eq!(simplify_rdp_segment_stats(&RDP_INPUT, 1.0), vec![0, 0, 1]);
+        assert_eq!(simplify_rdp_segment_stats(&RDP_INPUT, 0.0), vec![0; 4]);
+        assert!(simplify_rdp_segment_stats(&[[1.0, 1.0]], 1.0).is_empty());
+        assert!(simplify_rdp_segment_stats(&[], 1.0).is_empty());
+    }
+    #[test]
+    fn test_rdp_series() {
+        // a steep series, with a spike which is much further from the trend vertically than
+        // perpendicularly
+        let series = [
+            [0.0, 0.0],
+            [1.0, 5.0],
+            [2.0, 10.8],
+            [3.0, 15.0],
+            [4.0, 20.0],
+        ];
+        assert_eq!(
+            simplify_rdp_series(&series, 0.5),
+            vec![[0.0, 0.0], [2.0, 10.8], [4.0, 20.0]]
+        );
+        assert_eq!(simplify_rdp(&series, 0.5), vec![[0.0, 0.0], [4.0, 20.0]]);
+        assert_eq!(
+            simplify_rdp_series(&series, 1.0),
+            vec![[0.0, 0.0], [4.0, 20.0]]
+        );
+        assert!(simplify_rdp_series(&[], 1.0).is_empty());
+    }
+    #[test]
+    fn test_rdp_mask() {
+        assert_eq!(
+            simplify_rdp_mask(&RDP_INPUT, 1.0),
+            vec![true, true, true, false, true]
+        );
+        assert!(simplify_rdp_mask(&[], 1.0).is_empty());
+    }
+    #[test]
+    fn test_rdp_boundaries() {
+        let line = [
+            [0.0, 0.0],
+            [3.0, 0.2],
+            [4.9, -0.1],
+            [5.2, 0.1],
+            [7.0, -0.2],
+            [10.0, 0.0],
+        ];
+        assert_eq!(simplify_rdp(&line, 1.0), vec![[0.0, 0.0], [10.0, 0.0]]);
+        // the vertex nearest the tile edge at x = 5 is retained
+        assert_eq!(
+            simplify_rdp_boundaries(&line, 1.0, &[5.0]),
+            vec![[0.0, 0.0], [4.9, -0.1], [10.0, 0.0]]
+        );
+        // boundaries the line doesn't cross have no effect
+        assert_eq!(
+            simplify_rdp_boundaries(&line, 1.0, &[-5.0, 20.0]),
+            vec![[0.0, 0.0], [10.0, 0.0]]
+        );
+    }
+    #[test]
+    fn test_rdp_extrema() {
+        // a series with a spike, and its lowest point in a dip
+        let line = [
+            [0.0, 0.0],
+            [1.0, 0.1],
+            [2.0, 0.8],
+            [3.0, 0.0],
+            [4.0, -0.3],
+            [5.0, 0.0],
+        ];
+        assert_eq!(simplify_rdp(&line, 1.0), vec![[0.0, 0.0], [5.0, 0.0]]);
+        assert_eq!(
+            simplify_rdp_extrema(&line, 1.0),
+            vec![[0.0, 0.0], [2.0, 0.8], [4.0, -0.3], [5.0, 0.0]]
+        );
+        assert!(simplify_rdp_extrema(&[], 1.0).is_empty());
+    }
+    #[test]
+    fn test_rdp_corners() {
+        // a gentle bend, followed by a step formed by two right angles
+        let line = [
+            [0.0, 0.0],
+            [10.0, 0.4],
+            [20.0, 0.0],
+            [20.0, 1.0],
+            [40.0, 1.0],
+        ];
+        assert_eq!(simplify_rdp(&line, 2.0), vec![[0.0, 0.0], [40.0, 1.0]]);
+        assert_eq!(
+            simplify_rdp_corners(&line, 2.0, 100.0),
+            vec![[0.0, 0.0], [20.0, 0.0], [20.0, 1.0], [40.0, 1.0]]
+        );
+        // the gentle bend descends to (20, 0), so the corner there is slightly sharper than 90°
+        assert_eq!(
+            simplify_rdp_corners(&line, 2.0, 89.0),
+            vec![[0.0, 0.0], [20.0, 0.0], [40.0, 1.0]]
+        );
+        assert_eq!(
+            simplify_rdp_corners(&line, 2.0, 0.0),
+            simplify_rdp_symmetric(&line, 2.0)
+        );
+        // a duplicate vertex has no angle
+        let duplicated = [[0.0, 0.0], [1.0, 0.0], [1.0, 0.0], [2.0, 0.0]];
+        assert_eq!(
+            simplify_rdp_corners(&duplicated, 1.0, 180.0),
+            vec![[0.0, 0.0], [2.0, 0.0]]
+        );
+    }
+    #[test]
+    fn test_rdp_timed() {
+        let line = [[0.0, 0.0], [1.0, 0.1], [2.0, 0.0], [3.0, 0.1], [4.0, 0.0]];
+        // a ten-minute stop at the third vertex
+        let times = [0.0, 10.0, 20.0, 620.0, 630.0];
+        assert_eq!(simplify_rdp(&line, 1.0), vec![[0.0, 0.0], [4.0, 0.0]]);
+        assert_eq!(
+            simplify_rdp_timed(&line, &times, 1.0, 300.0),
+            vec![[0.0, 0.0], [2.0, 0.0], [3.0, 0.1], [4.0, 0.0]]
+        );
+        assert_eq!(
+            simplify_rdp_timed(&line, &times, 1.0, 600.0),
+            vec![[0.0, 0.0], [4.0, 0.0]]
+        );
+    }
+    #[test]
+    fn test_rdp_pinned() {
+        let line = [[0.0, 0.0], [1.0, 0.1], [2.0, 0.0], [3.0, 0.1], [4.0, 0.0]];
+        let pinned = [false, false, true, false, false];
+        assert_eq!(
+            simplify_rdp_pinned(&line, &pinned, 1.0),
+            vec![[0.0, 0.0], [2.0, 0.0], [4.0, 0.0]]
+        );
+        assert_eq!(
+            simplify_rdp_pinned(&line, &[false; 5], 1.0),
+            simplify_rdp(&line, 1.0)
+        );
+        assert_eq!(simplify_rdp_pinned(&line, &[true; 5], 1.0), line.to_vec());
+    }
+    #[test]
+    fn test_rdp_target_ratio() {
+        let line: Vec<[f64; 2]> = (0..10)
+            .map(|x| [f64::from(x), f64::from(x * x % 7)])
+            .collect();
+        for ratio in [0.2, 0.5, 0.7] {
+            let target = (ratio * 10.0_f64).round() as usize;
+            let simplified = simplify_rdp_target_ratio(&line, ratio);
+            assert!(simplified.len().abs_diff(target) <= 1);
+        }
+        assert_eq!(simplify_rdp_target_ratio(&line, 1.0), line);
+        assert_eq!(simplify_rdp_target_ratio(&line, 2.0), line);
+        assert_eq!(simplify_rdp_target_ratio(&line, 0.0).len(), 2);
+        assert_eq!(simplify_rdp_target_ratio(&line, f64::NAN).len(), 2);
+    }
+    #[test]
+    fn test_best() {
+        // RDP keeps only the peak, while every VW result within the tolerance keeps more
+        let line = [[0.0, 4.7], [2.0, 7.8], [4.0, 3.0], [6.0, 2.6], [8.0, 1.8]];
+        let rdp = simplify_rdp(&line, 2.0);
+        assert_eq!(rdp, vec![[0.0, 4.7], [2.0, 7.8], [8.0, 1.8]]);
+        assert!((0..1000)
+            .map(|area| simplify_vw(&line, f64::from(area) * 0.05))
+            .filter(|vw| within_tolerance(&line, vw, 2.0))
+            .all(|vw| vw.len() > rdp.len()));
+        assert_eq!(simplify_best(&line, 2.0), rdp);
+        // RDP splits at the farthest point, which it then has to keep
+        let line = [
+            [0.0, 3.8],
+            [2.0, 4.4],
+            [4.0, 3.1],
+            [6.0, 4.2],
+            [8.0, 0.6],
+            [10.0, 2.1],
+            [12.0, 9.8],
+        ];
+        assert_eq!(simplify_rdp(&line, 2.0).len(), 4);
+        let best = simplify_best(&line, 2.0);
+        assert_eq!(best, vec![[0.0, 3.8], [10.0, 2.1], [12.0, 9.8]]);
+        assert!(within_tolerance(&line, &best, 2.0));
+        assert!(simplify_best(&[], 1.0).is_empty());
+    }
+    #[test]
+    fn test_bounding_box() {
+        assert_eq!(bounding_box(&RDP_INPUT), Some([0.0, 0.0, 27.8, 5.5]));
+        assert_eq!(bounding_box(&[[1.0, 2.0]]), Some([1.0, 2.0, 1.0, 2.0]));
+        assert_eq!(bounding_box(&[]), None);
+    }
+    #[test]
+    fn test_rdp_range() {
+        let line = [[-1.0, 5.0], [0.0, 0.0], [1.0, 0.1], [2.0, 0.0], [3.0, 5.0]];
+        assert_eq!(
+            simplify_rdp_range(&line, 1, 3, 1.0),
+            Some(vec![[-1.0, 5.0], [0.0, 0.0], [2.0, 0.0], [3.0, 5.0]])
+        );
+        assert_eq!(
+            simplify_rdp_range(&RDP_INPUT, 0, RDP_INPUT.len() - 1, 1.0),
+            Some(simplify_rdp(&RDP_INPUT, 1.0))
+        );
+        assert_eq!(simplify_rdp_range(&line, 2, 2, 1.0), Some(line.to_vec()));
+        assert_eq!(simplify_rdp_range(&line, 3, 1, 1.0), None);
+        assert_eq!(simplify_rdp_range(&line, 1, 5, 1.0), None);
+    }
+    #[test]
+    fn test_merge_simplify_rdp() {
+        let a = [[0.0, 0.0], [1.0, 0.5], [2.0, 0.0]];
+        let b = [[2.0, 0.0], [3.0, -0.5], [4.0, 0.0]];
+        // the junction is retained as an endpoint of each line
+        assert_eq!(simplify_rdp(&a, 1.0), vec![[0.0, 0.0], [2.0, 0.0]]);
+        assert_eq!(simplify_rdp(&b, 1.0), vec![[2.0, 0.0], [4.0, 0.0]]);
+        assert_eq!(
+            merge_simplify_rdp(&a, &b, 1.0),
+            vec![[0.0, 0.0], [4.0, 0.0]]
+        );
+        // the shared vertex appears once
+        assert_eq!(merge_simplify_rdp(&a, &b, 0.0).len(), 5);
+        // unconnected lines are joined by a segment
+        assert_eq!(merge_simplify_rdp(&a, &b[1..], 0.0).len(), 5);
+        assert_eq!(merge_simplify_rdp(&[], &b, 0.0), b.to_vec());
+    }
+    #[test]
+    fn test_rdp_variable() {
+        assert_eq!(
+            simplify_rdp_variable(&RDP_INPUT, &[1.0; 5]),
+            simplify_rdp(&RDP_INPUT, 1.0)
+        );
+        // a higher tolerance for the point which deviated least removes it too
+        assert_eq!(
+            simplify_rdp_variable(&RDP_INPUT, &[1.0, 5.0, 1.0, 1.0, 1.0]),
+            vec![[0.0, 0.0], [11.0, 5.5], [27.8, 0.1]]
+        );
+        assert!(simplify_rdp_variable(&[], &[]).is_empty());
+    }
+    #[test]
+    fn test_rdp_density() {
+        let line: Vec<[f64; 2]> = (0..10).map(|x| [f64::from(x), 0.0]).collect();
+        assert_eq!(
+            simplify_rdp_density(&line, 1.0, 3),
+            vec![[0.0, 0.0], [4.0, 0.0], [8.0, 0.0], [9.0, 0.0]]
+        );
+        assert_eq!(simplify_rdp_density(&line, 1.0, 0), line);
+        assert_eq!(
+            simplify_rdp_density(&RDP_INPUT, 1.0, usize::MAX),
+            simplify_rdp(&RDP_INPUT, 1.0)
+        );
+        assert!(simplify_rdp_density(&[], 1.0, 1).is_empty());
+    }
+    #[test]
+    fn test_rdp_max_segment() {
+        let line = [[0.0, 0.0], [1.0, 0.1], [2.0, 0.0], [3.0, 0.1], [4.0, 0.0]];
+        assert_eq!(simplify_rdp(&line, 1.0), vec![[0.0, 0.0], [4.0, 0.0]]);
+        assert_eq!(
+            simplify_rdp_max_segment(&line, 1.0, 3.0),
+            vec![[0.0, 0.0], [2.0, 0.0], [4.0, 0.0]]
+        );
+        assert_eq!(simplify_rdp_max_segment(&line, 1.0, 1.5), line.to_vec());
+        // an input segment which is too long can't be split
+        assert_eq!(simplify_rdp_max_segment(&line, 1.0, 0.5), line.to_vec());
+        assert_eq!(
+            simplify_rdp_max_segment(&RDP_INPUT, 1.0, f64::INFINITY),
+            simplify_rdp(&RDP_INPUT, 1.0)
+        );
+        assert!(simplify_rdp_max_segment(&[], 1.0, 1.0).is_empty());
+    }
+    #[test]
+    fn test_rdp_i32() {
+        let line = [[0, 0], [5, 4], [11, 6], [17, 3], [28, 0]];
+        let converted = line.map(|[x, y]| [f64::from(x), f64::from(y)]);
+        let simplified = simplify_rdp_i32(&line, 1.0);
+        assert_eq!(simplified, vec![[0, 0], [5, 4], [11, 6], [28, 0]]);
+        assert_eq!(
+            simplified,
+            simplify_rdp(&converted, 1.0)
+                .into_iter()
+                .map(|[x, y]| [x as i32, y as i32])
+                .collect::<Vec<_>>()
+        );
+        // coordinates far beyond f32's exact range are retained exactly
+        let large = [[i32::MIN, i32::MIN], [0, 16], [i32::MAX, i32::MAX]];
+        assert_eq!(simplify_rdp_i32(&large, 1.0), large.to_vec());
+        assert!(simplify_rdp_i32(&[], 1.0).is_empty());
+    }
+    #[test]
+    fn test_rdp_snapped() {
+        // two features' copies of a shared boundary, digitised in opposite directions, which
+        // differ by less than half a grid cell
+        let boundary = [
+            [0.0, 0.0],
+            [1.0, 0.3],
+            [2.0, 0.1],
+            [3.0, 0.8],
+            [4.0, 0.2],
+            [5.0, 0.5],
+            [6.0, 1.5],
+            [7.0, 0.2],
+            [8.0, 0.6],
+            [9.0, 0.1],
+            [10.0, 0.0],
+        ];
+        let a: Vec<[f64; 2]> = boundary
+            .iter()
+            .enumerate()
+            .map(|(i, &[x, y])| [x + 0.01 * (i % 3) as f64, y - 0.02 * (i % 2) as f64])
+            .collect();
+        let b: Vec<[f64; 2]> = boundary
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(i, &[x, y])| [x - 0.03 * (i % 2) as f64, y + 0.02 * (i % 3) as f64])
+            .collect();
+        let mut reversed = simplify_rdp_snapped(&b, 0.25, 0.1);
+        reversed.reverse();
+        let shared = simplify_rdp_snapped(&a, 0.25, 0.1);
+        assert_eq!(shared, reversed);
+        assert!(shared.len() < boundary.len());
+        // the retained vertices are those of the boundary, snapped to the grid
+        let snapped = simplify_rdp_snapped(&boundary, 0.0, 0.1);
+        assert_eq!(snapped.len(), boundary.len());
+        assert!(shared.iter().all(|vertex| snapped.contains(vertex)));
+        // without snapping, the copies' retained vertices don't coincide
+        let mut reversed = simplify_rdp_snapped(&b, 0.25, 0.0);
+        reversed.reverse();
+        assert_ne!(simplify_rdp_snapped(&a, 0.25, 0.0), reversed);
+        // snapping can produce consecutive duplicates, which are removed
+        assert_eq!(
+            simplify_rdp_snapped(&[[0.0, 0.0], [0.04, 0.0], [1.0, 0.0]], 0.0, 0.1),
+            vec![[0.0, 0.0], [1.0, 0.0]]
+        );
+    }
+    #[test]
+    fn test_rdp_scaled() {
+        let line = [[0.0, 0.0], [1.0, 0.5], [2.0, 0.0], [3.0, 0.0]];
+        assert_eq!(
+            simplify_rdp_scaled(&line, 1.0, 1.0, 1.0),
+            simplify_rdp(&line, 1.0)
+        );
+        assert_eq!(
+            simplify_rdp_scaled(&line, 1.0, 1.0, 1.0),
+            vec![[0.0, 0.0], [3.0, 0.0]]
+        );
+        // stretching y moves the peak beyond the tolerance, but it's returned unscaled
+        assert_eq!(
+            simplify_rdp_scaled(&line, 1.0, 1.0, 4.0),
+            vec![[0.0, 0.0], [1.0, 0.5], [3.0, 0.0]]
+        );
+        // unscaling 0.1 would produce 0.10000000000000002, but the input is copied through
+        let simplified = simplify_rdp_scaled(&[[0.0, 0.0], [0.05, 1.0], [0.1, 0.0]], 1.0, 3.0, 3.0);
+        assert_eq!(simplified.last().unwrap()[0].to_bits(), 0.1f64.to_bits());
+        // uniform scaling is equivalent to scaling the tolerance
+        assert_eq!(
+            simplify_rdp_scaled(&line, 1.0, 4.0, 4.0),
+            simplify_rdp(&line, 0.25)
+        );
+    }
+    #[test]
+    fn test_vw_relative() {
+        // the line is 10 long
+        let line = [[0.0, 0.0], [3.0, 4.0], [6.0, 0.0]];
+        assert_eq!(simplify_vw_relative(&line, 0.1), line.to_vec());
+        assert_eq!(
+            simplify_vw_relative(&line, 0.2),
+            vec![[0.0, 0.0], [6.0, 0.0]]
+        );
+        assert_eq!(simplify_vw_relative(&line, 0.2), simplify_vw(&line, 20.0));
+        assert!(simplify_vw_relative(&[], 0.1).is_empty());
+    }
+    #[test]
+    fn test_rdp_relative() {
+        // the diagonal of the bounding box is 5
+        let line = [[0.0, 0.0], [1.0, 1.6], [2.0, 3.0], [3.0, 1.4], [4.0, 0.0]];
+        assert_eq!(diagonal(&line), 5.0);
+        assert_eq!(
+            simplify_rdp_relative(&line, 0.1),
+            vec![[0.0, 0.0], [2.0, 3.0], [4.0, 0.0]]
+        );
+        assert_eq!(simplify_rdp_relative(&line, 0.1), simplify_rdp(&line, 0.5));
+        // the retained points don't depend on the line's scale
+        let points: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let scaled: Vec<[f64; 2]> = points.iter().map(|&[x, y]| [x * 10.0, y * 10.0]).collect();
+        for fraction in [0.0001, 0.001, 0.005, 0.01, 0.1] {
+            let simplified: Vec<[f64; 2]> = simplify_rdp_relative(&points, fraction)
+                .into_iter()
+                .map(|[x, y]| [x * 10.0, y * 10.0])
+                .collect();
+            assert_eq!(
+                simplified,
+                simplify_rdp_relative(&scaled, fraction),
+                "{}",
+                fraction
+            );
+        }
+        assert!(simplify_rdp_relative(&[], 0.1).is_empty());
+    }
+    #[test]
+    fn test_rdp_max_points() {
+        let route: Vec<[f64; 2]> = (0..200)
+            .map(|i| {
+                let x = i as f64;
+                [x, (x / 7.0).sin() * 10.0 + (x / 3.0).cos()]
+            })
+            .collect();
+        for cap in [2, 3, 10, 50, 199] {
+            let simplified = simplify_rdp_max_points(&route, cap);
+            assert!(simplified.len() <= cap, "{} > {}", simplified.len(), cap);
+            assert_eq!(simplified.first(), route.first());
+            assert_eq!(simplified.last(), route.last());
+        }
+        // a tiny cap is clamped to a valid two-point line
+        assert_eq!(
+            simplify_rdp_max_points(&route, 0),
+            vec![route[0], route[199]]
+        );
+        // lines under the cap are unchanged
+        assert_eq!(simplify_rdp_max_points(&RDP_INPUT, 5), RDP_INPUT.to_vec());
+        // the smallest sufficient tolerance is used, so no more points are removed than necessary
+        assert_eq!(
+            simplify_rdp_max_points(&RDP_INPUT, 4),
+            simplify_rdp(&RDP_INPUT, 1.0)
+        );
+    }
+    #[test]
+    fn test_rdp_significance() {
+        let route: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let significance = rdp_significance(&route);
+        assert_eq!(significance.len(), route.len() - 2);
+        assert!(significance.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+        for tolerance in [0.00001, 0.0001, 0.001, 0.01, 0.1] {
+            let mut retained: Vec<usize> = significance
+                .iter()
+                .take_while(|(_, s)| *s > tolerance)
+                .map(|(idx, _)| *idx)
+                .chain([0, route.len() - 1])
+                .collect();
+            retained.sort_unstable();
+            assert_eq!(
+                retained,
+                simplify_rdp_idx(&route, tolerance),
+                "{}",
+                tolerance
+            );
+        }
+        // (17.3, 3.2) is the least significant point, 0.26 from the segment joining its neighbours
+        let significance = rdp_significance(&RDP_INPUT);
+        assert_eq!(significance[2].0, 3);
+        assert!((significance[2].1 - 0.2618).abs() < 1e-4);
+        assert!(rdp_significance(&RDP_INPUT[..2]).is_empty());
+    }
+    #[test]
+    fn test_rdp_sweep() {
+        let route: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let tolerances = [0.0, 0.00001, 0.0001, 0.001, 0.01, 0.1, 1.0];
+        let counts = simplify_rdp_sweep(&route, &tolerances);
+        assert!(counts.windows(2).all(|pair| pair[0] >= pair[1]));
+        for (tolerance, count) in tolerances.iter().zip(&counts) {
+            assert_eq!(*count, simplify_rdp(&route, *tolerance).len());
+        }
+        // unsorted tolerances produce the same counts for each value
+        assert_eq!(
+            simplify_rdp_sweep(&route, &[0.01, 0.0001, 1.0]),
+            vec![counts[4], counts[2], counts[6]]
+        );
+        assert_eq!(simplify_rdp_sweep(&RDP_INPUT[..2], &[1.0]), vec![2]);
+        assert_eq!(simplify_rdp_sweep(&[], &[1.0]), vec![0]);
+    }
+    #[test]
+    fn test_rdp_lod() {
+        let route: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let tolerances = [0.0, 0.0001, 0.001, 0.01];
+        let levels = simplify_rdp_lod(&route, &tolerances);
+        for (tolerance, level) in tolerances.iter().zip(&levels) {
+            assert_eq!(*level, simplify_rdp(&route, *tolerance));
+        }
+        // each level is a subset of the one before
+        for pair in levels.windows(2) {
+            assert!(pair[1].iter().all(|point| pair[0].contains(point)));
+        }
+        assert!(simplify_rdp_lod(&[], &[1.0])[0].is_empty());
+        assert!(simplify_rdp_lod(&route, &[]).is_empty());
+    }
+    #[test]
+    fn test_rdp_bounded() {
+        assert_eq!(
+            simplify_rdp_bounded(&RDP_INPUT, 1.0, usize::MAX),
+            (simplify_rdp(&RDP_INPUT, 1.0), false)
+        );
+        // splitting the whole line computes three distances, leaving none to split its halves
+        assert_eq!(
+            simplify_rdp_bounded(&RDP_INPUT, 1.0, 3),
+            (vec![[0.0, 0.0], [11.0, 5.5], [27.8, 0.1]], true)
+        );
+    }
+    #[test]
+    fn test_rdp_floor() {
+        let coords = [
+            [0.0, 0.0],
+            [1.0, 0.0],
+            [2.0, 0.6],
+            [3.0, 0.0],
+            [4.0, -0.3],
+            [5.0, 0.0],
+            [6.0, 0.0],
+        ];
+        assert_eq!(simplify_rdp(&coords, 1.0), vec![[0.0, 0.0], [6.0, 0.0]]);
+        // (2, 0.6) is 0.6 from the baseline. Once it's retained, (4, -0.3) is ~0.59 from the
+        // segment joining (2, 0.6) and (6, 0), while (1, 0) is only ~0.29 from its segment
+        assert_eq!(
+            simplify_rdp_floor(&coords, 1.0, 4),
+            vec![[0.0, 0.0], [2.0, 0.6], [4.0, -0.3], [6.0, 0.0]]
+        );
+        // the floor has no effect if the tolerance retains enough points
+        assert_eq!(
+            simplify_rdp_floor(&coords, 0.1, 2),
+            simplify_rdp(&coords, 0.1)
+        );
+        assert_eq!(simplify_rdp_floor(&coords, 1.0, 7), coords.to_vec());
+        assert_eq!(simplify_rdp_floor(&coords, 1.0, 10), coords.to_vec());
+    }
+    #[test]
+    fn test_rdp_symmetric() {
+        let route: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let reversed: Vec<[f64; 2]> = route.iter().rev().copied().collect();
+        let mut backward = simplify_rdp_symmetric(&reversed, 0.001);
+        backward.reverse();
+        assert_eq!(simplify_rdp_symmetric(&route, 0.001), backward);
+    }
+    #[test]
+    fn test_rdp_shared() {
+        // two squares sharing a wiggly edge from (10, 0) to (10, 10), traversed in opposite directions
+        let wiggle = [
+            [10.0, 0.0],
+            [10.3, 2.0],
+            [9.8, 4.1],
+            [10.1, 5.0],
+            [9.6, 7.2],
+            [10.2, 8.5],
+            [10.0, 10.0],
+        ];
+        let mut left = vec![[0.0, 10.0], [0.0, 0.0]];
+        left.extend(wiggle);
+        left.push([0.0, 10.0]);
+        let mut right: Vec<[f64; 2]> = vec![[20.0, 0.0], [20.0, 10.0]];
+        right.extend(wiggle.iter().rev());
+        right.push([20.0, 0.0]);
+        let shared_left: &[usize] = &[1, 7];
+        let shared_right: &[usize] = &[2, 8];
+        let simplified = simplify_rdp_shared(&[&left, &right], &[shared_left, shared_right], 0.3);
+        let edge = |line: &[[f64; 2]]| -> Vec<[f64; 2]> {
+            let mut edge: Vec<[f64; 2]> = line
+                .iter()
+                .copied()
+                .filter(|c| (9.0..=11.0).contains(&c[0]))
+                .collect();
+            edge.sort_by(|a, b| a[1].total_cmp(&b[1]));
+            edge
+        };
+        let left_edge = edge(&simplified[0]);
+        assert_eq!(left_edge, edge(&simplified[1]));
+        // the edge was simplified, but its endpoints were retained
+        assert!(left_edge.len() < wiggle.len());
+        assert_eq!(left_edge.first(), Some(&[10.0, 0.0]));
+        assert_eq!(left_edge.last(), Some(&[10.0, 10.0]));
+        // lines without an entry in shared are simplified as usual
+        assert_eq!(
+            simplify_rdp_shared(&[&RDP_INPUT], &[], 1.0),
+            vec![simplify_rdp(&RDP_INPUT, 1.0)]
+        );
+    }
+    #[test]
+    fn test_max_deviation() {
+        let original = [[0.0, 0.0], [3.0, 4.0], [6.0, 0.0], [10.0, 0.0]];
+        assert_eq!(
+            max_deviation(&original, &[[0.0, 0.0], [10.0, 0.0]]),
+            Some(4.0)
+        );
+        assert_eq!(max_deviation(&original, &original), Some(0.0));
+        // (3, 4) is 5 units from the only point
+        assert_eq!(max_deviation(&original[..2], &[[0.0, 0.0]]), Some(5.0));
+        // the deviation of a simplification never exceeds its tolerance
+        let simplified = simplify_rdp(&RDP_INPUT, 1.0);
+        assert!(max_deviation(&RDP_INPUT, &simplified).unwrap() <= 1.0);
+        assert_eq!(max_deviation(&[], &[[0.0, 0.0]]), None);
+        assert_eq!(max_deviation(&original, &[]), None);
+        assert_eq!(max_deviation(&original[..1], &original), None);
+    }
+    #[test]
+    fn test_within_tolerance() {
+        let original = [[0.0, 0.0], [3.0, 4.0], [6.0, 0.0], [10.0, 0.0]];
+        let simplified = [[0.0, 0.0], [10.0, 0.0]];
+        assert!(within_tolerance(&original, &simplified, 4.0));
+        assert!(!within_tolerance(&original, &simplified, 3.9));
+        assert!(within_tolerance(&original, &original, 0.0));
+        for tolerance in [0.0001, 0.001, 0.01] {
+            let points: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+            let simplified = simplify_rdp(&points, tolerance);
+            assert!(within_tolerance(&points, &simplified, tolerance));
+            // the simplification at twice the tolerance is too coarse
+            let coarse = simplify_rdp(&points, tolerance * 2.0);
+            assert!(!within_tolerance(&points, &coarse, tolerance));
+        }
+        assert!(!within_tolerance(&[], &simplified, 1.0));
+        assert!(!within_tolerance(&original, &[], 1.0));
+        assert!(!within_tolerance(&simplified, &original, 100.0));
+    }
+    #[test]
+    fn test_hausdorff_distance() {
+        let baseline = [[0.0, 0.0], [10.0, 0.0]];
+        // the apex of the tent is 3 from the baseline, which is no more than 1 from the tent
+        let tent = [[0.0, 1.0], [5.0, 3.0], [10.0, 1.0]];
+        assert_eq!(hausdorff_distance(&baseline, &tent), Some(3.0));
+        assert_eq!(hausdorff_distance(&tent, &baseline), Some(3.0));
+        // distances are measured to segments, not vertices: (5, 1) is 1 from the baseline
+        let parallel = [[0.0, 1.0], [5.0, 1.0], [10.0, 1.0]];
+        assert_eq!(hausdorff_distance(&baseline, &parallel), Some(1.0));
+        // the endpoints of the baseline are 5.099 from a single point at (5, 1)
+        let distance = hausdorff_distance(&baseline, &[[5.0, 1.0]]).unwrap();
+        assert!((distance - 26.0_f64.sqrt()).abs() < 1e-12);
+        assert_eq!(hausdorff_distance(&baseline, &baseline), Some(0.0));
+        assert_eq!(hausdorff_distance(&[], &baseline), None);
+        assert_eq!(hausdorff_distance(&baseline, &[]), None);
+    }
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_simplify_then_smooth() {
+        let simplified = simplify_rdp(&RDP_INPUT, 1.0);
+        assert_eq!(simplify_then_smooth(&RDP_INPUT, 1.0, 0), simplified);
+        let mut previous = simplified.len();
+        for iterations in 1..5 {
+            let smoothed = simplify_then_smooth(&RDP_INPUT, 1.0, iterations);
+            assert!(smoothed.len() > previous);
+            previous = smoothed.len();
+            assert_eq!(smoothed[0], RDP_INPUT[0]);
+            assert_eq!(smoothed[smoothed.len() - 1], RDP_INPUT[RDP_INPUT.len() - 1]);
+        }
+        // a corner is replaced by points a quarter of the way along each segment
+        assert_eq!(
+            simplify_then_smooth(&[[0.0, 0.0], [4.0, 4.0], [8.0, 0.0]], 1.0, 1),
+            vec![
+                [0.0, 0.0],
+                [1.0, 1.0],
+                [3.0, 3.0],
+                [5.0, 3.0],
+                [7.0, 1.0],
+                [8.0, 0.0]
+            ]
+        );
+        assert!(simplify_then_smooth(&[], 1.0, 2).is_empty());
+    }
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_vw() {
+        assert_eq!(
+            simplify_vw(&VW_INPUT, 30.0),
+            vec![[5.0, 2.0], [7.0, 25.0], [10.0, 10.0]]
+        );
+        assert_eq!(simplify_vw_idx(&VW_INPUT, 30.0), vec![0, 3, 4]);
+        assert_eq!(
+            simplify_vw_preserve(&VW_INPUT, 30.0),
+            vec![[5.0, 2.0], [7.0, 25.0], [10.0, 10.0]]
+        );
+    }
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_vw_preserve_idx() {
+        use geo::{Intersects, Line};
+
+        // whether any two non-adjacent segments of the line intersect
+        fn self_intersects(coords_in: &[[f64; 2]]) -> bool {
+            let segments: Vec<Line<f64>> = coords_in
+                .windows(2)
+                .map(|pair| Line::new(pair[0], pair[1]))
+                .collect();
+            segments
+                .iter()
+                .enumerate()
+                .any(|(i, a)| segments.iter().skip(i + 2).any(|b| a.intersects(b)))
+        }
+        let line = [
+            [10., 60.],
+            [135., 68.],
+            [94., 48.],
+            [126., 31.],
+            [280., 19.],
+            [117., 48.],
+            [300., 40.],
+            [301., 10.],
+        ];
+        assert!(!self_intersects(&line));
+        // whether the retained points of a line intersect
+        let retained_intersect = |coords_in: &[[f64; 2]], indices: &[usize]| {
+            self_intersects(
+                &indices
+                    .iter()
+                    .map(|&idx| coords_in[idx])
+                    .collect::<Vec<_>>(),
+            )
+        };
+        // plain VW removes points regardless of the consequences
+        assert!(retained_intersect(&line, &simplify_vw_idx(&line, 668.6)));
+        let preserved = simplify_vw_preserve_idx(&line, 668.6);
+        assert_eq!(preserved, vec![0, 3, 4, 5, 6, 7]);
+        assert!(!retained_intersect(&line, &preserved));
+        let coords_out: Vec<[f64; 2]> = preserved.iter().map(|&idx| line[idx]).collect();
+        assert_eq!(coords_out, simplify_vw_preserve(&line, 668.6));
+        assert_eq!(simplify_vw_preserve_idx(&line[..2], 668.6), vec![0, 1]);
+
+        // preventing an intersection can leave more points than VW
+        let zigzag = [
+            [5.0, 7.0],
+            [9.0, 0.0],
+            [2.0, 1.0],
+            [6.0, 5.0],
+            [2.0, 6.0],
+            [5.0, 8.0],
+            [10.0, 5.0],
+        ];
+        let naive = simplify_vw_idx(&zigzag, 8.5);
+        assert_eq!(naive, vec![0, 1, 2, 6]);
+        assert!(retained_intersect(&zigzag, &naive));
+        let preserved = simplify_vw_preserve_idx(&zigzag, 8.5);
+        assert_eq!(preserved, vec![0, 1, 2, 5, 6]);
+        assert!(!retained_intersect(&zigzag, &preserved));
+
+        // removing the shallow dip at (5, -1) makes the first segment cross the third, but the
+        // point preceding it is the first point, which can't be removed, so the crossing remains
+        let hook = [
+            [0.0, 0.0],
+            [5.0, -1.0],
+            [10.0, 0.0],
+            [10.0, 5.0],
+            [5.0, -0.5],
+            [0.0, 5.0],
+        ];
+        assert!(!self_intersects(&hook));
+        let preserved = simplify_vw_preserve_idx(&hook, 6.0);
+        assert_eq!(preserved, vec![0, 2, 3, 4, 5]);
+        assert!(retained_intersect(&hook, &preserved));
+
+        // repeated coordinates are told apart by index: the second (2, 6) is retained
+        let repeated = [
+            [0.0, 1.0],
+            [2.0, 6.0],
+            [5.0, 1.0],
+            [2.0, 6.0],
+            [2.0, 5.0],
+            [1.0, 1.0],
+        ];
+        let preserved = simplify_vw_preserve_idx(&repeated, 1.5);
+        assert_eq!(preserved, simplify_vw_idx(&repeated, 1.5));
+        assert_eq!(preserved, vec![0, 3, 5]);
+        let coords_out: Vec<[f64; 2]> = preserved.iter().map(|&idx| repeated[idx]).collect();
+        assert_eq!(coords_out, simplify_vw_preserve(&repeated, 1.5));
+    }
+    #[test]
+    fn test_rdp_simplifier() {
+        let rdp = RdpSimplifier { tolerance: 1.0 };
+        assert_eq!(rdp.simplify(&RDP_INPUT), simplify_rdp(&RDP_INPUT, 1.0));
+        assert!(rdp.simplify(&[]).is_empty());
+    }
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_simplifiers() {
+        let vw = VwSimplifier { epsilon: 30.0 };
+        assert_eq!(vw.simplify(&VW_INPUT), simplify_vw(&VW_INPUT, 30.0));
+        let preserve = VwPreserveSimplifier { epsilon: 30.0 };
+        assert_eq!(
+            preserve.simplify(&VW_INPUT),
+            simplify_vw_preserve(&VW_INPUT, 30.0)
+        );
+
+        let simplifiers: Vec<Box<dyn Simplifier>> = vec![
+            Box::new(RdpSimplifier { tolerance: 1.0 }),
+            Box::new(VwSimplifier { epsilon: 30.0 }),
+            Box::new(VwPreserveSimplifier { epsilon: 30.0 }),
+        ];
+        let results: Vec<Vec<[f64; 2]>> = simplifiers
+            .iter()
+            .map(|simplifier| simplifier.simplify(&VW_INPUT))
+            .collect();
+        assert_eq!(
+            results,
+            vec![
+                simplify_rdp(&VW_INPUT, 1.0),
+                vec![[5.0, 2.0], [7.0, 25.0], [10.0, 10.0]],
+                vec![[5.0, 2.0], [7.0, 25.0], [10.0, 10.0]],
+            ]
+        );
+    }
+}
@@ -0,0 +1,676 @@
+//! Simplification of many geometries in a single FFI call
+//!
+//! If the `rayon` feature is enabled, the geometries in a batch are simplified in parallel.
+
+use std::sync::{Mutex, PoisonError};
+use std::{ptr, slice};
+
+use geo::simplify::Simplify;
+use geo::simplify_vw::SimplifyVw;
+use geo::LineString;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::api::{self, simplify_with};
+use crate::ffi::{guarded, leak, reclaim};
+use crate::{drop_float_array, ExternalArray, InternalArray};
+
+// Validated input coordinates, or None if the geometry failed validation
+type BatchInput<'a> = Option<&'a [[f64; 2]]>;
+
+// The number of geometries simplified between calls to a progress callback
+const PROGRESS_INTERVAL: usize = 256;
+
+/// A function receiving the progress of a batch, for use with
+/// [`simplify_rdp_batch_progress_ffi`](fn.simplify_rdp_batch_progress_ffi.html)
+///
+/// It receives the number of geometries simplified so far and the number in the batch, followed by
+/// the caller's `user` pointer.
+pub type ProgressCallback =
+    Option<extern "C" fn(done: libc::size_t, total: libc::size_t, user: *mut libc::c_void)>;
+
+// Counts the geometries simplified so far, reporting the count to a callback every
+// PROGRESS_INTERVAL geometries, and once the batch is complete. The lock is held while the
+// callback runs, so it's never called by more than one thread at a time, and the counts it
+// receives never decrease
+struct Progress {
+    callback: ProgressCallback,
+    user: *mut libc::c_void,
+    total: usize,
+    done: Mutex<usize>,
+}
+
+// The user pointer is only passed to the callback, which is only called while the lock is held
+unsafe impl Sync for Progress {}
+
+impl Progress {
+    fn new(callback: ProgressCallback, user: *mut libc::c_void, total: usize) -> Self {
+        Progress {
+            callback,
+            user,
+            total,
+            done: Mutex::new(0),
+        }
+    }
+
+    // A batch whose progress isn't reported
+    fn none() -> Self {
+        Progress::new(None, ptr::null_mut(), 0)
+    }
+
+    fn tick(&self) {
+        let Some(callback) = self.callback else {
+            return;
+        };
+        let mut done = self.done.lock().unwrap_or_else(PoisonError::into_inner);
+        *done += 1;
+        if done.is_multiple_of(PROGRESS_INTERVAL) || *done == self.total {
+            callback(*done, self.total, self.user);
+        }
+    }
+}
+
+// Simplify a single geometry of a batch. A geometry whose simplification panics produces None
+fn simplify_one<F>(coords: &[[f64; 2]], simplify: &F) -> Option<LineString<f64>>
+where
+    F: Fn(&LineString<f64>) -> LineString<f64>,
+{
+    guarded(None, || {
+        Some(simplify_with(coords.to_vec().into(), simplify))
+    })
+}
+
+// Simplify each geometry in turn
+#[cfg(any(test, not(feature = "rayon")))]
+fn simplify_sequential<F>(
+    inputs: &[BatchInput],
+    simplify: F,
+    progress: &Progress,
+) -> Vec<Option<LineString<f64>>>
+where
+    F: Fn(&LineString<f64>) -> LineString<f64>,
+{
+    inputs
+        .iter()
+        .map(|coords| {
+            let simplified = coords.and_then(|c| simplify_one(c, &simplify));
+            progress.tick();
+            simplified
+        })
+        .collect()
+}
+
+// Simplify the geometries using rayon's thread pool. Output order matches input order
+#[cfg(feature = "rayon")]
+fn simplify_parallel<F>(
+    inputs: &[BatchInput],
+    simplify: F,
+    progress: &Progress,
+) -> Vec<Option<LineString<f64>>>
+where
+    F: Fn(&LineString<f64>) -> LineString<f64> + Sync + Send,
+{
+    inputs
+        .par_iter()
+        .map(|coords| {
+            let simplified = coords.and_then(|c| simplify_one(c, &simplify));
+            progress.tick();
+            simplified
+        })
+        .collect()
+}
+
+// Validate and simplify a batch of geometries, leaking each result so it can cross the FFI boundary
+unsafe fn simplify_batch<F>(
+    arrays: *const ExternalArray,
+    count: libc::size_t,
+    simplify: F,
+    progress: &Progress,
+) -> *mut InternalArray
+where
+    F: Fn(&LineString<f64>) -> LineString<f64> + Sync + Send,
+{
+    if arrays.is_null() || count == 0 {
+        return ptr::null_mut();
+    }
+    // raw pointers can't be shared between threads, so validation happens up front
+    let inputs: Vec<BatchInput> = slice::from_raw_parts(arrays, count)
+        .iter()
+        .map(|coords| coords.validate().ok())
+        .collect();
+    #[cfg(feature = "rayon")]
+    let simplified = simplify_parallel(&inputs, simplify, progress);
+    #[cfg(not(feature = "rayon"))]
+    let simplified = simplify_sequential(&inputs, simplify, progress);
+    let results: Vec<InternalArray> = simplified
+        .into_iter()
+        .map(|ls| ls.map_or_else(InternalArray::empty, InternalArray::from))
+        .collect();
+    leak(results).data as *mut InternalArray
+}
+
+/// FFI wrapper for RDP, simplifying a batch of geometries and returning their **coordinates**
+///
+/// Callers must pass three arguments:
+///
+/// - a pointer to the first of `count` contiguous [Structs](struct.ExternalArray.html), each of which
+///   describes a LineString in the same way as [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html)
+/// - `count`, the number of geometries in the batch. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance, which is applied to every geometry
+///
+/// The returned pointer refers to `count` contiguous `InternalArray`s, in the same order as the input.
+/// A geometry which fails validation (see [`simplify_rdp_checked_ffi`](fn.simplify_rdp_checked_ffi.html))
+/// produces an empty array in its slot. If `arrays` is a null pointer or `count` is 0, a null pointer
+/// is returned.
+///
+/// Implementations calling this function **must** call [`drop_float_array_batch`](fn.drop_float_array_batch.html)
+/// with the returned pointer and the original `count`, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub unsafe extern "C" fn simplify_rdp_batch_ffi(
+    arrays: *const ExternalArray,
+    count: libc::size_t,
+    precision: libc::c_double,
+) -> *mut InternalArray {
+    simplify_batch(
+        arrays,
+        count,
+        |ls| ls.simplify(&precision),
+        &Progress::none(),
+    )
+}
+
+/// FFI wrapper for RDP, simplifying a batch of geometries, reporting progress, and returning their **coordinates**
+///
+/// Callers must pass five arguments:
+///
+/// - a pointer to the first of `count` contiguous [Structs](struct.ExternalArray.html), each of which
+///   describes a LineString in the same way as [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html)
+/// - `count`, the number of geometries in the batch. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance, which is applied to every geometry
+/// - a [`ProgressCallback`](type.ProgressCallback.html), which is called every 256 geometries, and
+///   once every geometry has been simplified. It may be null
+/// - a void pointer, which is passed to each call of the callback unchanged. It may be null
+///
+/// The result is the same as that of [`simplify_rdp_batch_ffi`](fn.simplify_rdp_batch_ffi.html).
+/// Geometries which fail validation count towards the progress, so the last call of the callback
+/// has `done` equal to `total`. If the `rayon` feature is enabled, the callback may be called from
+/// any of rayon's threads, but calls are serialised: it's never called by more than one thread at a
+/// time, and `done` increases with each call. The callback should return quickly, as simplification
+/// waits for it.
+///
+/// Implementations calling this function **must** call [`drop_float_array_batch`](fn.drop_float_array_batch.html)
+/// with the returned pointer and the original `count`, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub unsafe extern "C" fn simplify_rdp_batch_progress_ffi(
+    arrays: *const ExternalArray,
+    count: libc::size_t,
+    precision: libc::c_double,
+    progress: ProgressCallback,
+    user: *mut libc::c_void,
+) -> *mut InternalArray {
+    simplify_batch(
+        arrays,
+        count,
+        |ls| ls.simplify(&precision),
+        &Progress::new(progress, user, count),
+    )
+}
+
+/// FFI wrapper for Visvalingam-Whyatt, simplifying a batch of geometries and returning their **coordinates**
+///
+/// Callers must pass three arguments:
+///
+/// - a pointer to the first of `count` contiguous [Structs](struct.ExternalArray.html), each of which
+///   describes a LineString in the same way as [`simplify_visvalingam_ffi`](fn.simplify_visvalingam_ffi.html)
+/// - `count`, the number of geometries in the batch. Its type must be `size_t`
+/// - a double-precision `float` for the epsilon, which is applied to every geometry
+///
+/// The returned pointer refers to `count` contiguous `InternalArray`s, in the same order as the input.
+/// A geometry which fails validation produces an empty array in its slot.
+/// If `arrays` is a null pointer or `count` is 0, a null pointer is returned.
+///
+/// Implementations calling this function **must** call [`drop_float_array_batch`](fn.drop_float_array_batch.html)
+/// with the returned pointer and the original `count`, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub unsafe extern "C" fn simplify_visvalingam_batch_ffi(
+    arrays: *const ExternalArray,
+    count: libc::size_t,
+    precision: libc::c_double,
+) -> *mut InternalArray {
+    simplify_batch(
+        arrays,
+        count,
+        |ls| ls.simplify_vw(&precision),
+        &Progress::none(),
+    )
+}
+
+/// FFI wrapper for RDP, simplifying a batch of geometries and returning their **indices** in a
+/// single packed array
+///
+/// Callers must pass four arguments:
+///
+/// - a pointer to the first of `count` contiguous [Structs](struct.ExternalArray.html), each of which
+///   describes a LineString in the same way as [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html)
+/// - `count`, the number of geometries in the batch. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance, which is applied to every geometry
+/// - a pointer to an [`InternalArray`](struct.InternalArray.html), which receives the offsets
+///
+/// The returned `Array` contains the `size_t` indices retained from every geometry, concatenated
+/// in input order. The offsets array contains `count + 1` `size_t` values: the indices retained
+/// from geometry `i` are the elements from `offsets[i]` up to (but not including) `offsets[i + 1]`.
+/// A geometry which fails validation retains no indices, so its slice is empty.
+///
+/// If `arrays` or `offsets_out` is a null pointer, or `count` is 0, an empty array is returned,
+/// and `offsets_out` (if it isn't null) receives an empty array.
+///
+/// Implementations calling this function **must** call [`drop_usize_array`](fn.drop_usize_array.html)
+/// with both the returned `Array` and the offsets, in order to free the memory they allocate.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses raw pointers which could contain arbitrary data
+#[no_mangle]
+pub unsafe extern "C" fn simplify_rdp_idx_packed_ffi(
+    arrays: *const ExternalArray,
+    count: libc::size_t,
+    precision: libc::c_double,
+    offsets_out: *mut InternalArray,
+) -> InternalArray {
+    if offsets_out.is_null() {
+        return InternalArray::empty();
+    }
+    if arrays.is_null() || count == 0 {
+        *offsets_out = InternalArray::empty();
+        return InternalArray::empty();
+    }
+    let mut indices = vec![];
+    let mut offsets = Vec::with_capacity(count + 1);
+    offsets.push(0);
+    for coords in slice::from_raw_parts(arrays, count) {
+        if let Ok(valid) = coords.validate() {
+            indices.extend(guarded(vec![], || api::simplify_rdp_idx(valid, precision)));
+        }
+        offsets.push(indices.len());
+    }
+    *offsets_out = leak(offsets);
+    leak(indices)
+}
+
+/// FFI wrapper for RDP, simplifying a batch of geometries which share boundaries and returning their **coordinates**
+///
+/// Callers must pass four arguments:
+///
+/// - a pointer to the first of `count` contiguous [Structs](struct.ExternalArray.html), each of which
+///   describes a LineString in the same way as [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html)
+/// - a pointer to the first of `count` contiguous [Structs](struct.ExternalArray.html), each of which
+///   describes an array of `size_t` indices of the vertices of the corresponding LineString which
+///   must be retained, such as the ends of boundaries shared with other geometries. An entry with a
+///   null `data` pointer pins no vertices, and out-of-bounds indices are ignored
+/// - `count`, the number of geometries in the batch. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance, which is applied to every geometry
+///
+/// Each geometry is split at its pinned vertices, and each section is simplified independently of
+/// its direction, so a boundary pinned at both ends in every geometry containing it is simplified
+/// identically in all of them. See [`api::simplify_rdp_shared`](api/fn.simplify_rdp_shared.html).
+///
+/// The returned pointer refers to `count` contiguous `InternalArray`s, in the same order as the input.
+/// A geometry which fails validation produces an empty array in its slot. If either pointer is null
+/// or `count` is 0, a null pointer is returned.
+///
+/// Implementations calling this function **must** call [`drop_float_array_batch`](fn.drop_float_array_batch.html)
+/// with the returned pointer and the original `count`, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses raw pointers which could contain arbitrary data
+#[no_mangle]
+pub unsafe extern "C" fn simplify_rdp_shared_ffi(
+    arrays: *const ExternalArray,
+    shared: *const ExternalArray,
+    count: libc::size_t,
+    precision: libc::c_double,
+) -> *mut InternalArray {
+    if arrays.is_null() || shared.is_null() || count == 0 {
+        return ptr::null_mut();
+    }
+    let results: Vec<InternalArray> = slice::from_raw_parts(arrays, count)
+        .iter()
+        .zip(slice::from_raw_parts(shared, count))
+        .map(|(coords, pinned)| match coords.validate() {
+            Ok(valid) => {
+                let pinned: &[usize] = if pinned.data.is_null() {
+                    &[]
+                } else {
+                    slice::from_raw_parts(pinned.data as *const usize, pinned.len)
+                };
+                guarded(InternalArray::empty(), || {
+                    let mut simplified = api::simplify_rdp_shared(&[valid], &[pinned], precision);
+                    simplified.pop().unwrap_or_default().into()
+                })
+            }
+            Err(_) => InternalArray::empty(),
+        })
+        .collect();
+    leak(results).data as *mut InternalArray
+}
+
+/// FFI wrapper for RDP, simplifying the components of a MultiLineString and returning their **coordinates**
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a pointer to an array of [Structs](struct.ExternalArray.html), each of which
+///       describes a component LineString in the same way as [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html)
+///     - `len`, the number of components. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance, which is applied to every component
+///
+/// The returned `Array` has the same structure as the input: its `data` field points to `len`
+/// `InternalArray`s, one per component, in the same order as the input. Components are never
+/// dropped or merged, even if they simplify to two points: a component which fails validation
+/// produces an empty array in its slot.
+///
+/// Implementations calling this function **must** call [`drop_multi_float_array`](fn.drop_multi_float_array.html)
+/// with the returned `Array`, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_multi_ffi(
+    components: ExternalArray,
+    precision: libc::c_double,
+) -> InternalArray {
+    let arrays = unsafe {
+        simplify_batch(
+            components.data as *const ExternalArray,
+            components.len,
+            |ls| ls.simplify(&precision),
+            &Progress::none(),
+        )
+    };
+    if arrays.is_null() {
+        return InternalArray::empty();
+    }
+    InternalArray {
+        data: arrays as *mut libc::c_void,
+        len: components.len,
+    }
+}
+
+/// Free memory which has been allocated across the FFI boundary by:
+/// - simplify_rdp_multi_ffi
+/// - simplify_rdp_polygon_ffi
+///
+/// Each component array is freed, followed by the outer array.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn drop_multi_float_array(arr: InternalArray) {
+    unsafe { drop_float_array_batch(arr.data as *mut InternalArray, arr.len) };
+}
+
+/// Free memory which has been allocated across the FFI boundary by:
+/// - simplify_rdp_batch_ffi
+/// - simplify_rdp_batch_progress_ffi
+/// - simplify_visvalingam_batch_ffi
+/// - simplify_rdp_shared_ffi
+///
+/// `count` **must** be the value which was passed to the simplification function.
+/// Each array in the batch is freed, followed by the batch itself.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub unsafe extern "C" fn drop_float_array_batch(arrays: *mut InternalArray, count: libc::size_t) {
+    if arrays.is_null() {
+        return;
+    }
+    let batch = InternalArray {
+        data: arrays as *mut libc::c_void,
+        len: count,
+    };
+    for arr in reclaim::<InternalArray>(batch) {
+        drop_float_array(arr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffi::{external, read_coords};
+    use crate::{drop_usize_array, simplify_rdp_ffi, simplify_visvalingam_ffi};
+
+    // Read the coordinates of an InternalArray without taking ownership of them
+    #[test]
+    fn test_ffi_rdp_batch_simplification() {
+        let inputs = [
+            vec![
+                [0.0, 0.0],
+                [5.0, 4.0],
+                [11.0, 5.5],
+                [17.3, 3.2],
+                [27.8, 0.1],
+            ],
+            vec![[0.0, 0.0], [1.0, 0.1], [2.0, 0.0]],
+            vec![
+                [5.0, 2.0],
+                [3.0, 8.0],
+                [6.0, 20.0],
+                [7.0, 25.0],
+                [10.0, 10.0],
+            ],
+        ];
+        let arrays: Vec<ExternalArray> = inputs
+            .iter()
+            .map(|input| LineString::from(input.clone()).into())
+            .collect();
+        let batch = unsafe { simplify_rdp_batch_ffi(arrays.as_ptr(), arrays.len(), 1.0) };
+        let results = unsafe { slice::from_raw_parts(batch, arrays.len()) };
+        for (input, result) in inputs.iter().zip(results) {
+            let expected: LineString<_> =
+                simplify_rdp_ffi(LineString::from(input.clone()).into(), 1.0).into();
+            assert_eq!(LineString::from(read_coords(result)), expected);
+        }
+        assert_eq!(read_coords(&results[1]), vec![[0.0, 0.0], [2.0, 0.0]],);
+        unsafe { drop_float_array_batch(batch, arrays.len()) };
+    }
+    #[test]
+    fn test_ffi_visvalingam_batch_simplification() {
+        let input = vec![
+            [5.0, 2.0],
+            [3.0, 8.0],
+            [6.0, 20.0],
+            [7.0, 25.0],
+            [10.0, 10.0],
+        ];
+        let arrays: Vec<ExternalArray> = (0..3)
+            .map(|_| LineString::from(input.clone()).into())
+            .collect();
+        let batch = unsafe { simplify_visvalingam_batch_ffi(arrays.as_ptr(), arrays.len(), 30.0) };
+        let results = unsafe { slice::from_raw_parts(batch, arrays.len()) };
+        let expected: LineString<_> =
+            simplify_visvalingam_ffi(LineString::from(input).into(), 30.0).into();
+        for result in results {
+            assert_eq!(LineString::from(read_coords(result)), expected);
+        }
+        unsafe { drop_float_array_batch(batch, arrays.len()) };
+    }
+    #[test]
+    fn test_ffi_rdp_multi_simplification() {
+        let inputs = [
+            vec![
+                [0.0, 0.0],
+                [5.0, 4.0],
+                [11.0, 5.5],
+                [17.3, 3.2],
+                [27.8, 0.1],
+            ],
+            // a degenerate component consisting of a single segment
+            vec![[3.0, 3.0], [4.0, 3.0]],
+            vec![[0.0, 0.0], [1.0, 0.1], [2.0, 0.0]],
+        ];
+        let components: Vec<ExternalArray> = inputs
+            .iter()
+            .map(|input| LineString::from(input.clone()).into())
+            .collect();
+        let multi = external(&components);
+        let result = simplify_rdp_multi_ffi(multi, 1.0);
+        assert_eq!(result.len, 3);
+        let results =
+            unsafe { slice::from_raw_parts(result.data as *const InternalArray, result.len) };
+        assert_eq!(
+            read_coords(&results[0]),
+            vec![[0.0, 0.0], [5.0, 4.0], [11.0, 5.5], [27.8, 0.1]]
+        );
+        assert_eq!(read_coords(&results[1]), vec![[3.0, 3.0], [4.0, 3.0]]);
+        assert_eq!(read_coords(&results[2]), vec![[0.0, 0.0], [2.0, 0.0]]);
+        drop_multi_float_array(result);
+    }
+    #[test]
+    fn test_ffi_rdp_idx_packed() {
+        let inputs = [
+            vec![
+                [0.0, 0.0],
+                [5.0, 4.0],
+                [11.0, 5.5],
+                [17.3, 3.2],
+                [27.8, 0.1],
+            ],
+            vec![[0.0, 0.0], [1.0, 0.1], [2.0, 0.0]],
+            vec![[3.0, 3.0], [4.0, 3.0]],
+        ];
+        let arrays: Vec<ExternalArray> = inputs
+            .iter()
+            .map(|input| LineString::from(input.clone()).into())
+            .collect();
+        let mut offsets = InternalArray::empty();
+        let packed = unsafe {
+            simplify_rdp_idx_packed_ffi(arrays.as_ptr(), arrays.len(), 1.0, &mut offsets)
+        };
+        let flat = unsafe { slice::from_raw_parts(packed.data as *const usize, packed.len) };
+        let bounds = unsafe { slice::from_raw_parts(offsets.data as *const usize, offsets.len) };
+        assert_eq!(flat, [0, 1, 2, 4, 0, 2, 0, 1]);
+        assert_eq!(bounds, [0, 4, 6, 8]);
+        for (input, bound) in inputs.iter().zip(bounds.windows(2)) {
+            assert_eq!(flat[bound[0]..bound[1]], api::simplify_rdp_idx(input, 1.0));
+        }
+        drop_usize_array(packed);
+        drop_usize_array(offsets);
+
+        let mut offsets = InternalArray::empty();
+        let packed = unsafe { simplify_rdp_idx_packed_ffi(ptr::null(), 3, 1.0, &mut offsets) };
+        assert!(packed.data.is_null());
+        assert!(offsets.data.is_null());
+    }
+    #[test]
+    fn test_ffi_rdp_shared() {
+        // two triangles sharing a wiggly edge, which they traverse in opposite directions
+        let left = [
+            [0.0, 5.0],
+            [10.0, 0.0],
+            [10.3, 3.0],
+            [9.8, 7.1],
+            [10.0, 10.0],
+            [0.0, 5.0],
+        ];
+        let right = [
+            [20.0, 5.0],
+            [10.0, 10.0],
+            [9.8, 7.1],
+            [10.3, 3.0],
+            [10.0, 0.0],
+            [20.0, 5.0],
+        ];
+        let pinned: [usize; 2] = [1, 4];
+        let arrays = [
+            LineString::from(left.to_vec()).into(),
+            LineString::from(right.to_vec()).into(),
+        ];
+        let shared = [
+            external(&pinned),
+            ExternalArray {
+                data: ptr::null(),
+                len: 0,
+            },
+        ];
+        let batch = unsafe { simplify_rdp_shared_ffi(arrays.as_ptr(), shared.as_ptr(), 2, 1.0) };
+        let results = unsafe { slice::from_raw_parts(batch, 2) };
+        assert_eq!(
+            read_coords(&results[0]),
+            vec![[0.0, 5.0], [10.0, 0.0], [10.0, 10.0], [0.0, 5.0]]
+        );
+        // without pinned vertices, the second triangle is simplified as a single section
+        assert_eq!(
+            read_coords(&results[1]),
+            api::simplify_rdp_symmetric(&right, 1.0)
+        );
+        unsafe { drop_float_array_batch(batch, 2) };
+        let batch = unsafe { simplify_rdp_shared_ffi(arrays.as_ptr(), ptr::null(), 2, 1.0) };
+        assert!(batch.is_null());
+    }
+    #[test]
+    fn test_ffi_rdp_batch_progress() {
+        extern "C" fn record(done: libc::size_t, total: libc::size_t, user: *mut libc::c_void) {
+            let reports = unsafe { &mut *(user as *mut Vec<(usize, usize)>) };
+            reports.push((done, total));
+        }
+        let route: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let mut arrays: Vec<ExternalArray> = (0..1000).map(|_| external(&route)).collect();
+        // invalid geometries count towards the progress too
+        arrays[500].data = ptr::null();
+        let mut reports: Vec<(usize, usize)> = vec![];
+        let batch = unsafe {
+            simplify_rdp_batch_progress_ffi(
+                arrays.as_ptr(),
+                arrays.len(),
+                0.001,
+                Some(record),
+                &mut reports as *mut Vec<(usize, usize)> as *mut libc::c_void,
+            )
+        };
+        assert_eq!(
+            reports,
+            vec![(256, 1000), (512, 1000), (768, 1000), (1000, 1000)]
+        );
+        let results = unsafe { slice::from_raw_parts(batch, arrays.len()) };
+        assert!(results[500].data.is_null());
+        assert_eq!(read_coords(&results[0]), api::simplify_rdp(&route, 0.001));
+        unsafe { drop_float_array_batch(batch, arrays.len()) };
+        // a null callback isn't called
+        let batch = unsafe {
+            simplify_rdp_batch_progress_ffi(arrays.as_ptr(), 3, 0.001, None, ptr::null_mut())
+        };
+        unsafe { drop_float_array_batch(batch, 3) };
+    }
+    #[test]
+    fn test_ffi_rdp_batch_null() {
+        let batch = unsafe { simplify_rdp_batch_ffi(ptr::null(), 3, 1.0) };
+        assert!(batch.is_null());
+        unsafe { drop_float_array_batch(batch, 3) };
+    }
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel_matches_sequential() {
+        let route: Vec<[f64; 2]> = include!("../src/mk_route_long.rs");
+        let inputs: Vec<BatchInput> = (0..32).map(|_| Some(route.as_slice())).collect();
+        let sequential = simplify_sequential(&inputs, |ls| ls.simplify(&0.001), &Progress::none());
+        let parallel = simplify_parallel(&inputs, |ls| ls.simplify(&0.001), &Progress::none());
+        assert_eq!(sequential, parallel);
+    }
+}
@@ -0,0 +1,94 @@
+//! Process-wide configuration, for callers which always simplify using the same parameters
+//!
+//! Values are stored atomically, so they can be read and written from multiple threads.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::{simplify_rdp_ffi, ExternalArray, InternalArray};
+
+// The bits of the default RDP tolerance. Zero bits are 0.0, which retains every point
+static DEFAULT_PRECISION: AtomicU64 = AtomicU64::new(0);
+
+/// Set the tolerance used by [`simplify_rdp_default_ffi`](fn.simplify_rdp_default_ffi.html)
+///
+/// The default is 0.0, which retains every point. NaN and infinite values are ignored.
+/// This function can safely be called from multiple threads.
+#[no_mangle]
+pub extern "C" fn rdp_set_default_precision(precision: libc::c_double) {
+    if precision.is_finite() {
+        DEFAULT_PRECISION.store(precision.to_bits(), Ordering::Relaxed);
+    }
+}
+
+/// Get the tolerance used by [`simplify_rdp_default_ffi`](fn.simplify_rdp_default_ffi.html)
+#[no_mangle]
+pub extern "C" fn rdp_get_default_precision() -> libc::c_double {
+    f64::from_bits(DEFAULT_PRECISION.load(Ordering::Relaxed))
+}
+
+/// FFI wrapper for RDP using the default tolerance, returning simplified geometry **coordinates**
+///
+/// Callers must pass a [Struct](struct.Array.html) with two fields:
+///
+/// - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+/// - `len`, the length of the array being passed. Its type must be `size_t`
+///
+/// The tolerance is set using [`rdp_set_default_precision`](fn.rdp_set_default_precision.html).
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_default_ffi(coords: ExternalArray) -> InternalArray {
+    simplify_rdp_ffi(coords, rdp_get_default_precision())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffi::external;
+    use geo::LineString;
+    use std::thread;
+
+    // The default is global, so everything touching it lives in a single test
+    #[test]
+    fn test_default_precision() {
+        let values = [0.5, 1.0, 2.0, 4.0];
+        let handles: Vec<_> = values
+            .iter()
+            .map(|&value| {
+                thread::spawn(move || {
+                    for _ in 0..1000 {
+                        rdp_set_default_precision(value);
+                        // reads are never torn, whichever thread wrote last
+                        assert!(values.contains(&rdp_get_default_precision()));
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert!(values.contains(&rdp_get_default_precision()));
+
+        rdp_set_default_precision(1.0);
+        rdp_set_default_precision(f64::NAN);
+        assert_eq!(rdp_get_default_precision(), 1.0);
+        let input = [
+            [0.0, 0.0],
+            [5.0, 4.0],
+            [11.0, 5.5],
+            [17.3, 3.2],
+            [27.8, 0.1],
+        ];
+        let coords = external(&input);
+        let simplified: LineString<f64> = simplify_rdp_default_ffi(coords).into();
+        assert_eq!(
+            simplified,
+            vec![[0.0, 0.0], [5.0, 4.0], [11.0, 5.5], [27.8, 0.1]].into()
+        );
+    }
+}
@@ -0,0 +1,861 @@
+//! An in-crate implementation of the Ramer–Douglas–Peucker algorithm
+//!
+//! geo's `Simplify` hardcodes both the coordinate type and the distance metric. This implementation
+//! only deals in point indices: callers supply the number of points and a function returning the
+//! distance of a point from the segment joining two others, so it can be used with 3D coordinates
+//! or alternative distance metrics. Its results are identical to geo's for the same metric.
+//! For the Euclidean metric, [`rdp_indices_batched`] computes several distances at once.
+//!
+//! Segments awaiting simplification are kept on an explicit stack rather than the call stack, so
+//! lines whose splits are maximally unbalanced can't overflow it. Pushing the right half of a split
+//! before the left half visits segments in the same order as recursion would.
+
+use alloc::collections::BinaryHeap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use num_traits::Float;
+
+// The number of points whose distances are computed together by `farthest_batched`. Loops over
+// arrays of this width are vectorised by the compiler, without requiring nightly `std::simd`
+const LANES: usize = 4;
+
+/// Compute the indices of the points retained by RDP
+///
+/// `distance(start, end, point)` must return the distance of the point at index `point` from the
+/// segment joining the points at indices `start` and `end`.
+/// As with geo, an `epsilon` which isn't greater than zero retains every point.
+#[cfg(feature = "std")]
+pub(crate) fn rdp_indices<F>(len: usize, epsilon: f64, distance: F) -> Vec<usize>
+where
+    F: Fn(usize, usize, usize) -> f64,
+{
+    if len == 0 {
+        return vec![];
+    }
+    if epsilon <= 0.0 || len < 3 {
+        return (0..len).collect();
+    }
+    let mut retained = vec![0];
+    compute_rdp(0, len - 1, epsilon, &distance, &mut retained);
+    retained
+}
+
+// Retain the points between start (which has already been retained) and end (inclusive)
+#[cfg(feature = "std")]
+fn compute_rdp<F>(start: usize, end: usize, epsilon: f64, distance: &F, retained: &mut Vec<usize>)
+where
+    F: Fn(usize, usize, usize) -> f64,
+{
+    let mut pending = vec![(start, end)];
+    while let Some((start, end)) = pending.pop() {
+        let (farthest_index, farthest_distance) = farthest(start, end, distance);
+        if farthest_distance > epsilon {
+            pending.push((farthest_index, end));
+            pending.push((start, farthest_index));
+        } else {
+            retained.push(end);
+        }
+    }
+}
+
+/// Compute the indices of the points retained by RDP, using [`segment_distance`]
+///
+/// The results are identical to those of [`rdp_indices`] with [`segment_distance`] as the metric,
+/// but the distances of several points from a segment are computed together, which is
+/// considerably faster for long lines.
+pub(crate) fn rdp_indices_batched(points: &[[f64; 2]], epsilon: f64) -> Vec<usize> {
+    rdp_indices_cancellable(points, epsilon, || false).unwrap_or_default()
+}
+
+/// As [`rdp_indices_batched`], calling `cancelled` before each segment is simplified
+///
+/// If `cancelled` returns `true`, simplification stops, and `None` is returned. Input which is too
+/// short to simplify is returned without calling it.
+pub(crate) fn rdp_indices_cancellable<C>(
+    points: &[[f64; 2]],
+    epsilon: f64,
+    cancelled: C,
+) -> Option<Vec<usize>>
+where
+    C: Fn() -> bool,
+{
+    let len = points.len();
+    if len == 0 {
+        return Some(vec![]);
+    }
+    if epsilon <= 0.0 || len < 3 {
+        return Some((0..len).collect());
+    }
+    let mut retained = vec![0];
+    compute_rdp_batched(points, 0, len - 1, epsilon, &cancelled, &mut retained)?;
+    Some(retained)
+}
+
+fn compute_rdp_batched<C>(
+    points: &[[f64; 2]],
+    start: usize,
+    end: usize,
+    epsilon: f64,
+    cancelled: &C,
+    retained: &mut Vec<usize>,
+) -> Option<()>
+where
+    C: Fn() -> bool,
+{
+    let mut pending = vec![(start, end)];
+    while let Some((start, end)) = pending.pop() {
+        if cancelled() {
+            return None;
+        }
+        let (farthest_index, farthest_distance) = farthest_batched(points, start, end);
+        if farthest_distance > epsilon {
+            pending.push((farthest_index, end));
+            pending.push((start, farthest_index));
+        } else {
+            retained.push(end);
+        }
+    }
+    Some(())
+}
+
+/// Compute the indices of the points retained by RDP, with a separate tolerance for each point
+///
+/// A segment is retained if each of its points is no farther from it than `tolerances` allows for
+/// that point. Otherwise, it's split at the farthest of the points which are too far from it, so
+/// equal tolerances produce the same result as [`rdp_indices`]. A point whose tolerance isn't
+/// greater than zero is always retained.
+pub(crate) fn rdp_indices_variable<F>(tolerances: &[f64], distance: F) -> Vec<usize>
+where
+    F: Fn(usize, usize, usize) -> f64,
+{
+    let len = tolerances.len();
+    if len < 3 {
+        return (0..len).collect();
+    }
+    let mut retained = vec![0];
+    let mut pending = vec![(0, len - 1)];
+    while let Some((start, end)) = pending.pop() {
+        let farthest = (start + 1..end)
+            .map(|index| (index, distance(start, end, index)))
+            .filter(|&(index, distance)| tolerances[index] <= 0.0 || distance > tolerances[index])
+            .fold(None, |farthest, (index, distance)| match farthest {
+                Some((_, farthest_distance)) if distance < farthest_distance => farthest,
+                _ => Some((index, distance)),
+            });
+        match farthest {
+            Some((farthest_index, _)) => {
+                pending.push((farthest_index, end));
+                pending.push((start, farthest_index));
+            }
+            None => retained.push(end),
+        }
+    }
+    retained
+}
+
+/// Compute the tolerance below which each point is retained by RDP
+///
+/// The point farthest from a segment doesn't depend on the tolerance, so RDP always splits in the
+/// same places: a point is retained if it and every split which led to it are farther than the
+/// tolerance from their segments. Its threshold is the smallest of these distances, and
+/// [`rdp_indices`] retains exactly the points whose threshold is greater than a positive
+/// tolerance. The endpoints are always retained, so their thresholds are infinite.
+pub(crate) fn rdp_thresholds<F>(len: usize, distance: F) -> Vec<f64>
+where
+    F: Fn(usize, usize, usize) -> f64,
+{
+    let mut thresholds = vec![f64::INFINITY; len];
+    if len >= 3 {
+        compute_thresholds(0, len - 1, f64::INFINITY, &distance, &mut thresholds);
+    }
+    thresholds
+}
+
+// Record the thresholds of the points between start and end, given the threshold of the split
+// which produced the segment
+fn compute_thresholds<F>(start: usize, end: usize, limit: f64, distance: &F, thresholds: &mut [f64])
+where
+    F: Fn(usize, usize, usize) -> f64,
+{
+    let mut pending = vec![(start, end, limit)];
+    while let Some((start, end, limit)) = pending.pop() {
+        let (farthest_index, farthest_distance) = farthest(start, end, distance);
+        if farthest_index == start {
+            continue;
+        }
+        let threshold = farthest_distance.min(limit);
+        thresholds[farthest_index] = threshold;
+        pending.push((farthest_index, end, threshold));
+        pending.push((start, farthest_index, threshold));
+    }
+}
+
+/// Compute the indices of the points retained by RDP, such that reversing the input reverses the output
+///
+/// Recursion order can otherwise make the results direction-dependent, which cracks edges shared
+/// between adjacent polygons. Two sources of asymmetry are removed: distances are always measured
+/// from segments oriented from their lexicographically smaller endpoint, so floating-point results
+/// don't depend on direction, and ties between equally distant points are resolved in favour of
+/// the point closest to the middle of the range, then the lexicographically smallest point.
+pub(crate) fn rdp_indices_symmetric(points: &[[f64; 2]], epsilon: f64) -> Vec<usize> {
+    let len = points.len();
+    if len == 0 {
+        return vec![];
+    }
+    if epsilon <= 0.0 || len < 3 {
+        return (0..len).collect();
+    }
+    let mut retained = vec![0];
+    compute_rdp_symmetric(points, 0, len - 1, epsilon, &mut retained);
+    retained
+}
+
+/// Compute the indices of the points retained by RDP, always retaining the `pinned` indices
+///
+/// The line is split at the pinned points, and each section is simplified independently using
+/// [`rdp_indices_symmetric`]. A section's result therefore only depends on its own points, so a
+/// section shared by two lines, and pinned at both ends in each, simplifies identically in both,
+/// whichever direction they traverse it in. Pinned indices which are out of bounds are ignored.
+pub(crate) fn rdp_indices_pinned(
+    points: &[[f64; 2]],
+    epsilon: f64,
+    pinned: &[usize],
+) -> Vec<usize> {
+    let len = points.len();
+    if len < 3 {
+        return (0..len).collect();
+    }
+    let mut breaks: Vec<usize> = pinned.iter().copied().filter(|&idx| idx < len).collect();
+    breaks.extend([0, len - 1]);
+    breaks.sort_unstable();
+    breaks.dedup();
+    let mut retained = vec![0];
+    for section in breaks.windows(2) {
+        let start = section[0];
+        retained.extend(
+            rdp_indices_symmetric(&points[start..=section[1]], epsilon)
+                .into_iter()
+                .skip(1)
+                .map(|idx| start + idx),
+        );
+    }
+    retained
+}
+
+// Lexicographic ordering of coordinates, which doesn't depend on the direction of the line
+fn lexicographic(a: [f64; 2], b: [f64; 2]) -> Ordering {
+    a[0].total_cmp(&b[0]).then(a[1].total_cmp(&b[1]))
+}
+
+fn compute_rdp_symmetric(
+    points: &[[f64; 2]],
+    start: usize,
+    end: usize,
+    epsilon: f64,
+    retained: &mut Vec<usize>,
+) {
+    let mut pending = vec![(start, end)];
+    while let Some((start, end)) = pending.pop() {
+        let (from, to) = match lexicographic(points[start], points[end]) {
+            Ordering::Greater => (points[end], points[start]),
+            _ => (points[start], points[end]),
+        };
+        // twice the index offset from the middle of the range, which is symmetric under reversal
+        let offset = |index: usize| (2 * index).abs_diff(start + end);
+        let farthest = (start + 1..end)
+            .map(|index| (index, segment_distance(points[index], from, to)))
+            .max_by(|(a, a_distance), (b, b_distance)| {
+                a_distance
+                    .total_cmp(b_distance)
+                    .then(offset(*b).cmp(&offset(*a)))
+                    .then(lexicographic(points[*b], points[*a]))
+            });
+        match farthest {
+            Some((farthest_index, farthest_distance)) if farthest_distance > epsilon => {
+                pending.push((farthest_index, end));
+                pending.push((start, farthest_index));
+            }
+            _ => retained.push(end),
+        }
+    }
+}
+
+// A segment awaiting a split at its farthest point, ordered by that point's distance
+struct Segment {
+    start: usize,
+    end: usize,
+    farthest_index: usize,
+    farthest_distance: f64,
+}
+
+impl Segment {
+    // Returns None if the segment has no interior points
+    fn new<F>(start: usize, end: usize, distance: &F) -> Option<Self>
+    where
+        F: Fn(usize, usize, usize) -> f64,
+    {
+        let (farthest_index, farthest_distance) = farthest(start, end, distance);
+        (farthest_index != start).then_some(Segment {
+            start,
+            end,
+            farthest_index,
+            farthest_distance,
+        })
+    }
+}
+
+// Segments with more distant points are split first. Ties are resolved in favour of the earlier segment
+impl Ord for Segment {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.farthest_distance
+            .total_cmp(&other.farthest_distance)
+            .then_with(|| other.start.cmp(&self.start))
+    }
+}
+
+impl PartialOrd for Segment {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Segment {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Segment {}
+
+/// Compute the indices of the points retained by RDP, retaining at least `min_points` points
+///
+/// Segments are split at their farthest point in order of decreasing distance, rather than
+/// recursively. Splitting stops once no segment has a point farther than `epsilon` from it, as
+/// long as `min_points` have been retained, so the result is the same as [`rdp_indices`] unless
+/// the floor is reached first, in which case the most distant of the removed points are retained.
+pub(crate) fn rdp_indices_floor<F>(
+    len: usize,
+    epsilon: f64,
+    min_points: usize,
+    distance: F,
+) -> Vec<usize>
+where
+    F: Fn(usize, usize, usize) -> f64,
+{
+    if epsilon <= 0.0 || len < 3 || min_points >= len {
+        return (0..len).collect();
+    }
+    let mut retained = vec![0, len - 1];
+    let mut segments: BinaryHeap<Segment> =
+        Segment::new(0, len - 1, &distance).into_iter().collect();
+    while let Some(segment) = segments.pop() {
+        if segment.farthest_distance <= epsilon && retained.len() >= min_points {
+            break;
+        }
+        retained.push(segment.farthest_index);
+        segments.extend(Segment::new(
+            segment.start,
+            segment.farthest_index,
+            &distance,
+        ));
+        segments.extend(Segment::new(segment.farthest_index, segment.end, &distance));
+    }
+    retained.sort_unstable();
+    retained
+}
+
+/// Compute the indices of the points retained by RDP, evaluating `distance` at most `max_work` times
+///
+/// Segments are split at their farthest point in order of decreasing distance, as in
+/// [`rdp_indices_floor`]. A segment whose farthest point would take the number of evaluations past
+/// `max_work` isn't split, so the most significant points are retained first. The second value is
+/// `true` if any segment was left unsplit in this way, in which case the result is coarser than
+/// that of [`rdp_indices`], but its points are still a subset of the input, in order. Otherwise, it
+/// is identical.
+pub(crate) fn rdp_indices_bounded<F>(
+    len: usize,
+    epsilon: f64,
+    max_work: usize,
+    distance: F,
+) -> (Vec<usize>, bool)
+where
+    F: Fn(usize, usize, usize) -> f64,
+{
+    if epsilon <= 0.0 || len < 3 {
+        return ((0..len).collect(), false);
+    }
+    let mut work = 0;
+    let mut truncated = false;
+    let mut segment = |start: usize, end: usize| {
+        let cost = end - start - 1;
+        if work + cost > max_work {
+            truncated |= cost > 0;
+            return None;
+        }
+        work += cost;
+        Segment::new(start, end, &distance)
+    };
+    let mut retained = vec![0, len - 1];
+    let mut segments: BinaryHeap<Segment> = segment(0, len - 1).into_iter().collect();
+    while let Some(split) = segments.pop() {
+        if split.farthest_distance <= epsilon {
+            break;
+        }
+        retained.push(split.farthest_index);
+        segments.extend(segment(split.start, split.farthest_index));
+        segments.extend(segment(split.farthest_index, split.end));
+    }
+    retained.sort_unstable();
+    (retained, truncated)
+}
+
+/// Check whether RDP, using [`segment_distance`], would retain every point
+///
+/// Segments are visited as in [`rdp_indices_batched`], but no indices are collected, and the check
+/// stops at the first segment which would be retained with points between its endpoints, as they
+/// would be removed. A line which is already simplified is fully traversed, so this is no cheaper
+/// than simplifying it, but redundant points are usually found well before that.
+pub(crate) fn rdp_retains_all(points: &[[f64; 2]], epsilon: f64) -> bool {
+    let len = points.len();
+    if epsilon <= 0.0 || len < 3 {
+        return true;
+    }
+    let mut pending = vec![(0, len - 1)];
+    while let Some((start, end)) = pending.pop() {
+        if end - start < 2 {
+            continue;
+        }
+        let (farthest_index, farthest_distance) = farthest_batched(points, start, end);
+        if farthest_distance <= epsilon {
+            return false;
+        }
+        pending.push((farthest_index, end));
+        pending.push((start, farthest_index));
+    }
+    true
+}
+
+// Find the farthest point from the segment joining start and end, and its distance. Ties are
+// resolved in favour of the later point, matching geo's behaviour. If there are no points between
+// start and end, start is returned
+fn farthest<F>(start: usize, end: usize, distance: &F) -> (usize, f64)
+where
+    F: Fn(usize, usize, usize) -> f64,
+{
+    (start + 1..end)
+        .map(|index| (index, distance(start, end, index)))
+        .fold(
+            (start, 0.0),
+            |(farthest_index, farthest_distance), (index, distance)| {
+                if distance >= farthest_distance {
+                    (index, distance)
+                } else {
+                    (farthest_index, farthest_distance)
+                }
+            },
+        )
+}
+
+// As farthest, using segment_distance, but computing the distances of LANES points at a time.
+// Most points project onto the segment, so their distance is the same expression of the segment's
+// length, and can be computed without branching. The few which don't are measured to the nearest
+// endpoint using segment_distance, so every distance is bit-identical to the scalar computation
+fn farthest_batched(points: &[[f64; 2]], start: usize, end: usize) -> (usize, f64) {
+    let (a, b) = (points[start], points[end]);
+    if a == b {
+        return farthest(start, end, &|start, end, point| {
+            segment_distance(points[point], points[start], points[end])
+        });
+    }
+    let dx = b[0] - a[0];
+    let dy = b[1] - a[1];
+    let d_squared = dx * dx + dy * dy;
+    let length = Float::hypot(dx, dy);
+    let mut best = (start, 0.0);
+    let mut consider = |index: usize, distance: f64| {
+        if distance >= best.1 {
+            best = (index, distance);
+        }
+    };
+    let interior = &points[start + 1..end];
+    let mut chunks = interior.chunks_exact(LANES);
+    let mut index = start + 1;
+    for chunk in &mut chunks {
+        let mut r = [0.0; LANES];
+        let mut distances = [0.0; LANES];
+        for lane in 0..LANES {
+            let p = chunk[lane];
+            r[lane] = ((p[0] - a[0]) * dx + (p[1] - a[1]) * dy) / d_squared;
+            let s = ((a[1] - p[1]) * dx - (a[0] - p[0]) * dy) / d_squared;
+            distances[lane] = Float::abs(s) * length;
+        }
+        for lane in 0..LANES {
+            if r[lane] <= 0.0 || r[lane] >= 1.0 {
+                consider(index, segment_distance(chunk[lane], a, b));
+            } else {
+                consider(index, distances[lane]);
+            }
+            index += 1;
+        }
+    }
+    for &p in chunks.remainder() {
+        consider(index, segment_distance(p, a, b));
+        index += 1;
+    }
+    best
+}
+
+/// Minimum Euclidean distance from `point` to the segment joining `start` and `end`
+///
+/// This is the same computation geo uses for its RDP implementation
+pub(crate) fn segment_distance(point: [f64; 2], start: [f64; 2], end: [f64; 2]) -> f64 {
+    if start == end {
+        return Float::hypot(start[0] - point[0], start[1] - point[1]);
+    }
+    let dx = end[0] - start[0];
+    let dy = end[1] - start[1];
+    let d_squared = dx * dx + dy * dy;
+    let r = ((point[0] - start[0]) * dx + (point[1] - start[1]) * dy) / d_squared;
+    if r <= 0.0 {
+        return Float::hypot(start[0] - point[0], start[1] - point[1]);
+    }
+    if r >= 1.0 {
+        return Float::hypot(end[0] - point[0], end[1] - point[1]);
+    }
+    let s = ((start[1] - point[1]) * dx - (start[0] - point[0]) * dy) / d_squared;
+    Float::abs(s) * Float::hypot(dx, dy)
+}
+
+/// Vertical distance from `point` to the line through `start` and `end`, at `point`'s x coordinate
+///
+/// This is the metric for time series, whose x coordinates are strictly increasing, so `end`'s x
+/// coordinate must be greater than `start`'s
+#[cfg(feature = "std")]
+pub(crate) fn vertical_distance(point: [f64; 2], start: [f64; 2], end: [f64; 2]) -> f64 {
+    let t = (point[0] - start[0]) / (end[0] - start[0]);
+    Float::abs(point[1] - (start[1] + t * (end[1] - start[1])))
+}
+
+/// Minimum Euclidean distance from `point` to the segment joining `start` and `end`, in three dimensions
+#[cfg(feature = "std")]
+pub(crate) fn segment_distance_3d(point: [f64; 3], start: [f64; 3], end: [f64; 3]) -> f64 {
+    let length = |a: [f64; 3], b: [f64; 3]| {
+        let (dx, dy, dz) = (b[0] - a[0], b[1] - a[1], b[2] - a[2]);
+        Float::sqrt(dx * dx + dy * dy + dz * dz)
+    };
+    if start == end {
+        return length(point, start);
+    }
+    let d = [end[0] - start[0], end[1] - start[1], end[2] - start[2]];
+    let d_squared = d[0] * d[0] + d[1] * d[1] + d[2] * d[2];
+    let r = ((point[0] - start[0]) * d[0]
+        + (point[1] - start[1]) * d[1]
+        + (point[2] - start[2]) * d[2])
+        / d_squared;
+    if r <= 0.0 {
+        return length(point, start);
+    }
+    if r >= 1.0 {
+        return length(point, end);
+    }
+    let projected = [
+        start[0] + r * d[0],
+        start[1] + r * d[1],
+        start[2] + r * d[2],
+    ];
+    length(point, projected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::simplify::SimplifyIdx;
+    use geo::LineString;
+
+    #[test]
+    fn test_rdp_indices_matches_geo() {
+        let points: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let ls: LineString<f64> = points.clone().into();
+        for epsilon in [0.0, 0.0001, 0.001, 0.01, 0.1] {
+            let indices = rdp_indices(points.len(), epsilon, |start, end, point| {
+                segment_distance(points[point], points[start], points[end])
+            });
+            assert_eq!(indices, ls.simplify_idx(&epsilon));
+        }
+    }
+    #[test]
+    fn test_rdp_indices_batched() {
+        let points: Vec<[f64; 2]> = include!("../src/mk_route_long.rs");
+        let distance =
+            |start, end, point| segment_distance(points[point], points[start], points[end]);
+        for epsilon in [0.0, 0.00001, 0.0001, 0.001, 0.01, 0.1, 1.0] {
+            assert_eq!(
+                rdp_indices_batched(&points, epsilon),
+                rdp_indices(points.len(), epsilon, distance),
+                "{}",
+                epsilon
+            );
+        }
+        // a closed line, points beyond the ends of segments, and equally distant points
+        let points = [
+            [0.0, 0.0],
+            [-1.0, 0.5],
+            [2.0, 1.0],
+            [4.0, 1.0],
+            [6.0, 1.0],
+            [8.0, -1.0],
+            [11.0, 0.5],
+            [10.0, 0.0],
+            [5.0, 3.0],
+            [0.0, 0.0],
+        ];
+        let distance =
+            |start, end, point| segment_distance(points[point], points[start], points[end]);
+        for epsilon in [0.1, 0.5, 1.0, 2.0, 5.0] {
+            assert_eq!(
+                rdp_indices_batched(&points, epsilon),
+                rdp_indices(points.len(), epsilon, distance),
+                "{}",
+                epsilon
+            );
+            assert_eq!(
+                rdp_indices_batched(&points[..9], epsilon),
+                rdp_indices(9, epsilon, distance),
+                "{}",
+                epsilon
+            );
+        }
+        assert_eq!(rdp_indices_batched(&points[..2], 1.0), vec![0, 1]);
+        assert!(rdp_indices_batched(&[], 1.0).is_empty());
+    }
+    #[test]
+    fn test_rdp_indices_cancellable() {
+        use core::cell::Cell;
+
+        let points: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let checks = Cell::new(0);
+        let count = || {
+            checks.set(checks.get() + 1);
+            false
+        };
+        assert_eq!(
+            rdp_indices_cancellable(&points, 0.001, count),
+            Some(rdp_indices_batched(&points, 0.001))
+        );
+        let total = checks.get();
+        assert!(total > 1);
+        // cancelling part of the way through abandons the simplification
+        checks.set(0);
+        let cancel = || {
+            checks.set(checks.get() + 1);
+            checks.get() > total / 2
+        };
+        assert_eq!(rdp_indices_cancellable(&points, 0.001, cancel), None);
+        assert_eq!(checks.get(), total / 2 + 1);
+        assert_eq!(rdp_indices_cancellable(&points, 0.001, || true), None);
+        assert_eq!(
+            rdp_indices_cancellable(&points[..2], 0.001, || true),
+            Some(vec![0, 1])
+        );
+    }
+    // Each split of this zig-zag peels off a single point, so recursion would be as deep as the line
+    // is long
+    fn zigzag(len: usize) -> Vec<[f64; 2]> {
+        (0..len)
+            .map(|i| {
+                let amplitude = (len - i) as f64;
+                [i as f64, if i % 2 == 0 { amplitude } else { -amplitude }]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rdp_indices_deep() {
+        let points = zigzag(400);
+        let ls: LineString<f64> = points.clone().into();
+        let expected = ls.simplify_idx(&0.5);
+        assert_eq!(expected, (0..400).collect::<Vec<_>>());
+        assert_eq!(rdp_indices_batched(&points, 0.5), expected);
+        let distance = |start: usize, end: usize, point: usize| {
+            segment_distance(points[point], points[start], points[end])
+        };
+        assert_eq!(rdp_indices(points.len(), 0.5, distance), expected);
+
+        // far deeper than a small stack could hold, were each split a stack frame
+        let handle = std::thread::Builder::new()
+            .stack_size(64 * 1024)
+            .spawn(|| {
+                let points = zigzag(5000);
+                let all: Vec<usize> = (0..points.len()).collect();
+                let distance = |start: usize, end: usize, point: usize| {
+                    segment_distance(points[point], points[start], points[end])
+                };
+                assert_eq!(rdp_indices_batched(&points, 0.5), all);
+                assert_eq!(rdp_indices(points.len(), 0.5, distance), all);
+                assert_eq!(rdp_indices_symmetric(&points, 0.5), all);
+                assert!(rdp_thresholds(points.len(), distance)
+                    .iter()
+                    .all(|&threshold| threshold > 0.5));
+            })
+            .unwrap();
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_rdp_indices_short_input() {
+        let distance = |_, _, _| 1.0;
+        assert_eq!(rdp_indices(0, 0.5, distance), Vec::<usize>::new());
+        assert_eq!(rdp_indices(1, 0.5, distance), vec![0]);
+        assert_eq!(rdp_indices(2, 0.5, distance), vec![0, 1]);
+    }
+    #[test]
+    fn test_rdp_indices_variable() {
+        let points: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let distance =
+            |start, end, point| segment_distance(points[point], points[start], points[end]);
+        // equal tolerances produce the same results
+        for epsilon in [0.0, 0.0001, 0.001, 0.01] {
+            assert_eq!(
+                rdp_indices_variable(&vec![epsilon; points.len()], distance),
+                rdp_indices(points.len(), epsilon, distance)
+            );
+        }
+        assert_eq!(rdp_indices_variable(&[1.0, 1.0], distance), vec![0, 1]);
+    }
+    #[test]
+    fn test_rdp_thresholds() {
+        let points: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let distance =
+            |start, end, point| segment_distance(points[point], points[start], points[end]);
+        let thresholds = rdp_thresholds(points.len(), distance);
+        for epsilon in [0.0001, 0.001, 0.01, 0.1] {
+            let above: Vec<usize> = (0..points.len())
+                .filter(|&idx| thresholds[idx] > epsilon)
+                .collect();
+            assert_eq!(above, rdp_indices(points.len(), epsilon, distance));
+        }
+        assert_eq!(rdp_thresholds(2, distance), vec![f64::INFINITY; 2]);
+    }
+    #[test]
+    fn test_rdp_indices_floor() {
+        let points: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let distance =
+            |start, end, point| segment_distance(points[point], points[start], points[end]);
+        // without a floor, the results are identical
+        for epsilon in [0.0001, 0.001, 0.01] {
+            assert_eq!(
+                rdp_indices_floor(points.len(), epsilon, 2, distance),
+                rdp_indices(points.len(), epsilon, distance)
+            );
+        }
+        let indices = rdp_indices_floor(points.len(), 1.0, 10, distance);
+        assert_eq!(indices.len(), 10);
+        assert_eq!(rdp_indices_floor(5, 1.0, 5, distance), vec![0, 1, 2, 3, 4]);
+    }
+    #[test]
+    fn test_rdp_indices_bounded() {
+        let points: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let distance =
+            |start, end, point| segment_distance(points[point], points[start], points[end]);
+        // with enough work, the results are identical
+        let (indices, truncated) = rdp_indices_bounded(points.len(), 0.001, usize::MAX, distance);
+        assert_eq!(indices, rdp_indices(points.len(), 0.001, distance));
+        assert!(!truncated);
+        // the work is counted in distance evaluations
+        let evaluations = core::cell::Cell::new(0);
+        let counted = |start, end, point| {
+            evaluations.set(evaluations.get() + 1);
+            distance(start, end, point)
+        };
+        let (coarse, truncated) = rdp_indices_bounded(points.len(), 0.001, 5000, counted);
+        assert!(truncated);
+        assert!(evaluations.get() <= 5000);
+        assert!(coarse.len() < indices.len());
+        assert!(coarse.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(coarse.iter().all(|idx| indices.contains(idx)));
+        // too little work to split the line at all retains its endpoints
+        let (endpoints, truncated) = rdp_indices_bounded(points.len(), 0.001, 10, distance);
+        assert_eq!(endpoints, vec![0, points.len() - 1]);
+        assert!(truncated);
+    }
+    #[test]
+    fn test_rdp_retains_all() {
+        let points: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        for epsilon in [0.0, 0.0001, 0.001, 0.01] {
+            let simplified: Vec<[f64; 2]> = rdp_indices_batched(&points, epsilon)
+                .into_iter()
+                .map(|idx| points[idx])
+                .collect();
+            for line in [&points, &simplified] {
+                assert_eq!(
+                    rdp_retains_all(line, epsilon),
+                    rdp_indices_batched(line, epsilon).len() == line.len(),
+                    "{}",
+                    epsilon
+                );
+            }
+        }
+        assert!(rdp_retains_all(&points[..2], 1.0));
+    }
+    #[test]
+    fn test_rdp_indices_symmetric() {
+        let points: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let reversed: Vec<[f64; 2]> = points.iter().rev().copied().collect();
+        let last = points.len() - 1;
+        for epsilon in [0.0, 0.0001, 0.001, 0.01, 0.1] {
+            let forward = rdp_indices_symmetric(&points, epsilon);
+            let mut backward: Vec<usize> = rdp_indices_symmetric(&reversed, epsilon)
+                .into_iter()
+                .map(|idx| last - idx)
+                .collect();
+            backward.reverse();
+            assert_eq!(forward, backward, "{}", epsilon);
+        }
+        // equally distant points either side of the middle can't be distinguished by distance
+        let points = [[0.0, 0.0], [1.0, 1.0], [2.0, 0.0], [3.0, 1.0], [4.0, 0.0]];
+        let reversed: Vec<[f64; 2]> = points.iter().rev().copied().collect();
+        let forward = rdp_indices_symmetric(&points, 0.5);
+        let backward = rdp_indices_symmetric(&reversed, 0.5);
+        assert_eq!(forward, vec![0, 1, 2, 3, 4]);
+        assert_eq!(backward, vec![0, 1, 2, 3, 4]);
+        let points = [[0.0, 0.0], [1.0, 1.0], [2.0, 0.5], [3.0, 1.0], [4.0, 0.0]];
+        let reversed: Vec<[f64; 2]> = points.iter().rev().copied().collect();
+        assert_eq!(rdp_indices_symmetric(&points, 0.6), vec![0, 1, 3, 4]);
+        assert_eq!(rdp_indices_symmetric(&reversed, 0.6), vec![0, 1, 3, 4]);
+    }
+    #[test]
+    fn test_rdp_indices_pinned() {
+        let points = [[0.0, 0.0], [1.0, 0.1], [2.0, 0.0], [3.0, 0.1], [4.0, 0.0]];
+        assert_eq!(rdp_indices_pinned(&points, 1.0, &[]), vec![0, 4]);
+        assert_eq!(
+            rdp_indices_pinned(&points, 1.0, &[1, 3, 3]),
+            vec![0, 1, 3, 4]
+        );
+        assert_eq!(rdp_indices_pinned(&points, 1.0, &[4, 10]), vec![0, 4]);
+        assert_eq!(rdp_indices_pinned(&points[..2], 1.0, &[]), vec![0, 1]);
+    }
+    #[test]
+    fn test_vertical_distance() {
+        assert_eq!(vertical_distance([1.0, 3.0], [0.0, 0.0], [2.0, 2.0]), 2.0);
+        assert_eq!(vertical_distance([1.0, 1.0], [0.0, 0.0], [2.0, 2.0]), 0.0);
+        // unlike segment_distance, this doesn't depend on the slope
+        let (p, a, b) = ([1.0, 11.0], [0.0, 0.0], [2.0, 20.0]);
+        assert_eq!(vertical_distance(p, a, b), 1.0);
+        assert!(segment_distance(p, a, b) < 0.1);
+    }
+    #[test]
+    fn test_segment_distance_3d() {
+        // equivalent to the 2D computation when z is constant
+        let (p, a, b) = ([4.5, 1.5, 3.0], [7.2, 2.0, 3.0], [6.0, 1.0, 3.0]);
+        assert_eq!(
+            segment_distance_3d(p, a, b),
+            segment_distance([4.5, 1.5], [7.2, 2.0], [6.0, 1.0])
+        );
+        assert_eq!(
+            segment_distance_3d([1.0, 0.0, 4.0], [0.0, 0.0, 0.0], [2.0, 0.0, 0.0]),
+            4.0
+        );
+    }
+}
@@ -0,0 +1,5340 @@
+//! The FFI functions, and the types used to pass arrays across the FFI boundary
+//!
+//! These require `std`, and are re-exported at the crate root.
+
+#[cfg(debug_assertions)]
+use std::any::type_name;
+use std::cell::RefCell;
+#[cfg(debug_assertions)]
+use std::collections::BTreeMap;
+use std::ffi::{CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::OnceLock;
+#[cfg(debug_assertions)]
+use std::sync::{Mutex, PoisonError};
+use std::{f64, ptr};
+use std::{mem, slice};
+
+use geo::simplify::Simplify;
+use geo::simplify_vw::{SimplifyVw, SimplifyVwPreserve};
+use geo::{ConvexHull, CoordFloat, EuclideanLength, LineString};
+use num_traits::ToPrimitive;
+
+use crate::api::{RdpSimplifier, Simplifier, VwPreserveSimplifier, VwSimplifier};
+use crate::{api, douglas_peucker};
+
+/// A C-compatible `struct` originating **outside** Rust
+/// used for passing arrays across the FFI boundary
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct ExternalArray {
+    pub data: *const libc::c_void,
+    pub len: libc::size_t,
+}
+
+/// A C-compatible `struct` originating **inside** Rust
+/// used for passing arrays across the FFI boundary
+///
+/// Each array must be freed exactly once, using the drop function documented by the function which
+/// returned it. In debug builds, freeing an array twice, or using a drop function for a different
+/// element type, panics instead of corrupting the heap.
+#[repr(C)]
+pub struct InternalArray {
+    pub data: *mut libc::c_void,
+    pub len: libc::size_t,
+}
+
+/// Which side of the FFI boundary an array described by an [`ArrayDescriptor`] originated on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ArrayKind {
+    /// An [`ExternalArray`], allocated outside Rust
+    External,
+    /// An [`InternalArray`], allocated by this library
+    Internal,
+}
+
+/// A description of an array passed across the FFI boundary, which omits its pointer
+///
+/// Descriptors are safe to log: with the `serde` feature enabled, they can be serialised, e.g. into
+/// structured logs, without leaking addresses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArrayDescriptor {
+    pub kind: ArrayKind,
+    pub len: usize,
+}
+
+/// Error codes returned as part of an [`FfiResult`](struct.FfiResult.html)
+///
+/// Every function records a more detailed, human-readable description of a failure, which can be
+/// retrieved using [`rdp_last_error_message`](fn.rdp_last_error_message.html).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FfiError {
+    /// The input was valid, and the accompanying array contains the result
+    Success = 0,
+    /// The `data` field of the input array was a null pointer
+    NullPointer = 1,
+    /// The `len` field of the input array was 0
+    ZeroLength = 2,
+    /// The input array contained at least one NaN or infinite coordinate
+    NonFiniteCoordinate = 3,
+    /// The caller-provided output array was too small to hold the result
+    BufferTooSmall = 4,
+    /// Simplification panicked, so no result could be produced
+    Panic = 5,
+    /// A retained index was too large to be represented in the output's index type
+    IndexOverflow = 6,
+}
+
+/// Simplification algorithms which can be selected when calling [`simplify_ffi`](fn.simplify_ffi.html)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    /// Ramer–Douglas–Peucker
+    Rdp = 0,
+    /// Visvalingam-Whyatt
+    Visvalingam = 1,
+    /// Topology-preserving Visvalingam-Whyatt
+    VisvalingamPreserve = 2,
+}
+
+impl TryFrom<libc::c_int> for Algorithm {
+    type Error = libc::c_int;
+
+    fn try_from(value: libc::c_int) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Algorithm::Rdp),
+            1 => Ok(Algorithm::Visvalingam),
+            2 => Ok(Algorithm::VisvalingamPreserve),
+            _ => Err(value),
+        }
+    }
+}
+
+/// The element types of the arrays returned by this library, for use with
+/// [`internal_array_byte_size`](fn.internal_array_byte_size.html)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElementKind {
+    /// Pairs of `double`s, as freed by [`drop_float_array`](fn.drop_float_array.html)
+    F64Pair = 0,
+    /// `size_t`s, as freed by [`drop_usize_array`](fn.drop_usize_array.html)
+    Usize = 1,
+    /// `uint32_t`s, as freed by [`drop_u32_array`](fn.drop_u32_array.html)
+    U32 = 2,
+    /// Bytes, as freed by [`drop_u8_array`](fn.drop_u8_array.html), or by `drop_byte_array` with the
+    /// `wkb` feature
+    U8 = 3,
+}
+
+impl TryFrom<libc::c_int> for ElementKind {
+    type Error = libc::c_int;
+
+    fn try_from(value: libc::c_int) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(ElementKind::F64Pair),
+            1 => Ok(ElementKind::Usize),
+            2 => Ok(ElementKind::U32),
+            3 => Ok(ElementKind::U8),
+            _ => Err(value),
+        }
+    }
+}
+
+impl ElementKind {
+    // The size of a single element, in bytes
+    fn size(self) -> usize {
+        match self {
+            ElementKind::F64Pair => mem::size_of::<[f64; 2]>(),
+            ElementKind::Usize => mem::size_of::<usize>(),
+            ElementKind::U32 => mem::size_of::<u32>(),
+            ElementKind::U8 => mem::size_of::<u8>(),
+        }
+    }
+}
+
+impl Algorithm {
+    // The algorithm, with the given tolerance
+    pub(crate) fn simplifier(self, tolerance: f64) -> Box<dyn Simplifier> {
+        match self {
+            Algorithm::Rdp => Box::new(RdpSimplifier { tolerance }),
+            Algorithm::Visvalingam => Box::new(VwSimplifier { epsilon: tolerance }),
+            Algorithm::VisvalingamPreserve => Box::new(VwPreserveSimplifier { epsilon: tolerance }),
+        }
+    }
+}
+
+/// A C-compatible `struct` returned by the `_checked` FFI functions
+///
+/// If `error` is anything other than `Success`, `array` is empty: its `data` field is a null pointer
+/// and its `len` field is 0. It is nevertheless safe to pass it to the relevant drop function.
+#[repr(C)]
+pub struct FfiResult {
+    pub array: InternalArray,
+    pub error: FfiError,
+}
+
+impl InternalArray {
+    // An array which owns no memory, returned when the input can't be simplified
+    pub(crate) fn empty() -> Self {
+        InternalArray {
+            data: ptr::null_mut(),
+            len: 0,
+        }
+    }
+
+    /// Describe the array, without its pointer
+    pub fn descriptor(&self) -> ArrayDescriptor {
+        ArrayDescriptor {
+            kind: ArrayKind::Internal,
+            len: self.len,
+        }
+    }
+}
+
+impl ExternalArray {
+    /// Describe the array, without its pointer
+    pub fn descriptor(&self) -> ArrayDescriptor {
+        ArrayDescriptor {
+            kind: ArrayKind::External,
+            len: self.len,
+        }
+    }
+
+    // Check that the array can be safely read as a slice of coordinates, which may not be finite
+    pub(crate) fn readable<T, const N: usize>(&self) -> Result<&[[T; N]], FfiError> {
+        if self.data.is_null() {
+            set_last_error("the input array's data field is a null pointer");
+            return Err(FfiError::NullPointer);
+        }
+        if self.len == 0 {
+            set_last_error("the input array's len field is 0");
+            return Err(FfiError::ZeroLength);
+        }
+        Ok(unsafe { slice::from_raw_parts(self.data as *const [T; N], self.len) })
+    }
+
+    // Check that the array can be safely read as a slice of finite coordinates. Integer
+    // coordinates are always finite
+    pub(crate) fn validate<T, const N: usize>(&self) -> Result<&[[T; N]], FfiError>
+    where
+        T: ToPrimitive,
+    {
+        let coords = self.readable::<T, N>()?;
+        if let Some(idx) = coords.iter().position(|point| {
+            point
+                .iter()
+                .any(|c| !c.to_f64().is_some_and(f64::is_finite))
+        }) {
+            set_last_error(format!(
+                "point {idx} of the input array has a NaN or infinite coordinate"
+            ));
+            return Err(FfiError::NonFiniteCoordinate);
+        }
+        Ok(coords)
+    }
+}
+
+thread_local! {
+    // A description of the most recent failure on this thread
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+// Record a description of a failure, for retrieval by `rdp_last_error_message`
+pub(crate) fn set_last_error(message: impl Into<String>) {
+    let message = CString::new(message.into().replace('\0', "")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+/// Get a description of the most recent failure on the calling thread
+///
+/// When a function returns an empty or null result, or an error code, because its input was
+/// invalid or simplification panicked, it records a human-readable description of the reason,
+/// such as a null pointer, a NaN coordinate (and its position), or a WKT or GeoJSON parsing error.
+/// This function returns it as a nul-terminated, UTF-8 string, or a null pointer if no failure has
+/// been recorded on the calling thread.
+///
+/// Descriptions are recorded per thread, so failures on other threads don't affect the result.
+/// Successful calls don't clear the description, so it should only be retrieved after a failure.
+///
+/// The string is owned by this library, and **must not** be freed. It remains valid until the next
+/// failure on the same thread, so callers wishing to keep it should copy it.
+#[no_mangle]
+pub extern "C" fn rdp_last_error_message() -> *const libc::c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |m| m.as_ptr()))
+}
+
+// The crate's version, and the version of geo whose algorithms it uses, which must match the
+// requirement in Cargo.toml
+const VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), " (geo 0.28)\0");
+
+/// Get the version of this library, and of the [geo](https://crates.io/crates/geo) crate it was
+/// compiled against, such as `0.12.11 (geo 0.28)`
+///
+/// The string is nul-terminated, static, and owned by this library, so it **must not** be freed.
+#[no_mangle]
+pub extern "C" fn rdp_version() -> *const libc::c_char {
+    VERSION.as_ptr() as *const libc::c_char
+}
+
+/// Report whether an optional feature was compiled into this library
+///
+/// Callers must pass a pointer to a nul-terminated string naming a feature: `rayon`, `geojson`,
+/// `wkb`, `serde`, or `f32`, for the single-precision functions such as
+/// [`simplify_rdp_ffi_f32`](fn.simplify_rdp_ffi_f32.html), which are always available.
+///
+/// Returns 1 if the feature is available, and 0 if it isn't, or if the name is a null pointer or
+/// isn't recognised, so hosts which load this library dynamically can probe its capabilities
+/// before calling functions which may not exist.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub unsafe extern "C" fn rdp_has_feature(name: *const libc::c_char) -> libc::c_int {
+    if name.is_null() {
+        return 0;
+    }
+    let features: [(&[u8], bool); 5] = [
+        (b"rayon", cfg!(feature = "rayon")),
+        (b"geojson", cfg!(feature = "geojson")),
+        (b"wkb", cfg!(feature = "wkb")),
+        (b"serde", cfg!(feature = "serde")),
+        (b"f32", true),
+    ];
+    let name = CStr::from_ptr(name).to_bytes();
+    features
+        .iter()
+        .any(|&(feature, available)| available && feature == name)
+        .into()
+}
+
+/// Get the number of bytes of memory owned by an array returned by this library
+///
+/// Callers must pass two arguments:
+///
+/// - a pointer to an `Array` returned by this library, which hasn't been freed
+/// - an `int` for the array's element type: see [`ElementKind`](enum.ElementKind.html)
+///
+/// The size is the array's `len` multiplied by the size of its element type, so hosts can account
+/// for the memory before freeing it. 0 is returned if the pointer is null, or the element type
+/// isn't valid.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub unsafe extern "C" fn internal_array_byte_size(
+    arr: *const InternalArray,
+    element_kind: libc::c_int,
+) -> libc::size_t {
+    match (arr.as_ref(), ElementKind::try_from(element_kind)) {
+        (Some(arr), Ok(kind)) => arr.len * kind.size(),
+        _ => 0,
+    }
+}
+
+// Run a computation, returning `fallback` if it panics. Unwinding across the FFI boundary is
+// undefined behaviour, so every FFI function which simplifies goes through here
+pub(crate) fn guarded<R, F>(fallback: R, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let reason = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("unknown cause");
+        set_last_error(format!("simplification panicked: {reason}"));
+        fallback
+    })
+}
+
+// Validate incoming coordinates before handing them to a simplification function
+fn checked<T, F>(coords: ExternalArray, simplify: F) -> FfiResult
+where
+    T: CoordFloat,
+    F: FnOnce(&[[T; 2]]) -> InternalArray,
+{
+    match coords.validate::<T, 2>() {
+        Ok(valid) => guarded(
+            FfiResult {
+                array: InternalArray::empty(),
+                error: FfiError::Panic,
+            },
+            || FfiResult {
+                array: simplify(valid),
+                error: FfiError::Success,
+            },
+        ),
+        Err(error) => FfiResult {
+            array: InternalArray::empty(),
+            error,
+        },
+    }
+}
+
+// The addresses of the arrays which have been leaked and not yet reclaimed, with a count for each,
+// as empty arrays share an address, and the name of the element type of the arrays leaked at the
+// address. Entries are removed when their count reaches 0, as the address can then be reused for
+// an array of another type. Arrays allocated by a host allocator aren't tracked, as the host can
+// free them without telling us. Debug builds use this to catch arrays which are freed twice, which weren't
+// allocated by `leak`, or which are freed as the wrong type, by calling the wrong drop function
+#[cfg(debug_assertions)]
+static LIVE: Mutex<BTreeMap<usize, (usize, &str)>> = Mutex::new(BTreeMap::new());
+
+/// A function which allocates the memory for arrays returned by this library, such as `malloc`
+///
+/// It's called with a size in bytes, which is never 0, and must return a pointer to memory which is
+/// suitably aligned for any type, or a null pointer if the allocation fails.
+pub type AllocCallback = Option<extern "C" fn(size: libc::size_t) -> *mut libc::c_void>;
+
+/// A function which frees memory allocated by an [`AllocCallback`], such as `free`
+pub type FreeCallback = Option<extern "C" fn(data: *mut libc::c_void)>;
+
+// A host allocator, which is used instead of Rust's for the arrays returned across the FFI boundary
+#[derive(Clone, Copy)]
+struct HostAllocator {
+    alloc: extern "C" fn(libc::size_t) -> *mut libc::c_void,
+    free: extern "C" fn(*mut libc::c_void),
+}
+
+// The host allocator, if any. It's fixed by the first call to `rdp_set_allocator` or the first
+// allocation, whichever comes first, so every array is freed by the allocator which allocated it
+static ALLOCATOR: OnceLock<Option<HostAllocator>> = OnceLock::new();
+
+fn host_allocator() -> Option<HostAllocator> {
+    *ALLOCATOR.get_or_init(|| None)
+}
+
+/// Allocate the arrays returned by this library using the host's allocator
+///
+/// Callers must pass two arguments:
+///
+/// - an [`AllocCallback`](type.AllocCallback.html), such as `malloc`
+/// - a [`FreeCallback`](type.FreeCallback.html) which frees the memory it allocates, such as `free`
+///
+/// Afterwards, the `data` of each returned array is allocated by `alloc_fn`, so the host can free
+/// it directly using its own allocator, rather than by calling the array's drop function (which
+/// calls `free_fn`). The arrays of arrays returned by the batch functions, and each array in them,
+/// are allocated in the same way. Empty arrays have a null `data` pointer, and nothing is
+/// allocated for them. Strings, and arrays borrowed from an [`RdpPool`](struct.RdpPool.html), are
+/// unaffected. If `alloc_fn` fails, an empty array is returned. The debug-build checks for arrays
+/// which are freed twice, or by the wrong drop function, don't apply to host-allocated arrays.
+///
+/// The allocator can only be set once, and **must** be set before any function returning an array
+/// is called, so that no array is freed by a different allocator from the one which allocated it.
+/// The callbacks may be called from any thread, so they must be thread-safe.
+///
+/// Returns 1 if the allocator was set, or 0 if either callback is null, or it's too late to set it.
+#[no_mangle]
+pub extern "C" fn rdp_set_allocator(alloc_fn: AllocCallback, free_fn: FreeCallback) -> libc::c_int {
+    let (Some(alloc), Some(free)) = (alloc_fn, free_fn) else {
+        return 0;
+    };
+    ALLOCATOR
+        .set(Some(HostAllocator { alloc, free }))
+        .is_ok()
+        .into()
+}
+
+// Leak a Vec across the FFI boundary, using the host allocator if there is one. It must be
+// reclaimed by `reclaim` or `free`, using the same element type
+pub(crate) fn leak<T>(mut v: Vec<T>) -> InternalArray {
+    let blen = v.len();
+    let host = host_allocator();
+    let rawp = match host {
+        Some(_) if v.is_empty() => return InternalArray::empty(),
+        Some(allocator) => {
+            let size = mem::size_of_val(v.as_slice());
+            let rawp = (allocator.alloc)(size) as *mut T;
+            if rawp.is_null() {
+                set_last_error(format!(
+                    "the host allocator failed to allocate {size} bytes"
+                ));
+                return InternalArray::empty();
+            }
+            debug_assert!(
+                rawp.is_aligned(),
+                "the host allocator returned unaligned memory"
+            );
+            // the elements now belong to the host's memory, so the Vec mustn't drop them
+            unsafe {
+                ptr::copy_nonoverlapping(v.as_ptr(), rawp, blen);
+                v.set_len(0);
+            }
+            rawp
+        }
+        None => Box::into_raw(v.into_boxed_slice()) as *mut T,
+    };
+    #[cfg(debug_assertions)]
+    if host.is_none() {
+        let mut live = LIVE.lock().unwrap_or_else(PoisonError::into_inner);
+        let (count, kind) = live.entry(rawp as usize).or_insert((0, type_name::<T>()));
+        if *count == 0 {
+            *kind = type_name::<T>();
+        }
+        *count += 1;
+    }
+    InternalArray {
+        data: rawp as *mut libc::c_void,
+        len: blen as libc::size_t,
+    }
+}
+
+// Take back ownership of an array created by `leak`. An empty array yields an empty Vec
+pub(crate) unsafe fn reclaim<T>(arr: InternalArray) -> Vec<T> {
+    if arr.data.is_null() {
+        return vec![];
+    }
+    let host = host_allocator();
+    #[cfg(debug_assertions)]
+    if host.is_none() {
+        let mut live = LIVE.lock().unwrap_or_else(PoisonError::into_inner);
+        let address = arr.data as usize;
+        // empty arrays of different types can share an address, but freeing them does nothing
+        let kind = live.get(&address).map(|&(_, kind)| kind);
+        let mismatched = arr.len > 0 && kind.is_some_and(|kind| kind != type_name::<T>());
+        let was_live = match live.get_mut(&address) {
+            _ if mismatched => true,
+            Some((count, _)) => {
+                *count -= 1;
+                if *count == 0 {
+                    live.remove(&address);
+                }
+                true
+            }
+            None => false,
+        };
+        drop(live);
+        debug_assert!(
+            was_live,
+            "array at {:p} was not allocated by this library, or has already been freed",
+            arr.data
+        );
+        debug_assert!(
+            !mismatched,
+            "array of {} at {:p} was freed as an array of {}: use the drop function documented \
+             by the function which returned it",
+            kind.unwrap_or_default(),
+            arr.data,
+            type_name::<T>()
+        );
+    }
+    match host {
+        // move the elements out of the host's memory before freeing it
+        Some(allocator) => {
+            let mut v = Vec::with_capacity(arr.len);
+            ptr::copy_nonoverlapping(arr.data as *const T, v.as_mut_ptr(), arr.len);
+            v.set_len(arr.len);
+            (allocator.free)(arr.data);
+            v
+        }
+        // we originated this data, so pointer-to-slice -> box -> vec
+        None => {
+            let p = ptr::slice_from_raw_parts_mut(arr.data as *mut T, arr.len);
+            Box::from_raw(p).into_vec()
+        }
+    }
+}
+
+// Free an array created by `leak`
+unsafe fn free<T>(arr: InternalArray) {
+    drop(reclaim::<T>(arr));
+}
+
+// Borrow a slice as an array originating outside Rust, as a caller would pass it
+#[cfg(test)]
+pub(crate) fn external<T>(data: &[T]) -> ExternalArray {
+    ExternalArray {
+        data: data.as_ptr() as *const libc::c_void,
+        len: data.len(),
+    }
+}
+
+// Copy the coordinates out of an array created by `leak`, which must still be freed
+#[cfg(test)]
+pub(crate) fn read_coords(arr: &InternalArray) -> Vec<[f64; 2]> {
+    unsafe { slice::from_raw_parts(arr.data as *const [f64; 2], arr.len).to_vec() }
+}
+
+// Copy the contents of an array originating outside Rust, so we can take ownership of them
+unsafe fn copied<T: Clone>(arr: ExternalArray) -> Vec<T> {
+    if arr.data.is_null() {
+        return vec![];
+    }
+    slice::from_raw_parts(arr.data as *const T, arr.len).to_vec()
+}
+
+// Validate incoming coordinates of any precision, and simplify them as a LineString
+fn simplify_generic<T, F>(coords: ExternalArray, simplify: F) -> InternalArray
+where
+    T: CoordFloat,
+    F: FnOnce(&LineString<T>) -> LineString<T>,
+{
+    checked(coords, |coords: &[[T; 2]]| {
+        api::simplify_with(LineString::from(coords.to_vec()), simplify).into()
+    })
+    .array
+}
+
+/// Take ownership of an array of coordinates returned by this library, as a `LineString`
+///
+/// This is intended for Rust code which calls the FFI functions, such as tests and bindings. The
+/// `LineString` takes over the array's memory, which is freed when the `LineString` is dropped: the
+/// array **must not** be passed to [`drop_float_array`](fn.drop_float_array.html) afterwards, and
+/// any copies of its `data` pointer are dangling. An empty array yields an empty `LineString`.
+///
+/// The array must have been returned by a function whose result is freed using
+/// [`drop_float_array`](fn.drop_float_array.html). In debug builds, arrays which have already been
+/// freed, or which weren't allocated by this library, cause a panic.
+pub fn internal_array_to_linestring(arr: InternalArray) -> LineString<f64> {
+    arr.into()
+}
+
+/// Move a `LineString` into an array, transferring ownership of its memory across the FFI boundary
+///
+/// The array **must** be freed exactly once: either by passing it to
+/// [`drop_float_array`](fn.drop_float_array.html), or by taking ownership of it again using
+/// [`internal_array_to_linestring`](fn.internal_array_to_linestring.html).
+pub fn linestring_to_internal_array(ls: LineString<f64>) -> InternalArray {
+    ls.into()
+}
+
+// Build an InternalArray from a LineString, so it can be leaked across the FFI boundary
+impl<T> From<LineString<T>> for InternalArray
+where
+    T: CoordFloat,
+{
+    fn from(sl: LineString<T>) -> Self {
+        leak(sl.0.iter().map(|p| [p.x, p.y]).collect::<Vec<[T; 2]>>())
+    }
+}
+
+// Build an ExternalArray from a LineString, so it can be leaked across the FFI boundary
+impl<T> From<LineString<T>> for ExternalArray
+where
+    T: CoordFloat,
+{
+    fn from(sl: LineString<T>) -> Self {
+        let arr = InternalArray::from(sl);
+        ExternalArray {
+            data: arr.data,
+            len: arr.len,
+        }
+    }
+}
+
+// Build an InternalArray from a vec of coordinates, so it can be leaked across the FFI boundary
+impl<T> From<Vec<[T; 2]>> for InternalArray
+where
+    T: CoordFloat,
+{
+    fn from(v: Vec<[T; 2]>) -> Self {
+        leak(v)
+    }
+}
+
+// Build an InternalArray from a vec of usize, so it can be leaked across the FFI boundary
+impl From<Vec<usize>> for InternalArray {
+    fn from(v: Vec<usize>) -> Self {
+        leak(v)
+    }
+}
+
+// Build an InternalArray from a vec of 3D coordinates, so it can be leaked across the FFI boundary
+impl From<Vec<[f64; 3]>> for InternalArray {
+    fn from(v: Vec<[f64; 3]>) -> Self {
+        leak(v)
+    }
+}
+
+// Build a Vec of 3D coordinates from an InternalArray
+impl From<InternalArray> for Vec<[f64; 3]> {
+    fn from(arr: InternalArray) -> Self {
+        unsafe { reclaim(arr) }
+    }
+}
+
+// Build a LineString of any precision from an ExternalArray
+impl<T> From<ExternalArray> for LineString<T>
+where
+    T: CoordFloat,
+{
+    fn from(arr: ExternalArray) -> Self {
+        unsafe { copied::<[T; 2]>(arr) }.into()
+    }
+}
+
+// Build a LineString of any precision from an InternalArray
+// Ideally this would be a LineString, but local types blah blah
+impl<T> From<InternalArray> for LineString<T>
+where
+    T: CoordFloat,
+{
+    fn from(arr: InternalArray) -> Self {
+        unsafe { reclaim::<[T; 2]>(arr) }.into()
+    }
+}
+
+// Build a Vec of usize from an ExternalArray
+impl From<ExternalArray> for Vec<usize> {
+    fn from(arr: ExternalArray) -> Self {
+        unsafe { copied(arr) }
+    }
+}
+
+// Build a Vec of usize from an InternalArray
+impl From<InternalArray> for Vec<usize> {
+    fn from(arr: InternalArray) -> Self {
+        unsafe { reclaim(arr) }
+    }
+}
+
+/// FFI wrapper for all simplification algorithms, returning simplified geometry **coordinates**
+///
+/// Callers must pass three arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance or epsilon
+/// - an `int` selecting the [`Algorithm`](enum.Algorithm.html): RDP (0), Visvalingam-Whyatt (1),
+///   or topology-preserving Visvalingam-Whyatt (2). Any other value returns an empty array.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+    algorithm: libc::c_int,
+) -> InternalArray {
+    match Algorithm::try_from(algorithm) {
+        Ok(algorithm) => {
+            let simplifier = algorithm.simplifier(precision);
+            checked(coords, |coords| simplifier.simplify(coords).into()).array
+        }
+        Err(_) => InternalArray::empty(),
+    }
+}
+
+/// FFI wrapper for RDP, returning simplified geometry **coordinates**
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+///
+/// Simplification doesn't recurse, so its stack usage doesn't depend on the shape of the line.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+) -> InternalArray {
+    simplify_ffi(coords, precision, Algorithm::Rdp as libc::c_int)
+}
+
+/// FFI wrapper for RDP, returning simplified geometry **coordinates** as a flat array
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+///
+/// The points are the same as those returned by [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html),
+/// but the returned `Array`'s `data` field points to interleaved `double`s: `[x0, y0, x1, y1, ...]`,
+/// and its `len` field is the number of `double`s, which is twice the number of points.
+///
+/// Implementations calling this function **must** call [`drop_flat_f64_array`](fn.drop_flat_f64_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+/// [`drop_float_array`](fn.drop_float_array.html) **must not** be used for this purpose.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_flat_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+) -> InternalArray {
+    checked(coords, |coords| {
+        leak(
+            api::simplify_rdp(coords, precision)
+                .into_iter()
+                .flatten()
+                .collect::<Vec<f64>>(),
+        )
+    })
+    .array
+}
+
+/// FFI wrapper for RDP, taking and returning x and y coordinates as separate arrays
+///
+/// Callers must pass four arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of double-precision `float` x coordinates
+///     - `len`, the number of x coordinates. Its type must be `size_t`
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of double-precision `float` y coordinates
+///     - `len`, the number of y coordinates, which must equal the number of x coordinates
+/// - a double-precision `float` for the tolerance
+/// - a pointer to an [`InternalArray`](struct.InternalArray.html), into which the y coordinates of
+///   the retained points are written. A null pointer is ignored.
+///
+/// The points are the same as those returned by [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html) for
+/// the interleaved coordinates: the returned `Array` holds their x coordinates, and the array
+/// written to `ys_out` holds their y coordinates. If either input is invalid, or their lengths
+/// differ, both arrays are empty.
+///
+/// Implementations calling this function **must** call [`drop_flat_f64_array`](fn.drop_flat_f64_array.html)
+/// with both the returned `Array` and the y coordinates, in order to free the memory they allocate.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses raw pointers which could contain arbitrary data
+#[no_mangle]
+pub unsafe extern "C" fn simplify_rdp_xy_ffi(
+    xs: ExternalArray,
+    ys: ExternalArray,
+    precision: libc::c_double,
+    ys_out: *mut InternalArray,
+) -> InternalArray {
+    let columns = match (xs.validate::<f64, 1>(), ys.validate::<f64, 1>()) {
+        (Ok(xs), Ok(ys)) if xs.len() == ys.len() => guarded(None, || {
+            let points: Vec<[f64; 2]> = xs.iter().zip(ys).map(|([x], [y])| [*x, *y]).collect();
+            Some(
+                api::simplify_rdp(&points, precision)
+                    .into_iter()
+                    .map(|[x, y]| (x, y))
+                    .unzip::<_, _, Vec<f64>, Vec<f64>>(),
+            )
+        }),
+        _ => None,
+    };
+    let Some((xs, ys)) = columns else {
+        if !ys_out.is_null() {
+            *ys_out = InternalArray::empty();
+        }
+        return InternalArray::empty();
+    };
+    if !ys_out.is_null() {
+        *ys_out = leak(ys);
+    }
+    leak(xs)
+}
+
+/// FFI wrapper for cancellable RDP, returning simplified geometry **coordinates**
+///
+/// Callers must pass three arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+/// - a pointer to an `int` flag, which another thread sets to a non-zero value to cancel the
+///   simplification. It must be written atomically, for instance as a C11 `atomic_int`, and must
+///   remain valid until this function returns. A null pointer means the simplification can't be
+///   cancelled
+///
+/// The flag is checked before each segment is simplified, so a long simplification stops soon after
+/// it is set, and an empty array is returned. Input with fewer than 3 points is returned without
+/// checking the flag.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses raw pointers which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_cancellable_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+    cancel: *const libc::c_int,
+) -> InternalArray {
+    let flag = (!cancel.is_null()).then(|| unsafe { AtomicI32::from_ptr(cancel as *mut i32) });
+    checked(coords, |coords| {
+        let cancelled = || flag.is_some_and(|flag| flag.load(Ordering::Relaxed) != 0);
+        douglas_peucker::rdp_indices_cancellable(coords, precision, cancelled).map_or_else(
+            InternalArray::empty,
+            |retained| {
+                retained
+                    .into_iter()
+                    .map(|idx| coords[idx])
+                    .collect::<Vec<_>>()
+                    .into()
+            },
+        )
+    })
+    .array
+}
+
+/// FFI wrapper for RDP, returning simplified geometry **indices**
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+///
+/// The indices are `size_t` values, so their width depends on the target: 4 bytes on 32-bit
+/// targets, and 8 bytes on 64-bit targets. Bindings which assume a fixed width should use
+/// [`simplify_rdp_idx_u32_ffi`](fn.simplify_rdp_idx_u32_ffi.html) instead.
+///
+/// Implementations calling this function **must** call [`drop_usize_array`](fn.drop_usize_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_idx_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+) -> InternalArray {
+    simplify_rdp_idx_checked_ffi(coords, precision).array
+}
+
+// Narrow indices to u32, failing if any of them is too large
+fn indices_u32(indices: Vec<usize>) -> Result<Vec<u32>, FfiError> {
+    indices
+        .into_iter()
+        .map(|idx| u32::try_from(idx).map_err(|_| FfiError::IndexOverflow))
+        .collect()
+}
+
+/// FFI wrapper for RDP, returning simplified geometry **indices** as 32-bit unsigned integers
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+///
+/// Unlike [`simplify_rdp_idx_ffi`](fn.simplify_rdp_idx_ffi.html), the indices are `uint32_t`
+/// values on every target. If any retained index is greater than `UINT32_MAX`, the returned
+/// [`FfiResult`](struct.FfiResult.html) contains an empty array and an `IndexOverflow` error.
+/// Invalid input is reported in the same way as by
+/// [`simplify_rdp_idx_checked_ffi`](fn.simplify_rdp_idx_checked_ffi.html).
+///
+/// Implementations calling this function **must** call [`drop_u32_array`](fn.drop_u32_array.html)
+/// with the returned `array` field, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_idx_u32_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+) -> FfiResult {
+    let mut overflowed = false;
+    let mut result = checked(coords, |coords| {
+        match indices_u32(api::simplify_rdp_idx(coords, precision)) {
+            Ok(indices) => leak(indices),
+            Err(_) => {
+                overflowed = true;
+                InternalArray::empty()
+            }
+        }
+    });
+    if overflowed {
+        result.error = FfiError::IndexOverflow;
+    }
+    result
+}
+
+/// FFI wrapper for RDP, returning the **indices** of the points removed by simplification
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+///
+/// The indices are sorted, and are the complement of those returned by
+/// [`simplify_rdp_idx_ffi`](fn.simplify_rdp_idx_ffi.html): together, they contain every index of
+/// the input exactly once. If the input is invalid, an empty array is returned.
+///
+/// Implementations calling this function **must** call [`drop_usize_array`](fn.drop_usize_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_removed_idx_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+) -> InternalArray {
+    checked(coords, |coords| {
+        api::simplify_rdp_removed_idx(coords, precision).into()
+    })
+    .array
+}
+
+/// FFI wrapper for RDP, returning the number of points removed from each simplified segment
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+///
+/// There is one count for each segment of the line returned by
+/// [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html), in order, so the counts show where points were
+/// removed, and sum to the number of points removed. Input with fewer than two points has no
+/// segments, and produces an empty array, as does invalid input.
+///
+/// Implementations calling this function **must** call [`drop_usize_array`](fn.drop_usize_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_segment_stats_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+) -> InternalArray {
+    checked(coords, |coords| {
+        api::simplify_rdp_segment_stats(coords, precision).into()
+    })
+    .array
+}
+
+/// FFI wrapper for RDP on a time series, returning simplified geometry **coordinates**
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point (x, y) pairs, whose x coordinates,
+///       such as times, are strictly increasing: `[[1.0, 2.0], [2.0, 1.5], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+///
+/// Distances are measured vertically, between each point and the value interpolated at its x
+/// coordinate by the segment's ends, rather than perpendicular to the segment as by
+/// [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html), so the tolerance is in the units of y alone. This
+/// suits charts, whose axes have unrelated units. The output's x coordinates are strictly increasing.
+/// If the input's x coordinates aren't strictly increasing, or the input is otherwise invalid, an
+/// empty array is returned.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_series_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+) -> InternalArray {
+    checked(coords, |coords| {
+        match coords.windows(2).position(|pair| pair[0][0] >= pair[1][0]) {
+            Some(idx) => {
+                set_last_error(format!(
+                    "the x coordinate of point {} of the input array isn't greater than that of \
+                     point {idx}",
+                    idx + 1
+                ));
+                InternalArray::empty()
+            }
+            None => api::simplify_rdp_series(coords, precision).into(),
+        }
+    })
+    .array
+}
+
+/// FFI wrapper for RDP, returning a **mask** of the points retained by simplification
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+///
+/// The returned `Array`'s `data` field points to `uint8_t` values, one per input point, which are
+/// 1 for the points returned by [`simplify_rdp_idx_ffi`](fn.simplify_rdp_idx_ffi.html), and 0 for
+/// the rest. Columnar callers can use it to filter the points' attributes in a single operation.
+/// If the input is invalid, an empty array is returned.
+///
+/// Implementations calling this function **must** call [`drop_u8_array`](fn.drop_u8_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_mask_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+) -> InternalArray {
+    checked(coords, |coords| {
+        leak(
+            api::simplify_rdp_mask(coords, precision)
+                .into_iter()
+                .map(u8::from)
+                .collect(),
+        )
+    })
+    .array
+}
+
+/// FFI wrapper for RDP, simplifying part of a line, and returning geometry **coordinates**
+///
+/// Callers must pass four arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a `size_t` for the index of the first point to simplify
+/// - a `size_t` for the index of the last point to simplify
+/// - a double-precision `float` for the tolerance
+///
+/// Only the points from `start` to `end` (inclusive) are simplified, in the same way as
+/// [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html), and the points before and after them are
+/// returned unchanged, so an editor can re-simplify just the part of a line being edited. Passing
+/// `0` and `len - 1` simplifies the whole line. If `start` is greater than `end`, or `end` is out
+/// of bounds, an empty array is returned.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_range_ffi(
+    coords: ExternalArray,
+    start: libc::size_t,
+    end: libc::size_t,
+    precision: libc::c_double,
+) -> InternalArray {
+    checked(coords, |coords| {
+        match api::simplify_rdp_range(coords, start, end, precision) {
+            Some(simplified) => simplified.into(),
+            None => InternalArray::empty(),
+        }
+    })
+    .array
+}
+
+/// FFI wrapper for RDP, joining two lines and returning the simplified geometry **coordinates**
+///
+/// Callers must pass three arguments:
+///
+/// - two [Structs](struct.Array.html), each with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+///
+/// The points of `b` are appended to those of `a`, and the joined line is simplified as one, in
+/// the same way as [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html). If the last point of `a` is
+/// equal to the first point of `b`, such as at the junction of two adjacent segments of a route, it
+/// appears once in the joined line, and is removed if it's within the tolerance. If either input is
+/// invalid, an empty array is returned.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses raw pointers which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn merge_simplify_rdp_ffi(
+    a: ExternalArray,
+    b: ExternalArray,
+    precision: libc::c_double,
+) -> InternalArray {
+    match (a.validate::<f64, 2>(), b.validate::<f64, 2>()) {
+        (Ok(a), Ok(b)) => guarded(InternalArray::empty(), || {
+            api::merge_simplify_rdp(a, b, precision).into()
+        }),
+        _ => InternalArray::empty(),
+    }
+}
+
+/// FFI wrapper for RDP, limiting the length of simplified segments, and returning simplified
+/// geometry **coordinates**
+///
+/// Callers must pass three arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+/// - a double-precision `float` for the maximum length of a segment of the simplified line
+///
+/// The line is simplified as by [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html), which can replace a
+/// long, gentle curve by a single segment. Then, each segment which is longer than
+/// `max_segment_length` is split by restoring the removed point closest to its midpoint, until
+/// every segment is short enough, or no removed points remain between its ends. Segments of the
+/// input which are longer than `max_segment_length` therefore can't be split.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_max_segment_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+    max_segment_length: libc::c_double,
+) -> InternalArray {
+    checked(coords, |coords| {
+        api::simplify_rdp_max_segment(coords, precision, max_segment_length).into()
+    })
+    .array
+}
+
+/// FFI wrapper for RDP, guaranteeing a minimum sampling density, and returning simplified geometry
+/// **coordinates**
+///
+/// Callers must pass three arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+/// - a `size_t` for the maximum number of input points a simplified segment may skip
+///
+/// The line is simplified as by [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html). Then, each segment
+/// which skips more than `max_span` input points is split by restoring every `max_span + 1`th
+/// removed point along it, which bounds the interpolation error in index space. A `max_span` of 0
+/// retains every point.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_density_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+    max_span: libc::size_t,
+) -> InternalArray {
+    checked(coords, |coords| {
+        api::simplify_rdp_density(coords, precision, max_span).into()
+    })
+    .array
+}
+
+/// FFI wrapper for RDP, returning simplified geometry **coordinates** and the number of removed points
+///
+/// Callers must pass three arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+/// - a pointer to a `size_t`, into which the number of points removed by simplification is written.
+///   If the input is invalid, 0 is written. A null pointer is ignored.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub unsafe extern "C" fn simplify_rdp_stats_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+    removed_out: *mut libc::size_t,
+) -> InternalArray {
+    let result = simplify_rdp_checked_ffi(coords, precision);
+    if !removed_out.is_null() {
+        *removed_out = match result.error {
+            FfiError::Success => coords.len - result.array.len,
+            _ => 0,
+        };
+    }
+    result.array
+}
+
+/// FFI wrapper for RDP, returning simplified geometry **coordinates** and their bounding box
+///
+/// Callers must pass three arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+/// - a pointer to an array of four double-precision `float`s, into which the bounding box of the
+///   simplified line is written as `[min_x, min_y, max_x, max_y]`. If the returned array is empty,
+///   NaN is written. A null pointer is ignored.
+///
+/// The bounding box is that of the returned coordinates, not of the input: if simplification
+/// removes an interior point at the edge of the input's bounding box, the simplified line's bounding
+/// box is slightly smaller. The endpoints are always retained, so it always contains them.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses raw pointers which could contain arbitrary data
+#[no_mangle]
+pub unsafe extern "C" fn simplify_rdp_bbox_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+    bbox_out: *mut [libc::c_double; 4],
+) -> InternalArray {
+    let result = simplify_rdp_checked_ffi(coords, precision);
+    if !bbox_out.is_null() {
+        let simplified = if result.array.data.is_null() {
+            &[]
+        } else {
+            slice::from_raw_parts(result.array.data as *const [f64; 2], result.array.len)
+        };
+        *bbox_out = api::bounding_box(simplified).unwrap_or([f64::NAN; 4]);
+    }
+    result.array
+}
+
+/// FFI wrapper for RDP, returning the convex hull of the simplified geometry's **coordinates**
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+///
+/// The input is simplified as by [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html), and the convex hull
+/// of the retained points is computed by geo. It's returned as a closed, counter-clockwise ring,
+/// whose vertices are a subset of the retained points, for use in broad-phase culling. If fewer
+/// than three distinct points are retained, they can't form a ring, so the retained points are
+/// returned instead.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_hull_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+) -> InternalArray {
+    checked(coords, |coords| {
+        let simplified = api::simplify_rdp(coords, precision);
+        let mut distinct = simplified.clone();
+        distinct.sort_unstable_by(|a, b| a[0].total_cmp(&b[0]).then(a[1].total_cmp(&b[1])));
+        distinct.dedup();
+        if distinct.len() < 3 {
+            return simplified.into();
+        }
+        LineString::from(simplified)
+            .convex_hull()
+            .into_inner()
+            .0
+            .into()
+    })
+    .array
+}
+
+/// FFI wrapper for RDP, returning simplified geometry **coordinates** and the length of the line
+/// before and after simplification
+///
+/// Callers must pass four arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+/// - a pointer to a double-precision `float`, into which the Euclidean length of the input is written
+/// - a pointer to a double-precision `float`, into which the Euclidean length of the simplified line
+///   is written
+///
+/// The simplified line's vertices are a subset of the input's, so its length never exceeds the
+/// input's. If the input is invalid, NaN is written to both. Null pointers are ignored.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses raw pointers which could contain arbitrary data
+#[no_mangle]
+pub unsafe extern "C" fn simplify_rdp_length_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+    orig_len_out: *mut libc::c_double,
+    simp_len_out: *mut libc::c_double,
+) -> InternalArray {
+    let result = simplify_rdp_checked_ffi(coords, precision);
+    let length = |line: &[[f64; 2]]| LineString::from(line.to_vec()).euclidean_length();
+    let (original, simplified) = match (result.error, coords.validate::<f64, 2>()) {
+        (FfiError::Success, Ok(input)) => (
+            length(input),
+            length(slice::from_raw_parts(
+                result.array.data as *const [f64; 2],
+                result.array.len,
+            )),
+        ),
+        _ => (f64::NAN, f64::NAN),
+    };
+    if !orig_len_out.is_null() {
+        *orig_len_out = original;
+    }
+    if !simp_len_out.is_null() {
+        *simp_len_out = simplified;
+    }
+    result.array
+}
+
+/// FFI wrapper for RDP with bounded work, returning simplified geometry **coordinates**
+///
+/// Callers must pass four arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+/// - a `size_t` for the maximum number of point-to-segment distances to compute
+/// - a pointer to an `int`, into which 1 is written if simplification stopped early, and 0
+///   otherwise. A null pointer is ignored.
+///
+/// RDP does O(n²) work for pathological input, so this gives latency-sensitive callers a hard
+/// ceiling. Segments are split in order of decreasing distance, so if `max_work` is reached, the
+/// most significant points have already been retained, and the result is a coarser (but valid)
+/// simplification. Otherwise, the result is identical to that of
+/// [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html). If the input is invalid, an empty array is
+/// returned, and 0 is written.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses raw pointers which could contain arbitrary data
+#[no_mangle]
+pub unsafe extern "C" fn simplify_rdp_bounded_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+    max_work: libc::size_t,
+    truncated_out: *mut libc::c_int,
+) -> InternalArray {
+    let mut truncated = false;
+    let result = checked(coords, |coords| {
+        let simplified;
+        (simplified, truncated) = api::simplify_rdp_bounded(coords, precision, max_work);
+        simplified.into()
+    });
+    if !truncated_out.is_null() {
+        *truncated_out = libc::c_int::from(truncated);
+    }
+    result.array
+}
+
+/// FFI wrapper for RDP, returning simplified geometry **coordinates** and their **indices**
+///
+/// Callers must pass three arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+/// - a pointer to an [`InternalArray`](struct.InternalArray.html), into which the indices of the
+///   retained points are written. A null pointer is ignored.
+///
+/// The geometry is only simplified once: the returned coordinates are those of the input at the
+/// written indices, in the same order. If the input is invalid, both arrays are empty.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array`, and [`drop_usize_array`](fn.drop_usize_array.html) with the indices,
+/// in order to free the memory they allocate.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses raw pointers which could contain arbitrary data
+#[no_mangle]
+pub unsafe extern "C" fn simplify_rdp_both_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+    idx_out: *mut InternalArray,
+) -> InternalArray {
+    let indices = match coords.validate::<f64, 2>() {
+        Ok(valid) => guarded(None, || {
+            Some((valid, api::simplify_rdp_idx(valid, precision)))
+        }),
+        Err(_) => None,
+    };
+    let Some((valid, indices)) = indices else {
+        if !idx_out.is_null() {
+            *idx_out = InternalArray::empty();
+        }
+        return InternalArray::empty();
+    };
+    let retained: Vec<[f64; 2]> = indices.iter().map(|&idx| valid[idx]).collect();
+    if !idx_out.is_null() {
+        *idx_out = indices.into();
+    }
+    retained.into()
+}
+
+/// FFI wrapper for RDP, returning simplified geometry **coordinates** after discarding
+/// non-finite input points
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+///
+/// Unlike [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html), which returns an empty array if any
+/// coordinate is NaN or infinite, this function drops every point with a non-finite coordinate
+/// (e.g. a dropped GPS fix) and simplifies the remaining points, in their original order.
+/// If no finite points remain, an empty array is returned.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_clean_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+) -> InternalArray {
+    let Ok(raw) = coords.readable::<f64, 2>() else {
+        return InternalArray::empty();
+    };
+    let finite: Vec<[f64; 2]> = raw
+        .iter()
+        .filter(|coord| coord.iter().all(|c| c.is_finite()))
+        .copied()
+        .collect();
+    if finite.is_empty() {
+        set_last_error("every point of the input array has a NaN or infinite coordinate");
+        return InternalArray::empty();
+    }
+    let finite = ExternalArray {
+        data: finite.as_ptr() as *const libc::c_void,
+        len: finite.len(),
+    };
+    simplify_rdp_checked_ffi(finite, precision).array
+}
+
+/// FFI wrapper for RDP with a tolerance relative to the line's extent, returning simplified geometry **coordinates**
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance, as a fraction of the length of the diagonal of
+///   the line's bounding box
+///
+/// A fraction of 0.005 simplifies each line with a tolerance of 0.5% of its extent, so features of
+/// very different sizes are simplified consistently: scaling a line doesn't change which of its
+/// points are retained.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_relative_ffi(
+    coords: ExternalArray,
+    fraction: libc::c_double,
+) -> InternalArray {
+    checked(coords, |coords| {
+        api::simplify_rdp_relative(coords, fraction).into()
+    })
+    .array
+}
+
+/// FFI wrapper for RDP, returning at most a given number of simplified geometry **coordinates**
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a `size_t` for the maximum number of points to return. Values below 2 are treated as 2, so
+///   the endpoints are always retained
+///
+/// The smallest tolerance producing no more than the requested number of points is used. Input
+/// which is already short enough is returned unchanged.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_max_points_ffi(
+    coords: ExternalArray,
+    max_points: libc::size_t,
+) -> InternalArray {
+    checked(coords, |coords| {
+        api::simplify_rdp_max_points(coords, max_points).into()
+    })
+    .array
+}
+
+/// FFI wrapper for RDP, retaining approximately a given fraction of the simplified geometry's
+/// **coordinates**
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the fraction of points to retain, which is clamped to (0, 1]
+///
+/// The tolerance is found by bisection, and the result whose length is closest to the fraction of
+/// the input's points (but never fewer than 2) is returned, so a ratio of 0.2 keeps about 20% of the
+/// points. Input which is already short enough is returned unchanged.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_target_ratio_ffi(
+    coords: ExternalArray,
+    keep_ratio: libc::c_double,
+) -> InternalArray {
+    checked(coords, |coords| {
+        api::simplify_rdp_target_ratio(coords, keep_ratio).into()
+    })
+    .array
+}
+
+/// FFI wrapper for RDP and Visvalingam-Whyatt, returning whichever simplified geometry has fewer
+/// **coordinates**
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the maximum distance of any input point from the output
+///
+/// The line is simplified as by [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html), and as by
+/// [`simplify_visvalingam_ffi`](fn.simplify_visvalingam_ffi.html) with the largest area (found by
+/// bisection) which keeps every input point within `tolerance` of the output. The result with fewer
+/// points is returned, or the RDP result if they're the same length.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_best_ffi(
+    coords: ExternalArray,
+    tolerance: libc::c_double,
+) -> InternalArray {
+    checked(coords, |coords| {
+        api::simplify_best(coords, tolerance).into()
+    })
+    .array
+}
+
+/// FFI wrapper for RDP, returning simplified geometry **coordinates** which fit in a given number of bytes
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a `size_t` for the maximum size of the returned coordinates, in bytes
+///
+/// Each returned point occupies 16 bytes, so this is equivalent to calling
+/// [`simplify_rdp_max_points_ffi`](fn.simplify_rdp_max_points_ffi.html) with `max_bytes / 16`
+/// points: the smallest tolerance which fits the budget is used. Input which already fits is
+/// returned unchanged. A budget too small to hold the line's endpoints produces an empty array.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_byte_budget_ffi(
+    coords: ExternalArray,
+    max_bytes: libc::size_t,
+) -> InternalArray {
+    let max_points = max_bytes / mem::size_of::<[f64; 2]>();
+    checked(coords, |coords| {
+        if coords.len() > max_points && max_points < 2 {
+            return InternalArray::empty();
+        }
+        api::simplify_rdp_max_points(coords, max_points).into()
+    })
+    .array
+}
+
+/// FFI wrapper for RDP, returning at least a given number of simplified geometry **coordinates**
+///
+/// Callers must pass three arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+/// - a `size_t` for the minimum number of points to return
+///
+/// If the tolerance alone would retain fewer points than the minimum, the removed points which are
+/// farthest from the simplified line are restored until the minimum is met. Input which has no more
+/// points than the minimum is returned unchanged.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_floor_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+    min_points: libc::size_t,
+) -> InternalArray {
+    checked(coords, |coords| {
+        api::simplify_rdp_floor(coords, precision, min_points).into()
+    })
+    .array
+}
+
+/// FFI wrapper for direction-independent RDP, returning simplified geometry **coordinates**
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+///
+/// Simplifying the reversed input produces the reversed output, so edges shared between adjacent
+/// polygons remain identical after simplification. Results can differ from those of
+/// [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html) when points are equally distant from a segment.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_symmetric_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+) -> InternalArray {
+    checked(coords, |coords| {
+        api::simplify_rdp_symmetric(coords, precision).into()
+    })
+    .array
+}
+
+/// FFI wrapper for direction-independent RDP, returning simplified geometry **coordinates**, optionally
+/// in reverse order
+///
+/// Callers must pass three arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+/// - an `int` flag: if it's non-zero, the coordinates are returned last-to-first
+///
+/// The input is simplified as by [`simplify_rdp_symmetric_ffi`](fn.simplify_rdp_symmetric_ffi.html),
+/// and the result is reversed in place, so callers requiring the opposite orientation don't have to
+/// reverse a copy. The reversed result is exactly the reverse of the forward result, and as the
+/// simplification doesn't depend on direction, it's also identical to the result of simplifying
+/// the reversed input.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_reverse_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+    reverse: libc::c_int,
+) -> InternalArray {
+    checked(coords, |coords| {
+        let mut simplified = api::simplify_rdp_symmetric(coords, precision);
+        if reverse != 0 {
+            simplified.reverse();
+        }
+        simplified.into()
+    })
+    .array
+}
+
+/// FFI wrapper for radial-distance thinning followed by RDP, returning simplified geometry **coordinates**
+///
+/// Callers must pass three arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the radial tolerance
+/// - a double-precision `float` for the RDP tolerance
+///
+/// Points within the radial tolerance of the previously retained point are removed first, and the
+/// remaining points are then simplified using RDP. This removes clusters of near-duplicate points,
+/// which are common in noisy GPS tracks, and which RDP would otherwise retain.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_radial_ffi(
+    coords: ExternalArray,
+    radial_tol: libc::c_double,
+    rdp_tol: libc::c_double,
+) -> InternalArray {
+    checked(coords, |coords| {
+        api::simplify_rdp_radial(coords, radial_tol, rdp_tol).into()
+    })
+    .array
+}
+
+/// FFI wrapper for RDP after snapping to a grid, returning simplified geometry **coordinates**
+///
+/// Callers must pass three arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+/// - a double-precision `float` for the size of the grid's cells
+///
+/// Each coordinate is rounded to the nearest multiple of the grid size, consecutive duplicates are
+/// removed, and the result is simplified as by [`simplify_rdp_symmetric_ffi`](fn.simplify_rdp_symmetric_ffi.html).
+/// Boundaries shared by the features of a coverage snap to identical coordinates, so they remain
+/// coincident after simplification, even if features traverse them in opposite directions.
+/// See [`api::simplify_rdp_snapped`](api/fn.simplify_rdp_snapped.html). A grid size which isn't a
+/// finite number greater than zero disables snapping.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_snap_grid_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+    grid_size: libc::c_double,
+) -> InternalArray {
+    checked(coords, |coords| {
+        api::simplify_rdp_snapped(coords, precision, grid_size).into()
+    })
+    .array
+}
+
+/// Remove consecutive duplicate points from a line, returning the remaining **coordinates**
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the distance within which points are duplicates
+///
+/// A point is removed if it's within the given distance of the last point which wasn't, so input
+/// whose points are all duplicates produces a single point, and a distance of 0 removes only exact
+/// duplicates. This is intended for cleaning lines before simplification: runs of duplicate points
+/// are common after snapping, and add work without adding detail.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn dedupe_coords_ffi(
+    coords: ExternalArray,
+    epsilon: libc::c_double,
+) -> InternalArray {
+    checked(coords, |coords| api::dedupe_coords(coords, epsilon).into()).array
+}
+
+/// FFI wrapper for RDP followed by Chaikin smoothing, returning smoothed geometry **coordinates**
+///
+/// Callers must pass three arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+/// - a `size_t` for the number of iterations of smoothing
+///
+/// The line is simplified as by [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html), then each
+/// iteration of smoothing replaces every corner with two points, roughly doubling the number of
+/// points, so only a few iterations are normally needed. The first and last points of an open line
+/// are unchanged, but a closed line is smoothed as a ring. Zero iterations are equivalent to
+/// `simplify_rdp_ffi`.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_then_smooth_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+    iterations: libc::size_t,
+) -> InternalArray {
+    checked(coords, |coords| {
+        api::simplify_then_smooth(coords, precision, iterations).into()
+    })
+    .array
+}
+
+/// FFI wrapper for RDP with per-axis scaling, returning simplified geometry **coordinates**
+///
+/// Callers must pass four arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+/// - a double-precision `float` by which x coordinates are multiplied
+/// - a double-precision `float` by which y coordinates are multiplied
+///
+/// Distances are measured after scaling, so the tolerance is in the scaled units. The retained
+/// coordinates are copied from the input rather than unscaled, so the returned endpoints are
+/// bit-identical to the input's, and can be used as join keys. For longitude and latitude in degrees, passing the
+/// cosine of the line's latitude as the x scale accounts for degrees of longitude shrinking away
+/// from the equator.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_scaled_ffi(
+    coords: ExternalArray,
+    tolerance: libc::c_double,
+    x_scale: libc::c_double,
+    y_scale: libc::c_double,
+) -> InternalArray {
+    checked(coords, |coords| {
+        api::simplify_rdp_scaled(coords, tolerance, x_scale, y_scale).into()
+    })
+    .array
+}
+
+/// FFI wrapper for RDP, retaining vertices at tile boundaries, and returning simplified geometry **coordinates**
+///
+/// Callers must pass three arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of double-precision `float` x coordinates of vertical
+///       boundaries, such as tile edges
+///     - `len`, the number of boundaries. Its type must be `size_t`
+///
+/// Wherever the line crosses a boundary, the vertex nearest to it is retained, so lines which
+/// continue into an adjacent tile share a vertex at the tile edge, and renderers don't produce
+/// cracks there. The line is simplified between the retained vertices in the same way as
+/// [`simplify_rdp_symmetric_ffi`](fn.simplify_rdp_symmetric_ffi.html). If the boundary array is
+/// empty or a null pointer, no vertices are retained in this way.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses raw pointers which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_boundaries_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+    boundaries: ExternalArray,
+) -> InternalArray {
+    let boundaries: Vec<f64> = boundaries
+        .validate::<f64, 1>()
+        .map(|valid| valid.iter().map(|[x]| *x).collect())
+        .unwrap_or_default();
+    checked(coords, |coords| {
+        api::simplify_rdp_boundaries(coords, precision, &boundaries).into()
+    })
+    .array
+}
+
+/// FFI wrapper for RDP with a tolerance for each vertex, returning simplified geometry **coordinates**
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of double-precision `float` tolerances, one per point
+///     - `len`, the number of tolerances, which must equal the number of points
+///
+/// `tolerances[i]` is the distance by which point `i` (the start of the line's `i`th segment) may
+/// deviate from the simplified line, so regions with high tolerances (such as sparse regions) are
+/// simplified aggressively, while regions with low tolerances keep their detail. Each segment is
+/// split at the farthest of the points which deviate from it by more than their tolerance, so
+/// equal tolerances produce the same result as [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html). A
+/// point whose tolerance isn't greater than zero is always retained, and the endpoints' tolerances
+/// are ignored, as they are always retained.
+///
+/// If either input is invalid, or the number of tolerances doesn't equal the number of points, an
+/// empty array is returned.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses raw pointers which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_vartol_ffi(
+    coords: ExternalArray,
+    tolerances: ExternalArray,
+) -> InternalArray {
+    match (coords.validate::<f64, 2>(), tolerances.validate::<f64, 1>()) {
+        (Ok(points), Ok(tolerances)) if points.len() == tolerances.len() => {
+            guarded(InternalArray::empty(), || {
+                let tolerances: Vec<f64> = tolerances.iter().map(|[t]| *t).collect();
+                api::simplify_rdp_variable(points, &tolerances).into()
+            })
+        }
+        _ => InternalArray::empty(),
+    }
+}
+
+/// FFI wrapper for RDP, retaining the extreme vertices, and returning simplified geometry **coordinates**
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+///
+/// The vertices with the smallest and largest x and y coordinates are always retained, so charts
+/// and sparklines keep their highest and lowest points, even where
+/// [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html) would remove them. These are four vertices at
+/// most, and fewer if one vertex has more than one extreme coordinate. If several vertices share an
+/// extreme coordinate, the first is retained. The line is simplified between the retained vertices
+/// in the same way as [`simplify_rdp_symmetric_ffi`](fn.simplify_rdp_symmetric_ffi.html).
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_extrema_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+) -> InternalArray {
+    checked(coords, |coords| {
+        api::simplify_rdp_extrema(coords, precision).into()
+    })
+    .array
+}
+
+/// FFI wrapper for RDP, retaining sharp corners, and returning simplified geometry **coordinates**
+///
+/// Callers must pass three arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+/// - a double-precision `float` for the minimum angle, in degrees
+///
+/// Each vertex's angle is computed from the two input segments meeting at it: it's 180 for a vertex
+/// on a straight line, and 90 for a right-angled corner. Vertices whose angle is less than the
+/// minimum are always retained, however aggressive the tolerance, so semantically important turns
+/// such as street corners survive. The line is simplified between the retained vertices in the same
+/// way as [`simplify_rdp_symmetric_ffi`](fn.simplify_rdp_symmetric_ffi.html). See
+/// [`api::simplify_rdp_corners`](api/fn.simplify_rdp_corners.html).
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_corners_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+    min_angle_deg: libc::c_double,
+) -> InternalArray {
+    checked(coords, |coords| {
+        api::simplify_rdp_corners(coords, precision, min_angle_deg).into()
+    })
+    .array
+}
+
+/// FFI wrapper for RDP, retaining vertices either side of long gaps in time, and returning
+/// simplified geometry **coordinates**
+///
+/// Callers must pass four arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of double-precision `float` times, in seconds since
+///       the epoch, one per point
+///     - `len`, the number of times, which must equal the number of points
+/// - a double-precision `float` for the tolerance
+/// - a double-precision `float` for the longest gap in seconds which may be simplified away
+///
+/// Wherever consecutive points are more than `max_gap_s` seconds apart, such as either side of a
+/// stop in a GPS track, both are retained, even if they're geometrically redundant. The line is
+/// simplified between the retained points in the same way as
+/// [`simplify_rdp_symmetric_ffi`](fn.simplify_rdp_symmetric_ffi.html).
+///
+/// If either input is invalid, or the number of times doesn't equal the number of points, an
+/// empty array is returned.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses raw pointers which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_timed_ffi(
+    coords: ExternalArray,
+    times: ExternalArray,
+    precision: libc::c_double,
+    max_gap_s: libc::c_double,
+) -> InternalArray {
+    match (coords.validate::<f64, 2>(), times.validate::<f64, 1>()) {
+        (Ok(points), Ok(times)) if points.len() == times.len() => {
+            guarded(InternalArray::empty(), || {
+                let times: Vec<f64> = times.iter().map(|[t]| *t).collect();
+                api::simplify_rdp_timed(points, &times, precision, max_gap_s).into()
+            })
+        }
+        _ => InternalArray::empty(),
+    }
+}
+
+/// FFI wrapper for RDP, always retaining marked points, and returning simplified geometry
+/// **coordinates**
+///
+/// Callers must pass three arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of `uint8_t` flags, one per point
+///     - `len`, the number of flags, which must equal the number of points
+/// - a double-precision `float` for the tolerance
+///
+/// Every point whose flag is non-zero, such as a junction or a labelled point, is retained. The
+/// line is simplified independently between the retained points, in the same way as
+/// [`simplify_rdp_symmetric_ffi`](fn.simplify_rdp_symmetric_ffi.html).
+///
+/// If either input is invalid, or the number of flags doesn't equal the number of points, an
+/// empty array is returned.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses raw pointers which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_pinned_ffi(
+    coords: ExternalArray,
+    pinned: ExternalArray,
+    precision: libc::c_double,
+) -> InternalArray {
+    match (coords.validate::<f64, 2>(), pinned.validate::<u8, 1>()) {
+        (Ok(points), Ok(flags)) if points.len() == flags.len() => {
+            guarded(InternalArray::empty(), || {
+                let flags: Vec<bool> = flags.iter().map(|&[flag]| flag != 0).collect();
+                api::simplify_rdp_pinned(points, &flags, precision).into()
+            })
+        }
+        (Ok(points), Ok(flags)) => {
+            set_last_error(format!(
+                "the input array has {} points, but {} flags were given",
+                points.len(),
+                flags.len()
+            ));
+            InternalArray::empty()
+        }
+        _ => InternalArray::empty(),
+    }
+}
+
+/// FFI wrapper for RDP, returning the number of points retained for each of several tolerances
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of double-precision `float` tolerances
+///     - `len`, the number of tolerances. Its type must be `size_t`
+///
+/// The result is an array of `size_t` point counts, one for each tolerance, in the same order. The
+/// tolerances needn't be sorted. Each count is the length of the array
+/// [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html) would return for that tolerance, but the
+/// geometry is only simplified once. If either input is invalid, an empty array is returned.
+///
+/// Implementations calling this function **must** call [`drop_usize_array`](fn.drop_usize_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses raw pointers which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_sweep_ffi(
+    coords: ExternalArray,
+    tolerances: ExternalArray,
+) -> InternalArray {
+    match (coords.validate::<f64, 2>(), tolerances.validate::<f64, 1>()) {
+        (Ok(coords), Ok(tolerances)) => guarded(InternalArray::empty(), || {
+            let tolerances: Vec<f64> = tolerances.iter().map(|[t]| *t).collect();
+            leak(api::simplify_rdp_sweep(coords, &tolerances))
+        }),
+        _ => InternalArray::empty(),
+    }
+}
+
+/// FFI wrapper for RDP, returning the significance of each interior point
+///
+/// Callers must pass a [Struct](struct.Array.html) with two fields:
+///
+/// - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+/// - `len`, the length of the array being passed. Its type must be `size_t`
+///
+/// The result is an array of `[index, significance]` pairs of double-precision `float`s, one for
+/// each point apart from the first and last, in order of decreasing significance. A point's
+/// significance is the tolerance below which [`simplify_rdp_idx_ffi`](fn.simplify_rdp_idx_ffi.html)
+/// retains it, so for any positive tolerance, the points retained are the endpoints and the
+/// indices of the leading pairs whose significance is greater than the tolerance. This lets
+/// interactive applications reveal or hide detail without simplifying again.
+/// See [`api::rdp_significance`](api/fn.rdp_significance.html). Indices are exact for lines with
+/// fewer than 2<sup>53</sup> points.
+///
+/// If the input is invalid, or has fewer than three points, an empty array is returned.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn rdp_significance_ffi(coords: ExternalArray) -> InternalArray {
+    match coords.validate::<f64, 2>() {
+        Ok(points) if points.len() >= 3 => guarded(InternalArray::empty(), || {
+            api::rdp_significance(points)
+                .into_iter()
+                .map(|(idx, significance)| [idx as f64, significance])
+                .collect::<Vec<_>>()
+                .into()
+        }),
+        _ => InternalArray::empty(),
+    }
+}
+
+/// FFI wrapper for RDP, returning simplified geometry **coordinates** at several levels of detail
+///
+/// Callers must pass three arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of double-precision `float` tolerances, one per level
+///     - `len`, the number of tolerances. Its type must be `size_t`
+/// - a pointer to an [`InternalArray`](struct.InternalArray.html), which receives the offsets
+///
+/// The returned `Array` contains the coordinates retained at every tolerance, concatenated in the
+/// order of the tolerances. The offsets array contains one more `size_t` value than there are
+/// tolerances: the coordinates retained at tolerance `i` are the elements from `offsets[i]` up to
+/// (but not including) `offsets[i + 1]`. Each level is identical to the result of
+/// [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html) with its tolerance, but the line is only traversed
+/// once, and each level is a subset of every level with a lower tolerance.
+///
+/// If either input is invalid, or `offsets_out` is a null pointer, an empty array is returned,
+/// and `offsets_out` (if it isn't null) receives an empty array.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array`, and [`drop_usize_array`](fn.drop_usize_array.html) with the offsets,
+/// in order to free the memory they allocate.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses raw pointers which could contain arbitrary data
+#[no_mangle]
+pub unsafe extern "C" fn simplify_rdp_lod_ffi(
+    coords: ExternalArray,
+    tolerances: ExternalArray,
+    offsets_out: *mut InternalArray,
+) -> InternalArray {
+    if offsets_out.is_null() {
+        return InternalArray::empty();
+    }
+    let levels = match (coords.validate::<f64, 2>(), tolerances.validate::<f64, 1>()) {
+        (Ok(coords), Ok(tolerances)) => guarded(None, || {
+            let tolerances: Vec<f64> = tolerances.iter().map(|[t]| *t).collect();
+            Some(api::simplify_rdp_lod(coords, &tolerances))
+        }),
+        _ => None,
+    };
+    let Some(levels) = levels else {
+        *offsets_out = InternalArray::empty();
+        return InternalArray::empty();
+    };
+    let mut offsets = Vec::with_capacity(levels.len() + 1);
+    offsets.push(0);
+    offsets.extend(levels.iter().scan(0, |end, level| {
+        *end += level.len();
+        Some(*end)
+    }));
+    *offsets_out = leak(offsets);
+    levels.concat().into()
+}
+
+/// FFI wrapper for RDP, writing simplified geometry **coordinates** into a caller-provided buffer
+///
+/// Callers must pass four arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+/// - a [Struct](struct.Array.html) describing the output buffer, with two fields:
+///     - `data`, a void pointer to writable memory for `len` coordinate pairs
+///     - `len`, the capacity of the buffer, in coordinate pairs
+/// - a pointer to a `size_t`, into which the number of retained points is written
+///
+/// The return value is an [`FfiError`](enum.FfiError.html) code. If the output buffer is too small,
+/// `BufferTooSmall` is returned, nothing is written to the buffer, and the required capacity is
+/// written to `out_len`, so callers can retry with a larger buffer. If the input is invalid,
+/// 0 is written to `out_len`.
+///
+/// No memory is allocated across the FFI boundary, so there is nothing to drop.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses raw pointers which could contain arbitrary data
+#[no_mangle]
+pub unsafe extern "C" fn simplify_rdp_into_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+    out: InternalArray,
+    out_len: *mut libc::size_t,
+) -> libc::c_int {
+    if out.data.is_null() || out_len.is_null() {
+        return FfiError::NullPointer as libc::c_int;
+    }
+    let simplified = match coords.validate::<f64, 2>().and_then(|valid| {
+        guarded(Err(FfiError::Panic), || {
+            Ok(api::simplify_rdp(valid, precision))
+        })
+    }) {
+        Ok(simplified) => simplified,
+        Err(error) => {
+            *out_len = 0;
+            return error as libc::c_int;
+        }
+    };
+    *out_len = simplified.len();
+    if simplified.len() > out.len {
+        return FfiError::BufferTooSmall as libc::c_int;
+    }
+    ptr::copy_nonoverlapping(
+        simplified.as_ptr(),
+        out.data as *mut [f64; 2],
+        simplified.len(),
+    );
+    FfiError::Success as libc::c_int
+}
+
+/// FFI wrapper for RDP, serialising simplified geometry **coordinates** into a caller-provided
+/// byte buffer
+///
+/// Callers must pass five arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+/// - a pointer to writable memory, such as a memory-mapped file or shared memory. It needn't be
+///   aligned
+/// - a `size_t` for the capacity of the buffer, in bytes
+/// - a pointer to a `size_t`, into which the number of bytes written is written
+///
+/// The result is written in little-endian byte order, whatever the platform's:
+///
+/// | Offset       | Size | Type  | Value                        |
+/// |--------------|------|-------|------------------------------|
+/// | 0            | 4    | `u32` | the number of points, `n`    |
+/// | 4 + 16i      | 8    | `f64` | the x coordinate of point i  |
+/// | 4 + 16i + 8  | 8    | `f64` | the y coordinate of point i  |
+///
+/// so the result occupies `4 + 16n` bytes. The return value is an [`FfiError`](enum.FfiError.html)
+/// code. If the buffer is too small, `BufferTooSmall` is returned, nothing is written to the
+/// buffer, and the required capacity is written to `written`, so callers can retry with a larger
+/// buffer. If more points are retained than a `u32` can count, `IndexOverflow` is returned. If the
+/// input is invalid, 0 is written to `written`.
+///
+/// No memory is allocated across the FFI boundary, so there is nothing to drop.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses raw pointers which could contain arbitrary data
+#[no_mangle]
+pub unsafe extern "C" fn simplify_rdp_write_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+    out_buf: *mut u8,
+    out_cap: libc::size_t,
+    written: *mut libc::size_t,
+) -> libc::c_int {
+    if out_buf.is_null() || written.is_null() {
+        return FfiError::NullPointer as libc::c_int;
+    }
+    let simplified = match coords.validate::<f64, 2>().and_then(|valid| {
+        guarded(Err(FfiError::Panic), || {
+            Ok(api::simplify_rdp(valid, precision))
+        })
+    }) {
+        Ok(simplified) => simplified,
+        Err(error) => {
+            *written = 0;
+            return error as libc::c_int;
+        }
+    };
+    let Ok(count) = u32::try_from(simplified.len()) else {
+        *written = 0;
+        return FfiError::IndexOverflow as libc::c_int;
+    };
+    let bytes: Vec<u8> = count
+        .to_le_bytes()
+        .into_iter()
+        .chain(simplified.iter().flatten().flat_map(|c| c.to_le_bytes()))
+        .collect();
+    *written = bytes.len();
+    if bytes.len() > out_cap {
+        return FfiError::BufferTooSmall as libc::c_int;
+    }
+    ptr::copy_nonoverlapping(bytes.as_ptr(), out_buf, bytes.len());
+    FfiError::Success as libc::c_int
+}
+
+/// FFI wrapper for Visvalingam-Whyatt, returning simplified geometry **coordinates**
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the epsilon
+///
+/// The epsilon is an area: points are removed until every remaining point forms a triangle with its
+/// neighbours whose area is greater than the epsilon.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_visvalingam_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+) -> InternalArray {
+    simplify_ffi(coords, precision, Algorithm::Visvalingam as libc::c_int)
+}
+
+/// FFI wrapper for Visvalingam-Whyatt with an epsilon relative to the line's length, returning
+/// simplified geometry **coordinates**
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the epsilon, as a fraction of the square of the line's length
+///
+/// The line is simplified as by [`simplify_visvalingam_ffi`](fn.simplify_visvalingam_ffi.html), with
+/// an epsilon of `area_fraction` multiplied by the square of the line's total length. Areas scale
+/// with the square of a line's size, so scaling a line doesn't change which of its points are
+/// retained.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_vw_relative_ffi(
+    coords: ExternalArray,
+    area_fraction: libc::c_double,
+) -> InternalArray {
+    checked(coords, |coords| {
+        api::simplify_vw_relative(coords, area_fraction).into()
+    })
+    .array
+}
+
+/// FFI wrapper for Visvalingam-Whyatt, returning simplified geometry **indices**
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the epsilon
+///
+/// Implementations calling this function **must** call [`drop_usize_array`](fn.drop_usize_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_visvalingam_idx_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+) -> InternalArray {
+    simplify_visvalingam_idx_checked_ffi(coords, precision).array
+}
+
+/// FFI wrapper for topology-preserving Visvalingam-Whyatt, returning simplified geometry **coordinates**.
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the epsilon
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_visvalingamp_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+) -> InternalArray {
+    simplify_ffi(
+        coords,
+        precision,
+        Algorithm::VisvalingamPreserve as libc::c_int,
+    )
+}
+
+/// FFI wrapper for topology-preserving Visvalingam-Whyatt, returning simplified geometry **indices**.
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the epsilon
+///
+/// Whenever removing a point would make the simplified line cross itself, the point preceding it
+/// is removed too, which usually removes the crossing. The result may therefore differ from that
+/// of [`simplify_visvalingam_idx_ffi`](fn.simplify_visvalingam_idx_ffi.html), and may have more
+/// points. This is a heuristic rather than a guarantee: the first point is never removed, so a
+/// crossing introduced by removing the second point remains.
+///
+/// Implementations calling this function **must** call [`drop_usize_array`](fn.drop_usize_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_visvalingamp_idx_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+) -> InternalArray {
+    simplify_visvalingamp_idx_checked_ffi(coords, precision).array
+}
+
+/// FFI wrapper for RDP, returning simplified geometry **coordinates** and an error code
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+///
+/// The input is validated before simplification: if `data` is a null pointer, `len` is 0, or any
+/// coordinate is NaN or infinite, the returned [`FfiResult`](struct.FfiResult.html) contains an empty
+/// array and a non-zero `error`.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `array` field, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_checked_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+) -> FfiResult {
+    checked(coords, |coords| api::simplify_rdp(coords, precision).into())
+}
+
+/// FFI wrapper for RDP, returning simplified geometry **indices** and an error code
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+///
+/// The input is validated before simplification: if `data` is a null pointer, `len` is 0, or any
+/// coordinate is NaN or infinite, the returned [`FfiResult`](struct.FfiResult.html) contains an empty
+/// array and a non-zero `error`.
+///
+/// Implementations calling this function **must** call [`drop_usize_array`](fn.drop_usize_array.html)
+/// with the returned `array` field, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_idx_checked_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+) -> FfiResult {
+    checked(coords, |coords| {
+        api::simplify_rdp_idx(coords, precision).into()
+    })
+}
+
+/// FFI wrapper for Visvalingam-Whyatt, returning simplified geometry **coordinates** and an error code
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the epsilon
+///
+/// The input is validated before simplification: if `data` is a null pointer, `len` is 0, or any
+/// coordinate is NaN or infinite, the returned [`FfiResult`](struct.FfiResult.html) contains an empty
+/// array and a non-zero `error`.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `array` field, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_visvalingam_checked_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+) -> FfiResult {
+    checked(coords, |coords| api::simplify_vw(coords, precision).into())
+}
+
+/// FFI wrapper for Visvalingam-Whyatt, returning simplified geometry **indices** and an error code
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the epsilon
+///
+/// The input is validated before simplification: if `data` is a null pointer, `len` is 0, or any
+/// coordinate is NaN or infinite, the returned [`FfiResult`](struct.FfiResult.html) contains an empty
+/// array and a non-zero `error`.
+///
+/// Implementations calling this function **must** call [`drop_usize_array`](fn.drop_usize_array.html)
+/// with the returned `array` field, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_visvalingam_idx_checked_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+) -> FfiResult {
+    checked(coords, |coords| {
+        api::simplify_vw_idx(coords, precision).into()
+    })
+}
+
+/// FFI wrapper for topology-preserving Visvalingam-Whyatt, returning simplified geometry **coordinates** and an error code
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the epsilon
+///
+/// The input is validated before simplification: if `data` is a null pointer, `len` is 0, or any
+/// coordinate is NaN or infinite, the returned [`FfiResult`](struct.FfiResult.html) contains an empty
+/// array and a non-zero `error`.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `array` field, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_visvalingamp_checked_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+) -> FfiResult {
+    checked(coords, |coords| {
+        api::simplify_vw_preserve(coords, precision).into()
+    })
+}
+
+/// FFI wrapper for topology-preserving Visvalingam-Whyatt, returning simplified geometry **indices** and an error code
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the epsilon
+///
+/// The input is validated before simplification: if `data` is a null pointer, `len` is 0, or any
+/// coordinate is NaN or infinite, the returned [`FfiResult`](struct.FfiResult.html) contains an empty
+/// array and a non-zero `error`.
+///
+/// Implementations calling this function **must** call [`drop_usize_array`](fn.drop_usize_array.html)
+/// with the returned `array` field, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_visvalingamp_idx_checked_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+) -> FfiResult {
+    checked(coords, |coords| {
+        api::simplify_vw_preserve_idx(coords, precision).into()
+    })
+}
+
+/// FFI wrapper for RDP, returning simplified 3D geometry **coordinates**
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point 3D point coordinates: `[[1.0, 2.0, 3.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+///
+/// Distances are computed using the x and y coordinates only; the z coordinate of each retained
+/// point is carried through to the output unchanged. Use
+/// [`simplify_rdp_3d_z_ffi`](fn.simplify_rdp_3d_z_ffi.html) if z should be considered.
+///
+/// Implementations calling this function **must** call [`drop_float_array_3d`](fn.drop_float_array_3d.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_3d_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+) -> InternalArray {
+    simplify_rdp_3d(coords, precision, |p, a, b| {
+        douglas_peucker::segment_distance([p[0], p[1]], [a[0], a[1]], [b[0], b[1]])
+    })
+}
+
+/// FFI wrapper for RDP, returning simplified 3D geometry **coordinates**, taking z into account
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point 3D point coordinates: `[[1.0, 2.0, 3.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+///
+/// Unlike [`simplify_rdp_3d_ffi`](fn.simplify_rdp_3d_ffi.html), distances are computed in three
+/// dimensions, so points which deviate only in z can be retained.
+///
+/// Implementations calling this function **must** call [`drop_float_array_3d`](fn.drop_float_array_3d.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_3d_z_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+) -> InternalArray {
+    simplify_rdp_3d(coords, precision, douglas_peucker::segment_distance_3d)
+}
+
+// Validate and simplify 3D coordinates using the supplied point-to-segment distance
+fn simplify_rdp_3d<F>(coords: ExternalArray, precision: f64, distance: F) -> InternalArray
+where
+    F: Fn([f64; 3], [f64; 3], [f64; 3]) -> f64,
+{
+    match coords.validate::<f64, 3>() {
+        Ok(points) => guarded(InternalArray::empty(), || {
+            douglas_peucker::rdp_indices(points.len(), precision, |start, end, point| {
+                distance(points[point], points[start], points[end])
+            })
+            .into_iter()
+            .map(|idx| points[idx])
+            .collect::<Vec<_>>()
+            .into()
+        }),
+        Err(_) => InternalArray::empty(),
+    }
+}
+
+/// A function receiving retained points, for use with
+/// [`simplify_rdp_foreach_ffi`](fn.simplify_rdp_foreach_ffi.html)
+///
+/// It receives the coordinates of a retained point, followed by the caller's `user` pointer.
+pub type PointCallback =
+    Option<extern "C" fn(x: libc::c_double, y: libc::c_double, user: *mut libc::c_void)>;
+
+/// FFI wrapper for RDP, passing each retained point to a callback
+///
+/// Callers must pass four arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+/// - a [`PointCallback`](type.PointCallback.html), which is called once for each retained point, in order
+/// - a void pointer, which is passed to each call of the callback unchanged. It may be null
+///
+/// If the input is invalid or the callback is a null pointer, the callback isn't called.
+/// No memory is allocated across the FFI boundary, so there is nothing to drop.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_foreach_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+    sink: PointCallback,
+    user: *mut libc::c_void,
+) {
+    let (Some(sink), Ok(points)) = (sink, coords.validate::<f64, 2>()) else {
+        return;
+    };
+    for idx in guarded(vec![], || api::simplify_rdp_idx(points, precision)) {
+        let [x, y] = points[idx];
+        sink(x, y, user);
+    }
+}
+
+/// Compute the maximum deviation of a simplified geometry from the original
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) containing the original coordinates, with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a [Struct](struct.Array.html) containing the simplified coordinates, in the same format
+///
+/// The result is the maximum distance of any original point from the simplified line. NaN is
+/// returned if either array is invalid or empty, or if the simplified array is longer than the
+/// original.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn max_deviation_ffi(
+    original: ExternalArray,
+    simplified: ExternalArray,
+) -> libc::c_double {
+    match (original.validate(), simplified.validate()) {
+        (Ok(original), Ok(simplified)) => guarded(f64::NAN, || {
+            api::max_deviation(original, simplified).unwrap_or(f64::NAN)
+        }),
+        _ => f64::NAN,
+    }
+}
+
+/// Check whether a simplified geometry is within a tolerance of the original
+///
+/// Callers must pass three arguments:
+///
+/// - a [Struct](struct.Array.html) containing the original coordinates, with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a [Struct](struct.Array.html) containing the simplified coordinates, in the same format
+/// - a double-precision `float` for the tolerance
+///
+/// 1 is returned if every original point is within the tolerance of the simplified line, and 0
+/// otherwise. Checking stops at the first point which is too far away, so this is cheaper than
+/// comparing the result of [`max_deviation_ffi`](fn.max_deviation_ffi.html) with the tolerance.
+/// 0 is returned in the cases in which `max_deviation_ffi` returns NaN.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses raw pointers which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn within_tolerance_ffi(
+    original: ExternalArray,
+    simplified: ExternalArray,
+    tolerance: libc::c_double,
+) -> libc::c_int {
+    match (original.validate(), simplified.validate()) {
+        (Ok(original), Ok(simplified)) => guarded(0, || {
+            api::within_tolerance(original, simplified, tolerance).into()
+        }),
+        _ => 0,
+    }
+}
+
+/// Check whether a geometry is already simplified at a tolerance
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+///
+/// 1 is returned if [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html) would return every point
+/// unchanged, and 0 otherwise. Checking stops at the first point which would be removed, so
+/// callers can cheaply skip redundant simplification. If the input is invalid, 0 is returned.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn is_simplified_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+) -> libc::c_int {
+    match coords.validate() {
+        Ok(points) => guarded(0, || api::is_simplified(points, precision).into()),
+        Err(_) => 0,
+    }
+}
+
+/// Compute the symmetric Hausdorff distance between two geometries
+///
+/// Callers must pass two [Structs](struct.Array.html), each with two fields:
+///
+/// - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+/// - `len`, the length of the array being passed. Its type must be `size_t`
+///
+/// The result is the greater of the maximum distance of any vertex of either geometry from the
+/// other geometry's segments. Unlike [`max_deviation_ffi`](fn.max_deviation_ffi.html), it is
+/// symmetric, so it also accounts for simplified segments which stray from the original geometry.
+/// NaN is returned if either array is invalid or empty.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses raw pointers which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn hausdorff_distance_ffi(a: ExternalArray, b: ExternalArray) -> libc::c_double {
+    match (a.validate(), b.validate()) {
+        (Ok(a), Ok(b)) => guarded(f64::NAN, || {
+            api::hausdorff_distance(a, b).unwrap_or(f64::NAN)
+        }),
+        _ => f64::NAN,
+    }
+}
+
+/// A point-to-segment distance function, for use with
+/// [`simplify_rdp_custom_ffi`](fn.simplify_rdp_custom_ffi.html)
+///
+/// It receives the coordinates of the segment's start (`ax`, `ay`) and end (`bx`, `by`), followed
+/// by those of the point (`px`, `py`), and must return the point's distance from the segment.
+pub type DistanceCallback = Option<
+    extern "C" fn(
+        ax: libc::c_double,
+        ay: libc::c_double,
+        bx: libc::c_double,
+        by: libc::c_double,
+        px: libc::c_double,
+        py: libc::c_double,
+    ) -> libc::c_double,
+>;
+
+/// FFI wrapper for RDP using a caller-supplied distance metric, returning simplified geometry **coordinates**
+///
+/// Callers must pass three arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+/// - a [`DistanceCallback`](type.DistanceCallback.html), which is called to compute the distance of
+///   each candidate point from the segment under consideration, in place of the Euclidean distance
+///
+/// A point is retained if its distance is greater than the tolerance. If the callback is a null
+/// pointer, an empty array is returned.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_custom_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+    metric: DistanceCallback,
+) -> InternalArray {
+    let Some(metric) = metric else {
+        return InternalArray::empty();
+    };
+    checked(coords, |points: &[[f64; 2]]| {
+        douglas_peucker::rdp_indices(points.len(), precision, |start, end, point| {
+            let ([ax, ay], [bx, by], [px, py]) = (points[start], points[end], points[point]);
+            metric(ax, ay, bx, by, px, py)
+        })
+        .into_iter()
+        .map(|idx| points[idx])
+        .collect::<Vec<_>>()
+        .into()
+    })
+    .array
+}
+
+/// FFI wrapper for RDP, returning simplified single-precision geometry **coordinates**
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of single-precision point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a single-precision `float` for the tolerance
+///
+/// Implementations calling this function **must** call [`drop_float_array_f32`](fn.drop_float_array_f32.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+/// [`drop_float_array`](fn.drop_float_array.html) **must not** be used for this purpose.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_ffi_f32(
+    coords: ExternalArray,
+    precision: libc::c_float,
+) -> InternalArray {
+    simplify_generic(coords, |ls: &LineString<f32>| ls.simplify(&precision))
+}
+
+/// FFI wrapper for Visvalingam-Whyatt, returning simplified single-precision geometry **coordinates**
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of single-precision point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a single-precision `float` for the epsilon
+///
+/// Implementations calling this function **must** call [`drop_float_array_f32`](fn.drop_float_array_f32.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+/// [`drop_float_array`](fn.drop_float_array.html) **must not** be used for this purpose.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_visvalingam_ffi_f32(
+    coords: ExternalArray,
+    precision: libc::c_float,
+) -> InternalArray {
+    simplify_generic(coords, |ls: &LineString<f32>| ls.simplify_vw(&precision))
+}
+
+/// FFI wrapper for topology-preserving Visvalingam-Whyatt, returning simplified single-precision geometry **coordinates**
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of single-precision point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a single-precision `float` for the epsilon
+///
+/// Implementations calling this function **must** call [`drop_float_array_f32`](fn.drop_float_array_f32.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+/// [`drop_float_array`](fn.drop_float_array.html) **must not** be used for this purpose.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_visvalingamp_ffi_f32(
+    coords: ExternalArray,
+    precision: libc::c_float,
+) -> InternalArray {
+    simplify_generic(coords, |ls: &LineString<f32>| {
+        ls.simplify_vw_preserve(&precision)
+    })
+}
+
+/// FFI wrapper for RDP, returning simplified integer geometry **coordinates**
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of 32-bit signed integer point coordinates: `[[1, 2], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+///
+/// This suits integer coordinate spaces, such as vector tiles. Distances are computed using
+/// double-precision `float`s, which represent every 32-bit integer exactly, and the returned
+/// coordinates are copied from the input, so they are never rounded. If `data` is a null pointer or
+/// `len` is 0, an empty array is returned.
+///
+/// Implementations calling this function **must** call [`drop_i32_array`](fn.drop_i32_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+/// [`drop_float_array`](fn.drop_float_array.html) **must not** be used for this purpose.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_i32_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+) -> InternalArray {
+    match coords.validate::<i32, 2>() {
+        Ok(points) => guarded(InternalArray::empty(), || {
+            leak(api::simplify_rdp_i32(points, precision))
+        }),
+        Err(_) => InternalArray::empty(),
+    }
+}
+
+/// Free memory which has been allocated across the FFI boundary by:
+/// - simplify_ffi
+/// - simplify_rdp_ffi
+/// - simplify_rdp_cancellable_ffi
+/// - simplify_rdp_series_ffi
+/// - simplify_rdp_range_ffi
+/// - merge_simplify_rdp_ffi
+/// - simplify_rdp_max_segment_ffi
+/// - simplify_rdp_density_ffi
+/// - simplify_rdp_stats_ffi
+/// - simplify_rdp_bbox_ffi
+/// - simplify_rdp_hull_ffi
+/// - simplify_rdp_length_ffi
+/// - simplify_rdp_bounded_ffi
+/// - simplify_rdp_both_ffi (the coordinates)
+/// - simplify_rdp_clean_ffi
+/// - simplify_rdp_relative_ffi
+/// - simplify_rdp_max_points_ffi
+/// - simplify_rdp_target_ratio_ffi
+/// - simplify_best_ffi
+/// - simplify_rdp_byte_budget_ffi
+/// - simplify_rdp_floor_ffi
+/// - simplify_rdp_symmetric_ffi
+/// - simplify_rdp_reverse_ffi
+/// - simplify_rdp_radial_ffi
+/// - simplify_snap_grid_ffi
+/// - dedupe_coords_ffi
+/// - simplify_then_smooth_ffi
+/// - simplify_rdp_scaled_ffi
+/// - simplify_rdp_boundaries_ffi
+/// - simplify_rdp_vartol_ffi
+/// - simplify_rdp_extrema_ffi
+/// - simplify_rdp_corners_ffi
+/// - simplify_rdp_timed_ffi
+/// - simplify_rdp_pinned_ffi
+/// - rdp_significance_ffi
+/// - simplify_rdp_lod_ffi (the coordinates)
+/// - simplify_visvalingam_ffi
+/// - simplify_vw_relative_ffi
+/// - simplify_visvalingamp_ffi
+/// - simplify_rdp_checked_ffi (the `array` field)
+/// - simplify_visvalingam_checked_ffi (the `array` field)
+/// - simplify_visvalingamp_checked_ffi (the `array` field)
+/// - simplify_rdp_custom_ffi
+/// - simplify_rdp_default_ffi
+/// - simplify_rdp_geodesic_ffi
+/// - simplify_rdp_ring_ffi
+/// - simplify_ring_oriented_ffi
+/// - simplify_ring_area_delta_ffi
+/// - rdp_stream_finish
+/// - simplify_vw_weighted_ffi
+/// - simplify_visvalingam_tiebreak_ffi
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn drop_float_array(arr: InternalArray) {
+    unsafe { free::<[f64; 2]>(arr) }
+}
+
+/// Free memory which has been allocated across the FFI boundary by:
+/// - simplify_rdp_ffi_f32
+/// - simplify_visvalingam_ffi_f32
+/// - simplify_visvalingamp_ffi_f32
+///
+/// This function **must not** be used to free arrays returned by the double-precision
+/// functions: use [`drop_float_array`](fn.drop_float_array.html) for those.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn drop_float_array_f32(arr: InternalArray) {
+    unsafe { free::<[f32; 2]>(arr) }
+}
+
+/// Free memory which has been allocated across the FFI boundary by:
+/// - simplify_rdp_3d_ffi
+/// - simplify_rdp_3d_z_ffi
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn drop_float_array_3d(arr: InternalArray) {
+    unsafe { free::<[f64; 3]>(arr) }
+}
+
+/// Free memory which has been allocated across the FFI boundary by:
+/// - simplify_rdp_i32_ffi
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn drop_i32_array(arr: InternalArray) {
+    unsafe { free::<[i32; 2]>(arr) }
+}
+
+/// Free memory which has been allocated across the FFI boundary by:
+/// - simplify_rdp_flat_ffi
+/// - simplify_rdp_xy_ffi (both the returned array and the y coordinates)
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn drop_flat_f64_array(arr: InternalArray) {
+    unsafe { free::<f64>(arr) }
+}
+
+/// Free memory which has been allocated across the FFI boundary by:
+/// - simplify_rdp_idx_ffi
+/// - simplify_visvalingam_idx_ffi
+/// - simplify_visvalingamp_idx_ffi
+/// - simplify_rdp_idx_packed_ffi (both the returned array and the offsets)
+/// - simplify_rdp_sweep_ffi
+/// - simplify_rdp_removed_idx_ffi
+/// - simplify_rdp_segment_stats_ffi
+/// - simplify_rdp_both_ffi (the indices)
+/// - simplify_rdp_lod_ffi (the offsets)
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn drop_usize_array(arr: InternalArray) {
+    unsafe { free::<usize>(arr) }
+}
+
+/// Free memory which has been allocated across the FFI boundary by:
+/// - simplify_rdp_idx_u32_ffi
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn drop_u32_array(arr: InternalArray) {
+    unsafe { free::<u32>(arr) }
+}
+
+/// Free memory which has been allocated across the FFI boundary by:
+/// - simplify_rdp_mask_ffi
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn drop_u8_array(arr: InternalArray) {
+    unsafe { free::<u8>(arr) }
+}
+
+/// Free memory which has been allocated across the FFI boundary by:
+/// - simplify_vw_areas_ffi
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn drop_double_array(arr: InternalArray) {
+    unsafe { free::<f64>(arr) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simplify_rdp_geodesic_ffi;
+
+    use geo::{LineString, Point};
+
+    use std::ptr;
+    #[test]
+    fn test_linestring_to_array() {
+        let ls: LineString<_> = vec![Point::new(1.0, 2.0), Point::new(3.0, 4.0)].into();
+        let _: InternalArray = ls.into();
+    }
+    #[test]
+    fn test_array_conversion() {
+        let original = vec![
+            [0.0, 0.0],
+            [5.0, 4.0],
+            [11.0, 5.5],
+            [17.3, 3.2],
+            [27.8, 0.1],
+        ];
+        let ls: LineString<_> = original.clone().into();
+        // move into an Array, and leak it
+        let arr: InternalArray = ls.into();
+        // move back into a Vec -- leaked value still needs to be dropped
+        let converted: LineString<_> = arr.into();
+        assert_eq!(converted, original.into());
+        // drop it
+        drop_float_array(converted.into());
+    }
+    #[test]
+    fn test_linestring_ownership() {
+        let original: LineString<f64> = vec![[0.0, 0.0], [5.0, 4.0], [27.8, 0.1]].into();
+        // ownership moves into the array, and back out again: the LineString frees it when dropped
+        let arr = linestring_to_internal_array(original.clone());
+        assert_eq!(arr.len, 3);
+        let converted = internal_array_to_linestring(arr);
+        assert_eq!(converted, original);
+        // results of the FFI functions can be taken over in the same way
+        let simplified = internal_array_to_linestring(simplify_rdp_ffi(original.into(), 1.0));
+        assert_eq!(simplified, vec![[0.0, 0.0], [5.0, 4.0], [27.8, 0.1]].into());
+        assert_eq!(
+            internal_array_to_linestring(InternalArray::empty()),
+            LineString::new(vec![])
+        );
+    }
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "has already been freed")]
+    fn test_double_free_is_caught() {
+        let arr = linestring_to_internal_array(vec![[1.0, 2.0], [3.0, 4.0]].into());
+        let copy = InternalArray {
+            data: arr.data,
+            len: arr.len,
+        };
+        drop(internal_array_to_linestring(arr));
+        // the copy dangles, so reclaiming it is caught before anything is freed
+        drop(internal_array_to_linestring(copy));
+    }
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "array of [f64; 2] at")]
+    fn test_mismatched_free_is_caught() {
+        // coordinates freed as indices, as if by calling drop_usize_array
+        let arr = linestring_to_internal_array(vec![[1.0, 2.0], [3.0, 4.0]].into());
+        unsafe { free::<usize>(arr) }
+    }
+    #[cfg(debug_assertions)]
+    #[test]
+    fn test_mismatched_free_leaves_array_live() {
+        let input = [[1.0, 2.0], [3.0, 4.0]];
+        let coords = external(&input);
+        let arr = simplify_rdp_idx_ffi(coords, 1.0);
+        let copy = InternalArray {
+            data: arr.data,
+            len: arr.len,
+        };
+        let mismatched = panic::catch_unwind(|| unsafe { free::<[f64; 2]>(copy) });
+        let message = mismatched.unwrap_err();
+        assert_eq!(
+            message.downcast_ref::<String>().unwrap(),
+            &format!(
+                "array of usize at {:p} was freed as an array of [f64; 2]: use the drop function \
+                 documented by the function which returned it",
+                arr.data
+            )
+        );
+        // nothing was freed, so the array can still be freed correctly
+        drop_usize_array(arr);
+        // empty arrays are never mismatched
+        drop_float_array(InternalArray::empty());
+        drop_usize_array(leak(Vec::<[f64; 2]>::new()));
+    }
+    #[cfg(debug_assertions)]
+    #[test]
+    fn test_reused_address_takes_new_type() {
+        // empty arrays are placed at their type's alignment, which no other test uses
+        #[repr(align(4096))]
+        struct Page;
+        #[repr(align(4096))]
+        struct OtherPage;
+        let entry = |arr: &InternalArray| LIVE.lock().unwrap().get(&(arr.data as usize)).copied();
+        let page = leak(Vec::<Page>::new());
+        assert_eq!(entry(&page), Some((1, type_name::<Page>())));
+        let address = page.data;
+        drop(unsafe { reclaim::<Page>(page) });
+        assert_eq!(LIVE.lock().unwrap().get(&(address as usize)), None);
+        let other = leak(Vec::<OtherPage>::new());
+        assert_eq!(other.data, address);
+        assert_eq!(entry(&other), Some((1, type_name::<OtherPage>())));
+        drop(unsafe { reclaim::<OtherPage>(other) });
+    }
+    #[test]
+    fn test_array_conversion_f32() {
+        let original: Vec<[f32; 2]> = vec![
+            [0.0, 0.0],
+            [5.0, 4.0],
+            [11.0, 5.5],
+            [17.3, 3.2],
+            [27.8, 0.1],
+        ];
+        let ls: LineString<f32> = original.clone().into();
+        // move into an Array, and leak it
+        let arr: InternalArray = ls.into();
+        // move back into a Vec -- leaked value still needs to be dropped
+        let converted: LineString<f32> = arr.into();
+        assert_eq!(converted, original.into());
+        // drop it
+        drop_float_array_f32(converted.into());
+    }
+    #[test]
+    fn test_ffi_rdp_simplification() {
+        let input = vec![
+            [0.0, 0.0],
+            [5.0, 4.0],
+            [11.0, 5.5],
+            [17.3, 3.2],
+            [27.8, 0.1],
+        ];
+        let ls: LineString<_> = input.into();
+        let output = vec![[0.0, 0.0], [5.0, 4.0], [11.0, 5.5], [27.8, 0.1]];
+        let transformed: LineString<_> = simplify_rdp_ffi(ls.into(), 1.0).into();
+        assert_eq!(transformed, output.into());
+    }
+    #[test]
+    fn test_ffi_rdp_simplification_f32() {
+        let input: Vec<[f32; 2]> = vec![
+            [0.0, 0.0],
+            [5.0, 4.0],
+            [11.0, 5.5],
+            [17.3, 3.2],
+            [27.8, 0.1],
+        ];
+        let ls: LineString<f32> = input.into();
+        let output: Vec<[f32; 2]> = vec![[0.0, 0.0], [5.0, 4.0], [11.0, 5.5], [27.8, 0.1]];
+        let transformed: LineString<f32> = simplify_rdp_ffi_f32(ls.into(), 1.0).into();
+        assert_eq!(transformed, output.into());
+    }
+    #[test]
+    fn test_simplify_generic() {
+        let input64 = [
+            [0.0, 0.0],
+            [5.0, 4.0],
+            [11.0, 5.5],
+            [17.3, 3.2],
+            [27.8, 0.1],
+        ];
+        let input32 = input64.map(|[x, y]| [x as f32, y as f32]);
+        let arr64 = external(&input64);
+        let arr32 = external(&input32);
+        let transformed64: LineString<f64> =
+            simplify_generic(arr64, |ls: &LineString<f64>| ls.simplify(&1.0)).into();
+        let transformed32: LineString<f32> =
+            simplify_generic(arr32, |ls: &LineString<f32>| ls.simplify(&1.0)).into();
+        assert_eq!(
+            transformed64,
+            vec![[0.0, 0.0], [5.0, 4.0], [11.0, 5.5], [27.8, 0.1]].into()
+        );
+        assert_eq!(
+            transformed32,
+            vec![[0.0f32, 0.0], [5.0, 4.0], [11.0, 5.5], [27.8, 0.1]].into()
+        );
+        // invalid input is rejected before the simplification function is called
+        let invalid = ExternalArray {
+            data: ptr::null(),
+            len: 5,
+        };
+        let empty = simplify_generic(invalid, |_: &LineString<f32>| unreachable!());
+        assert!(empty.data.is_null());
+        // and an empty array can be converted and freed
+        let converted: LineString<f32> = empty.into();
+        assert!(converted.0.is_empty());
+    }
+    #[test]
+    fn test_ffi_rdp_custom_simplification() {
+        extern "C" fn euclidean(ax: f64, ay: f64, bx: f64, by: f64, px: f64, py: f64) -> f64 {
+            douglas_peucker::segment_distance([px, py], [ax, ay], [bx, by])
+        }
+        // ignore the x axis entirely
+        extern "C" fn vertical(ax: f64, ay: f64, bx: f64, by: f64, px: f64, py: f64) -> f64 {
+            let t = if bx == ax { 0.0 } else { (px - ax) / (bx - ax) };
+            (py - (ay + t * (by - ay))).abs()
+        }
+        let points: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let coords = external(&points);
+        for precision in [0.0001, 0.001, 0.01] {
+            let custom: LineString<f64> =
+                simplify_rdp_custom_ffi(coords, precision, Some(euclidean)).into();
+            let builtin: LineString<f64> = simplify_rdp_ffi(coords, precision).into();
+            assert_eq!(custom, builtin);
+        }
+        let input = [[0.0, 0.0], [5.0, 0.9], [10.0, 0.0]];
+        let coords = external(&input);
+        let custom: LineString<f64> = simplify_rdp_custom_ffi(coords, 1.0, Some(vertical)).into();
+        assert_eq!(custom, vec![[0.0, 0.0], [10.0, 0.0]].into());
+        assert!(simplify_rdp_custom_ffi(coords, 1.0, None).data.is_null());
+    }
+    #[test]
+    fn test_panics_are_caught() {
+        let input = [[0.0, 0.0], [1.0, 1.0], [2.0, 0.0]];
+        let coords = external(&input);
+        let result = checked(coords, |_: &[[f64; 2]]| -> InternalArray {
+            panic!("simplification failed")
+        });
+        assert_eq!(result.error, FfiError::Panic);
+        assert!(result.array.data.is_null());
+        drop_float_array(result.array);
+        assert!(guarded(f64::NAN, || -> f64 { panic!("deviation failed") }).is_nan());
+        assert_eq!(guarded(f64::NAN, || 1.0), 1.0);
+    }
+    // Copy the calling thread's last error message
+    // The input index of each output point. The input mustn't have repeated points, so that each
+    // output point has a unique input index
+    fn input_indices(input: &[[f64; 2]], output: &[[f64; 2]]) -> Vec<usize> {
+        output
+            .iter()
+            .map(|c| input.iter().position(|i| i == c).unwrap())
+            .collect()
+    }
+    fn last_error() -> Option<String> {
+        let message = rdp_last_error_message();
+        if message.is_null() {
+            return None;
+        }
+        let message = unsafe { std::ffi::CStr::from_ptr(message) };
+        Some(message.to_str().unwrap().to_owned())
+    }
+    #[test]
+    fn test_version() {
+        let version = unsafe { CStr::from_ptr(rdp_version()) }.to_str().unwrap();
+        assert!(version.starts_with(env!("CARGO_PKG_VERSION")));
+        // the geo version must be kept in step with the manifest
+        let geo = version.split("(geo ").nth(1).unwrap().trim_end_matches(')');
+        let manifest = include_str!("../Cargo.toml");
+        assert!(manifest.contains(&format!("geo = {{ version = \"{geo}.")));
+    }
+    #[test]
+    fn test_has_feature() {
+        let has = |name: &str| unsafe { rdp_has_feature(CString::new(name).unwrap().as_ptr()) };
+        assert_eq!(has("rayon"), cfg!(feature = "rayon") as libc::c_int);
+        assert_eq!(has("geojson"), cfg!(feature = "geojson") as libc::c_int);
+        assert_eq!(has("wkb"), cfg!(feature = "wkb") as libc::c_int);
+        assert_eq!(has("serde"), cfg!(feature = "serde") as libc::c_int);
+        assert_eq!(has("f32"), 1);
+        assert_eq!(has("quantum"), 0);
+        assert_eq!(has(""), 0);
+        assert_eq!(unsafe { rdp_has_feature(ptr::null()) }, 0);
+    }
+    #[test]
+    fn test_internal_array_byte_size() {
+        let input = [
+            [0.0, 0.0],
+            [5.0, 4.0],
+            [11.0, 5.5],
+            [17.3, 3.2],
+            [27.8, 0.1],
+        ];
+        let coords = external(&input);
+        let simplified = simplify_rdp_ffi(coords, 1.0);
+        let indices = simplify_rdp_idx_ffi(coords, 1.0);
+        let narrow = simplify_rdp_idx_u32_ffi(coords, 1.0).array;
+        let bytes = leak(vec![0u8; 5]);
+        for (arr, kind, size) in [
+            (&simplified, ElementKind::F64Pair, 4 * 16),
+            (&indices, ElementKind::Usize, 4 * mem::size_of::<usize>()),
+            (&narrow, ElementKind::U32, 4 * 4),
+            (&bytes, ElementKind::U8, 5),
+        ] {
+            assert_eq!(
+                unsafe { internal_array_byte_size(arr, kind as libc::c_int) },
+                size
+            );
+        }
+        assert_eq!(unsafe { internal_array_byte_size(&simplified, 4) }, 0);
+        assert_eq!(unsafe { internal_array_byte_size(ptr::null(), 0) }, 0);
+        drop_float_array(simplified);
+        drop_usize_array(indices);
+        drop_u32_array(narrow);
+        drop(unsafe { reclaim::<u8>(bytes) });
+    }
+    #[test]
+    fn test_last_error_message() {
+        // each test runs on its own thread, so nothing has failed on this one yet
+        assert_eq!(last_error(), None);
+        let null = ExternalArray {
+            data: ptr::null(),
+            len: 3,
+        };
+        let empty = simplify_rdp_ffi(null, 1.0);
+        assert!(empty.data.is_null());
+        drop_float_array(empty);
+        assert_eq!(
+            last_error().unwrap(),
+            "the input array's data field is a null pointer"
+        );
+
+        let input = [[0.0, 0.0], [1.0, 1.0], [2.0, f64::NAN]];
+        let coords = external(&input);
+        let result = simplify_rdp_checked_ffi(coords, 1.0);
+        assert_eq!(result.error, FfiError::NonFiniteCoordinate);
+        assert_eq!(
+            last_error().unwrap(),
+            "point 2 of the input array has a NaN or infinite coordinate"
+        );
+        guarded((), || panic!("deviation failed"));
+        assert_eq!(
+            last_error().unwrap(),
+            "simplification panicked: deviation failed"
+        );
+        // a successful call leaves the message alone
+        let simplified = simplify_rdp_ffi(
+            ExternalArray {
+                data: input.as_ptr() as *const libc::c_void,
+                len: 2,
+            },
+            1.0,
+        );
+        drop_float_array(simplified);
+        assert!(last_error().unwrap().contains("deviation failed"));
+        // messages are recorded per thread
+        std::thread::spawn(|| assert_eq!(last_error(), None))
+            .join()
+            .unwrap();
+    }
+    #[test]
+    fn test_ffi_short_inputs() {
+        extern "C" fn euclidean(ax: f64, ay: f64, bx: f64, by: f64, px: f64, py: f64) -> f64 {
+            douglas_peucker::segment_distance([px, py], [ax, ay], [bx, by])
+        }
+        type Simplifier = (&'static str, fn(ExternalArray) -> InternalArray);
+        let coord_fns: [Simplifier; 9] = [
+            ("simplify_ffi", |c| {
+                simplify_ffi(c, 1.0, Algorithm::Rdp as libc::c_int)
+            }),
+            ("simplify_rdp_ffi", |c| simplify_rdp_ffi(c, 1.0)),
+            ("simplify_rdp_clean_ffi", |c| simplify_rdp_clean_ffi(c, 1.0)),
+            ("simplify_rdp_max_points_ffi", |c| {
+                simplify_rdp_max_points_ffi(c, 2)
+            }),
+            ("simplify_rdp_custom_ffi", |c| {
+                simplify_rdp_custom_ffi(c, 1.0, Some(euclidean))
+            }),
+            ("simplify_rdp_geodesic_ffi", |c| {
+                simplify_rdp_geodesic_ffi(c, 1.0, 0)
+            }),
+            ("simplify_visvalingam_ffi", |c| {
+                simplify_visvalingam_ffi(c, 1.0)
+            }),
+            ("simplify_visvalingamp_ffi", |c| {
+                simplify_visvalingamp_ffi(c, 1.0)
+            }),
+            ("simplify_visvalingamp_checked_ffi", |c| {
+                simplify_visvalingamp_checked_ffi(c, 1.0).array
+            }),
+        ];
+        let idx_fns: [Simplifier; 3] = [
+            ("simplify_rdp_idx_ffi", |c| simplify_rdp_idx_ffi(c, 1.0)),
+            ("simplify_visvalingam_idx_ffi", |c| {
+                simplify_visvalingam_idx_ffi(c, 1.0)
+            }),
+            ("simplify_visvalingamp_idx_ffi", |c| {
+                simplify_visvalingamp_idx_ffi(c, 1.0)
+            }),
+        ];
+        let f32_fns: [Simplifier; 3] = [
+            ("simplify_rdp_ffi_f32", |c| simplify_rdp_ffi_f32(c, 1.0)),
+            ("simplify_visvalingam_ffi_f32", |c| {
+                simplify_visvalingam_ffi_f32(c, 1.0)
+            }),
+            ("simplify_visvalingamp_ffi_f32", |c| {
+                simplify_visvalingamp_ffi_f32(c, 1.0)
+            }),
+        ];
+        let points = [[0.0, 0.0], [1.0, 1.0]];
+        for len in 0..=2 {
+            let input = &points[..len];
+            let coords = external(input);
+            for (name, f) in coord_fns {
+                let result = f(coords);
+                if len == 0 {
+                    assert!(result.data.is_null(), "{}", name);
+                }
+                let result: LineString<f64> = result.into();
+                assert_eq!(result, input.to_vec().into(), "{} ({} points)", name, len);
+            }
+            for (name, f) in idx_fns {
+                let result: Vec<usize> = f(coords).into();
+                assert_eq!(result, (0..len).collect::<Vec<_>>(), "{}", name);
+            }
+            let input32 = input
+                .iter()
+                .map(|[x, y]| [*x as f32, *y as f32])
+                .collect::<Vec<_>>();
+            let coords32 = external(&input32);
+            for (name, f) in f32_fns {
+                let result: LineString<f32> = f(coords32).into();
+                assert_eq!(result, input32.clone().into(), "{}", name);
+            }
+        }
+    }
+    #[test]
+    fn test_ffi_rdp_foreach_simplification() {
+        extern "C" fn collect(x: f64, y: f64, user: *mut libc::c_void) {
+            let points = unsafe { &mut *(user as *mut Vec<[f64; 2]>) };
+            points.push([x, y]);
+        }
+        let input: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let coords = external(&input);
+        let mut points: Vec<[f64; 2]> = vec![];
+        let user = &mut points as *mut Vec<[f64; 2]> as *mut libc::c_void;
+        simplify_rdp_foreach_ffi(coords, 0.001, Some(collect), user);
+        let expected: LineString<f64> = simplify_rdp_ffi(coords, 0.001).into();
+        assert_eq!(LineString::from(points), expected);
+        // invalid input never reaches the callback
+        let mut points: Vec<[f64; 2]> = vec![];
+        let user = &mut points as *mut Vec<[f64; 2]> as *mut libc::c_void;
+        let invalid = ExternalArray {
+            data: ptr::null(),
+            len: 3,
+        };
+        simplify_rdp_foreach_ffi(invalid, 0.001, Some(collect), user);
+        assert!(points.is_empty());
+    }
+    #[test]
+    fn test_ffi_max_deviation() {
+        let original = [[0.0, 0.0], [3.0, 4.0], [6.0, 0.0], [10.0, 0.0]];
+        let simplified = [[0.0, 0.0], [10.0, 0.0]];
+        assert_eq!(
+            max_deviation_ffi(external(&original), external(&simplified)),
+            4.0
+        );
+        assert!(max_deviation_ffi(external(&original), external::<[f64; 2]>(&[])).is_nan());
+        assert!(max_deviation_ffi(external(&simplified), external(&original)).is_nan());
+    }
+    #[test]
+    fn test_ffi_within_tolerance() {
+        let original: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let simplified: Vec<[f64; 2]> =
+            unsafe { reclaim(simplify_rdp_ffi(external(&original), 0.001)) };
+        assert_eq!(
+            within_tolerance_ffi(external(&original), external(&simplified), 0.001),
+            1
+        );
+        // keeping only the endpoints is a deliberate over-simplification
+        let endpoints = [original[0], original[original.len() - 1]];
+        assert_eq!(
+            within_tolerance_ffi(external(&original), external(&endpoints), 0.001),
+            0
+        );
+        assert_eq!(
+            within_tolerance_ffi(external(&original), external::<[f64; 2]>(&[]), 0.001),
+            0
+        );
+    }
+    #[test]
+    fn test_ffi_is_simplified() {
+        let minimal = [[0.0, 0.0], [5.0, 4.0], [11.0, 5.5], [27.8, 0.1]];
+        assert_eq!(is_simplified_ffi(external(&minimal), 1.0), 1);
+        // (17.3, 3.2) is less than 1 from the segment joining its neighbours
+        let redundant = [
+            [0.0, 0.0],
+            [5.0, 4.0],
+            [11.0, 5.5],
+            [17.3, 3.2],
+            [27.8, 0.1],
+        ];
+        assert_eq!(is_simplified_ffi(external(&redundant), 1.0), 0);
+        assert_eq!(is_simplified_ffi(external(&redundant), 0.0), 1);
+        assert_eq!(is_simplified_ffi(external::<[f64; 2]>(&[]), 1.0), 0);
+    }
+    #[test]
+    fn test_ffi_hausdorff_distance() {
+        let baseline = [[0.0, 0.0], [10.0, 0.0]];
+        let tent = [[0.0, 1.0], [5.0, 3.0], [10.0, 1.0]];
+        let distance = hausdorff_distance_ffi(external(&baseline), external(&tent));
+        assert!((distance - 3.0).abs() < 1e-12);
+        assert_eq!(
+            hausdorff_distance_ffi(external(&tent), external(&baseline)),
+            distance
+        );
+        assert!(hausdorff_distance_ffi(external(&baseline), external::<[f64; 2]>(&[])).is_nan());
+    }
+    #[test]
+    fn test_ffi_rdp_both() {
+        let input: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let coords = external(&input);
+        let mut idx = InternalArray::empty();
+        let simplified: Vec<[f64; 2]> =
+            unsafe { reclaim(simplify_rdp_both_ffi(coords, 0.001, &mut idx)) };
+        let indices: Vec<usize> = idx.into();
+        assert_eq!(indices, api::simplify_rdp_idx(&input, 0.001));
+        let expected: Vec<[f64; 2]> = indices.iter().map(|&i| input[i]).collect();
+        assert_eq!(simplified, expected);
+        // a null index pointer is tolerated
+        let simplified: LineString<f64> =
+            unsafe { simplify_rdp_both_ffi(coords, 0.001, ptr::null_mut()) }.into();
+        assert_eq!(simplified.0.len(), indices.len());
+        let mut idx = InternalArray::empty();
+        let empty = ExternalArray {
+            data: ptr::null(),
+            len: 0,
+        };
+        let result = unsafe { simplify_rdp_both_ffi(empty, 0.001, &mut idx) };
+        assert!(result.data.is_null() && idx.data.is_null());
+    }
+    #[test]
+    fn test_ffi_rdp_cancellable() {
+        let input: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let coords = external(&input);
+        let expected: LineString<f64> = simplify_rdp_ffi(coords, 0.001).into();
+        let flag = AtomicI32::new(0);
+        let uncancelled: LineString<f64> =
+            simplify_rdp_cancellable_ffi(coords, 0.001, flag.as_ptr()).into();
+        assert_eq!(uncancelled, expected);
+        let uncancellable: LineString<f64> =
+            simplify_rdp_cancellable_ffi(coords, 0.001, ptr::null()).into();
+        assert_eq!(uncancellable, expected);
+        flag.store(1, Ordering::Relaxed);
+        let cancelled = simplify_rdp_cancellable_ffi(coords, 0.001, flag.as_ptr());
+        assert!(cancelled.data.is_null());
+        drop_float_array(cancelled);
+    }
+    #[test]
+    fn test_ffi_rdp_deep() {
+        // each split peels off a single point, so recursion would overflow this thread's stack
+        let handle = std::thread::Builder::new()
+            .stack_size(64 * 1024)
+            .spawn(|| {
+                let input: Vec<[f64; 2]> = (0..3000)
+                    .map(|i| {
+                        let amplitude = (3000 - i) as f64;
+                        [i as f64, if i % 2 == 0 { amplitude } else { -amplitude }]
+                    })
+                    .collect();
+                let coords = external(&input);
+                let simplified: LineString<f64> = simplify_rdp_ffi(coords, 0.5).into();
+                assert_eq!(simplified, input.into());
+            })
+            .unwrap();
+        handle.join().unwrap();
+    }
+    #[test]
+    fn test_ffi_rdp_xy() {
+        let input: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let coords = external(&input);
+        let expected: LineString<f64> = simplify_rdp_ffi(coords, 0.001).into();
+        let (x_column, y_column): (Vec<f64>, Vec<f64>) = input.iter().map(|&[x, y]| (x, y)).unzip();
+        let xs = external(&x_column);
+        let ys = external(&y_column);
+        let mut ys_out = InternalArray::empty();
+        let xs_out = unsafe { simplify_rdp_xy_ffi(xs, ys, 0.001, &mut ys_out) };
+        let simplified_x: Vec<f64> = unsafe { reclaim(xs_out) };
+        let simplified_y: Vec<f64> = unsafe { reclaim(ys_out) };
+        let simplified: LineString<f64> = simplified_x
+            .into_iter()
+            .zip(simplified_y)
+            .map(|(x, y)| [x, y])
+            .collect::<Vec<_>>()
+            .into();
+        assert_eq!(simplified, expected);
+
+        let short = ExternalArray {
+            data: y_column.as_ptr() as *const libc::c_void,
+            len: y_column.len() - 1,
+        };
+        let mut ys_out = InternalArray::empty();
+        let xs_out = unsafe { simplify_rdp_xy_ffi(xs, short, 0.001, &mut ys_out) };
+        assert!(xs_out.data.is_null() && ys_out.data.is_null());
+        drop_flat_f64_array(xs_out);
+        drop_flat_f64_array(ys_out);
+    }
+    #[test]
+    fn test_ffi_rdp_flat() {
+        let input: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let coords = external(&input);
+        let flat = simplify_rdp_flat_ffi(coords, 0.001);
+        let pairs: LineString<f64> = simplify_rdp_ffi(coords, 0.001).into();
+        assert_eq!(flat.len, pairs.0.len() * 2);
+        let values = unsafe { slice::from_raw_parts(flat.data as *const f64, flat.len) };
+        let decoded: LineString<f64> = values
+            .chunks_exact(2)
+            .map(|xy| [xy[0], xy[1]])
+            .collect::<Vec<_>>()
+            .into();
+        assert_eq!(decoded, pairs);
+        drop_flat_f64_array(flat);
+        let invalid = simplify_rdp_flat_ffi(
+            ExternalArray {
+                data: ptr::null(),
+                len: 0,
+            },
+            0.001,
+        );
+        assert!(invalid.data.is_null());
+        drop_flat_f64_array(invalid);
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_array_descriptor_serde() {
+        let input: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let coords = external(&input);
+        let simplified = simplify_rdp_ffi(coords, 0.001);
+        for (descriptor, kind, address) in [
+            (
+                coords.descriptor(),
+                ArrayKind::External,
+                coords.data as usize,
+            ),
+            (
+                simplified.descriptor(),
+                ArrayKind::Internal,
+                simplified.data as usize,
+            ),
+        ] {
+            let json = serde_json::to_string(&descriptor).unwrap();
+            // the pointer isn't serialised, in any form
+            assert!(!json.contains("data"));
+            assert!(!json.contains(&address.to_string()));
+            assert!(!json.contains(&format!("{address:x}")));
+            let parsed: ArrayDescriptor = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, descriptor);
+            assert_eq!(parsed.kind, kind);
+        }
+        assert_eq!(
+            serde_json::to_value(coords.descriptor()).unwrap(),
+            serde_json::json!({"kind": "External", "len": input.len()})
+        );
+        drop_float_array(simplified);
+    }
+    #[test]
+    fn test_ffi_rdp_idx_u32() {
+        let input: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let coords = external(&input);
+        let result = simplify_rdp_idx_u32_ffi(coords, 0.001);
+        assert_eq!(result.error, FfiError::Success);
+        let narrow: Vec<u32> = unsafe { reclaim(result.array) };
+        let wide: Vec<usize> = simplify_rdp_idx_ffi(coords, 0.001).into();
+        assert_eq!(narrow.len(), wide.len());
+        assert!(narrow.iter().zip(&wide).all(|(&n, &w)| n as usize == w));
+        let invalid = simplify_rdp_idx_u32_ffi(
+            ExternalArray {
+                data: ptr::null(),
+                len: 0,
+            },
+            0.001,
+        );
+        assert_eq!(invalid.error, FfiError::NullPointer);
+        drop_u32_array(invalid.array);
+        // indices which don't fit are rejected, rather than truncated
+        assert_eq!(
+            indices_u32(vec![0, 1, u32::MAX as usize]),
+            Ok(vec![0, 1, u32::MAX])
+        );
+        #[cfg(target_pointer_width = "64")]
+        assert_eq!(
+            indices_u32(vec![0, u32::MAX as usize + 1]),
+            Err(FfiError::IndexOverflow)
+        );
+    }
+    #[test]
+    fn test_ffi_rdp_stats_simplification() {
+        let input = vec![
+            [0.0, 0.0],
+            [5.0, 4.0],
+            [11.0, 5.5],
+            [17.3, 3.2],
+            [27.8, 0.1],
+        ];
+        let ls: LineString<_> = input.into();
+        let coords: ExternalArray = ls.into();
+        let mut removed: libc::size_t = 0;
+        let transformed = unsafe { simplify_rdp_stats_ffi(coords, 1.0, &mut removed) };
+        assert_eq!(transformed.len, 4);
+        assert_eq!(removed, 1);
+        drop_float_array(transformed);
+        // a null pointer is tolerated
+        let transformed = unsafe { simplify_rdp_stats_ffi(coords, 1.0, ptr::null_mut()) };
+        assert_eq!(transformed.len, 4);
+        drop_float_array(transformed);
+    }
+    #[test]
+    fn test_ffi_rdp_bbox() {
+        // the interior point at the top of the input's bounding box is removed
+        let input = [[0.0, 0.0], [5.0, 0.5], [10.0, 0.2], [20.0, 0.0]];
+        let coords = external(&input);
+        let mut bbox = [0.0; 4];
+        let simplified = unsafe { simplify_rdp_bbox_ffi(coords, 1.0, &mut bbox) };
+        let retained: Vec<[f64; 2]> = unsafe { reclaim(simplified) };
+        assert_eq!(retained, vec![[0.0, 0.0], [20.0, 0.0]]);
+        assert_eq!(Some(bbox), api::bounding_box(&retained));
+        assert_eq!(bbox, [0.0, 0.0, 20.0, 0.0]);
+        assert_eq!(api::bounding_box(&input), Some([0.0, 0.0, 20.0, 0.5]));
+
+        let invalid = ExternalArray {
+            data: ptr::null(),
+            len: 0,
+        };
+        let empty = unsafe { simplify_rdp_bbox_ffi(invalid, 1.0, &mut bbox) };
+        assert!(bbox.iter().all(|value| value.is_nan()));
+        drop_float_array(empty);
+        let simplified = unsafe { simplify_rdp_bbox_ffi(coords, 1.0, ptr::null_mut()) };
+        assert_eq!(simplified.len, 2);
+        drop_float_array(simplified);
+    }
+    #[test]
+    fn test_ffi_rdp_hull() {
+        // a zigzag, whose retained notch at (5.0, 1.0) lies inside the hull
+        let input = [
+            [0.0, 0.0],
+            [2.0, 4.0],
+            [3.0, 4.1],
+            [4.0, 4.0],
+            [5.0, 1.0],
+            [6.0, 3.0],
+            [8.0, 0.0],
+        ];
+        let coords = external(&input);
+        let simplified: Vec<[f64; 2]> = unsafe { reclaim(simplify_rdp_ffi(coords, 0.5)) };
+        let hull: LineString<f64> = simplify_rdp_hull_ffi(coords, 0.5).into();
+        assert!(hull.is_closed());
+        assert!(hull.0.len() >= 4);
+        assert!(simplified.contains(&[5.0, 1.0]));
+        assert!(hull
+            .points()
+            .all(|point| simplified.contains(&[point.x(), point.y()])));
+        assert_eq!(
+            hull,
+            vec![
+                [8.0, 0.0],
+                [6.0, 3.0],
+                [4.0, 4.0],
+                [3.0, 4.1],
+                [2.0, 4.0],
+                [0.0, 0.0],
+                [8.0, 0.0]
+            ]
+            .into()
+        );
+        // the hull of a segment is the segment
+        let segment = [[0.0, 0.0], [1.0, 1.0], [0.0, 0.0]];
+        let degenerate: LineString<f64> = simplify_rdp_hull_ffi(external(&segment), 0.5).into();
+        assert_eq!(degenerate, segment.to_vec().into());
+    }
+    #[test]
+    fn test_ffi_rdp_length() {
+        // 3-4-5 triangles either side of the removed point
+        let input = [[0.0, 0.0], [3.0, 0.4], [6.0, 0.0], [9.0, 4.0]];
+        let coords = external(&input);
+        let (mut original, mut simplified) = (0.0, 0.0);
+        let transformed =
+            unsafe { simplify_rdp_length_ffi(coords, 1.0, &mut original, &mut simplified) };
+        let retained: Vec<[f64; 2]> = unsafe { reclaim(transformed) };
+        assert_eq!(retained, vec![[0.0, 0.0], [6.0, 0.0], [9.0, 4.0]]);
+        assert_eq!(original, 2.0 * 3.0f64.hypot(0.4) + 5.0);
+        assert_eq!(simplified, 11.0);
+        assert!(simplified <= original);
+
+        let invalid = ExternalArray {
+            data: ptr::null(),
+            len: 0,
+        };
+        let empty =
+            unsafe { simplify_rdp_length_ffi(invalid, 1.0, &mut original, &mut simplified) };
+        assert!(original.is_nan() && simplified.is_nan());
+        drop_float_array(empty);
+        let transformed =
+            unsafe { simplify_rdp_length_ffi(coords, 1.0, ptr::null_mut(), ptr::null_mut()) };
+        assert_eq!(transformed.len, 3);
+        drop_float_array(transformed);
+    }
+    #[test]
+    fn test_ffi_rdp_i32() {
+        // tile coordinates, 4096 units to a tile
+        let input: Vec<[i32; 2]> = include!("../src/mk_route.rs")
+            .iter()
+            .map(|&[x, y]: &[f64; 2]| [(x * 4096.0) as i32, (y * 4096.0) as i32])
+            .collect();
+        let coords = external(&input);
+        let simplified: Vec<[i32; 2]> = unsafe { reclaim(simplify_rdp_i32_ffi(coords, 2.0)) };
+        assert!(simplified.len() < input.len());
+        // the output is an ordered subset of the input, so nothing has been rounded
+        let mut remaining = input.iter();
+        assert!(simplified
+            .iter()
+            .all(|point| remaining.any(|candidate| candidate == point)));
+        assert_eq!(simplified[0], input[0]);
+        assert_eq!(simplified[simplified.len() - 1], input[input.len() - 1]);
+
+        let invalid = ExternalArray {
+            data: ptr::null(),
+            len: 5,
+        };
+        let empty = simplify_rdp_i32_ffi(invalid, 2.0);
+        assert!(empty.data.is_null());
+        drop_i32_array(empty);
+        assert_eq!(
+            last_error().unwrap(),
+            "the input array's data field is a null pointer"
+        );
+    }
+    #[test]
+    fn test_ffi_rdp_range() {
+        let input: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let coords = external(&input);
+        let (start, end) = (100, 400);
+        let simplified: Vec<[f64; 2]> =
+            unsafe { reclaim(simplify_rdp_range_ffi(coords, start, end, 0.001)) };
+        let tail = input.len() - end - 1;
+        assert!(simplified.len() < input.len());
+        // the points outside the range are bit-identical to the input
+        let bits = |points: &[[f64; 2]]| -> Vec<[u64; 2]> {
+            points.iter().map(|point| point.map(f64::to_bits)).collect()
+        };
+        assert_eq!(bits(&simplified[..start]), bits(&input[..start]));
+        assert_eq!(
+            bits(&simplified[simplified.len() - tail..]),
+            bits(&input[end + 1..])
+        );
+        assert_eq!(
+            &simplified[start..simplified.len() - tail],
+            api::simplify_rdp(&input[start..=end], 0.001).as_slice()
+        );
+
+        let whole: LineString<f64> =
+            simplify_rdp_range_ffi(coords, 0, input.len() - 1, 0.001).into();
+        let expected: LineString<f64> = simplify_rdp_ffi(coords, 0.001).into();
+        assert_eq!(whole, expected);
+        let out_of_range = simplify_rdp_range_ffi(coords, 0, input.len(), 0.001);
+        assert!(out_of_range.data.is_null());
+        drop_float_array(out_of_range);
+        let reversed = simplify_rdp_range_ffi(coords, end, start, 0.001);
+        assert!(reversed.data.is_null());
+        drop_float_array(reversed);
+    }
+    #[test]
+    fn test_ffi_merge_simplify_rdp() {
+        // two legs of a route meeting at a collinear junction
+        let first = [[0.0, 0.0], [5.0, 0.2], [10.0, 0.0]];
+        let second = [[10.0, 0.0], [15.0, -0.2], [20.0, 0.0]];
+        let a = external(&first);
+        let b = external(&second);
+        let merged: LineString<f64> = merge_simplify_rdp_ffi(a, b, 1.0).into();
+        assert_eq!(merged, vec![[0.0, 0.0], [20.0, 0.0]].into());
+        // simplified separately, each leg retains the junction
+        let separate: LineString<f64> = simplify_rdp_ffi(a, 1.0).into();
+        assert_eq!(separate, vec![[0.0, 0.0], [10.0, 0.0]].into());
+
+        let invalid = ExternalArray {
+            data: ptr::null(),
+            len: 0,
+        };
+        let empty = merge_simplify_rdp_ffi(a, invalid, 1.0);
+        assert!(empty.data.is_null());
+        drop_float_array(empty);
+    }
+    #[test]
+    fn test_ffi_rdp_bounded() {
+        let input: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let coords = external(&input);
+        let expected: LineString<f64> = simplify_rdp_ffi(coords, 0.0001).into();
+        let mut truncated = -1;
+        let unbounded: LineString<f64> =
+            unsafe { simplify_rdp_bounded_ffi(coords, 0.0001, usize::MAX, &mut truncated) }.into();
+        assert_eq!(unbounded, expected);
+        assert_eq!(truncated, 0);
+        // a budget of a few passes over the line stops early, with a coarser simplification
+        let bounded: LineString<f64> =
+            unsafe { simplify_rdp_bounded_ffi(coords, 0.0001, input.len() * 3, &mut truncated) }
+                .into();
+        assert_eq!(truncated, 1);
+        assert!(bounded.0.len() > 2 && bounded.0.len() < expected.0.len());
+        assert_eq!(bounded.0.first(), expected.0.first());
+        assert_eq!(bounded.0.last(), expected.0.last());
+        assert!(bounded.0.iter().all(|c| expected.0.contains(c)));
+        let simplified = unsafe { simplify_rdp_bounded_ffi(coords, 0.0001, 10, ptr::null_mut()) };
+        assert_eq!(simplified.len, 2);
+        drop_float_array(simplified);
+    }
+    #[test]
+    fn test_ffi_rdp_density() {
+        let input: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let coords = external(&input);
+        let simplified: Vec<[f64; 2]> = unsafe { reclaim(simplify_rdp_ffi(coords, 0.001)) };
+        for max_span in [0, 1, 5, 20] {
+            let dense: Vec<[f64; 2]> =
+                unsafe { reclaim(simplify_rdp_density_ffi(coords, 0.001, max_span)) };
+            assert!(simplified.iter().all(|c| dense.contains(c)));
+            let indices = input_indices(&input, &dense);
+            assert_eq!(indices.first(), Some(&0));
+            assert_eq!(indices.last(), Some(&(input.len() - 1)));
+            assert!(indices
+                .windows(2)
+                .all(|w| w[0] < w[1] && w[1] - w[0] - 1 <= max_span));
+        }
+    }
+    #[test]
+    fn test_ffi_rdp_max_segment() {
+        // a gentle arc of radius 1000, sampled every degree
+        let input: Vec<[f64; 2]> = (0..=60)
+            .map(|degrees| {
+                let theta = f64::from(degrees).to_radians();
+                [1000.0 * theta.sin(), 1000.0 * (1.0 - theta.cos())]
+            })
+            .collect();
+        let coords = external(&input);
+        let length = |ls: &LineString<f64>| -> f64 {
+            ls.lines()
+                .map(|line| line.dx().hypot(line.dy()))
+                .fold(0.0, f64::max)
+        };
+        let plain: LineString<f64> = simplify_rdp_ffi(coords, 50.0).into();
+        assert!(length(&plain) > 200.0);
+        let limited: LineString<f64> = simplify_rdp_max_segment_ffi(coords, 50.0, 200.0).into();
+        assert!(length(&limited) <= 200.0);
+        assert!(limited.0.len() < input.len());
+        assert!(limited.0.iter().all(|c| input.contains(&[c.x, c.y])));
+        assert_eq!(limited.0.first(), plain.0.first());
+        assert_eq!(limited.0.last(), plain.0.last());
+    }
+    #[test]
+    fn test_ffi_rdp_clean_simplification() {
+        let input = [
+            [0.0, 0.0],
+            [5.0, 4.0],
+            [f64::NAN, 1.0],
+            [11.0, 5.5],
+            [17.3, f64::INFINITY],
+            [17.3, 3.2],
+            [27.8, 0.1],
+        ];
+        let coords = external(&input);
+        let output = vec![[0.0, 0.0], [5.0, 4.0], [11.0, 5.5], [27.8, 0.1]];
+        let transformed: LineString<_> = simplify_rdp_clean_ffi(coords, 1.0).into();
+        assert_eq!(transformed, output.into());
+        // the strict variant rejects the same input
+        assert!(simplify_rdp_ffi(coords, 1.0).data.is_null());
+        // nothing finite remains
+        let input = [[f64::NAN, 0.0], [0.0, f64::NEG_INFINITY]];
+        let coords = external(&input);
+        let transformed = simplify_rdp_clean_ffi(coords, 1.0);
+        assert!(transformed.data.is_null());
+        assert_eq!(transformed.len, 0);
+        assert_eq!(
+            last_error().unwrap(),
+            "every point of the input array has a NaN or infinite coordinate"
+        );
+        let null = ExternalArray {
+            data: ptr::null(),
+            len: 2,
+        };
+        assert!(simplify_rdp_clean_ffi(null, 1.0).data.is_null());
+        assert_eq!(
+            last_error().unwrap(),
+            "the input array's data field is a null pointer"
+        );
+    }
+    #[test]
+    fn test_ffi_rdp_max_points_simplification() {
+        let input = vec![
+            [0.0, 0.0],
+            [5.0, 4.0],
+            [11.0, 5.5],
+            [17.3, 3.2],
+            [27.8, 0.1],
+        ];
+        let ls: LineString<_> = input.into();
+        let transformed: LineString<_> = simplify_rdp_max_points_ffi(ls.into(), 1).into();
+        assert_eq!(transformed, vec![[0.0, 0.0], [27.8, 0.1]].into());
+    }
+    #[test]
+    fn test_ffi_rdp_byte_budget() {
+        let input: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let coords = external(&input);
+        let mut previous = 0;
+        for budget in [32, 100, 500, 1000, 4000] {
+            let simplified: Vec<[f64; 2]> =
+                unsafe { reclaim(simplify_rdp_byte_budget_ffi(coords, budget)) };
+            assert!(mem::size_of_val(simplified.as_slice()) <= budget);
+            assert!(simplified.len() >= previous);
+            previous = simplified.len();
+        }
+        // the whole line fits
+        let budget = mem::size_of_val(input.as_slice());
+        let simplified: Vec<[f64; 2]> =
+            unsafe { reclaim(simplify_rdp_byte_budget_ffi(coords, budget)) };
+        assert_eq!(simplified, input);
+        // the endpoints don't fit
+        let simplified = simplify_rdp_byte_budget_ffi(coords, 31);
+        assert!(simplified.data.is_null());
+        drop_float_array(simplified);
+    }
+    #[test]
+    fn test_ffi_rdp_relative() {
+        let input = [[0.0, 0.0], [1.0, 1.6], [2.0, 3.0], [3.0, 1.4], [4.0, 0.0]];
+        let scaled = input.map(|[x, y]| [x * 10.0, y * 10.0]);
+        let coords = external(&input);
+        let coords_scaled = external(&scaled);
+        let simplified: LineString<f64> = simplify_rdp_relative_ffi(coords, 0.1).into();
+        let simplified_scaled: LineString<f64> =
+            simplify_rdp_relative_ffi(coords_scaled, 0.1).into();
+        assert_eq!(simplified, vec![[0.0, 0.0], [2.0, 3.0], [4.0, 0.0]].into());
+        assert_eq!(
+            simplified_scaled,
+            vec![[0.0, 0.0], [20.0, 30.0], [40.0, 0.0]].into()
+        );
+    }
+    #[test]
+    fn test_ffi_vw_relative() {
+        let input: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let scaled: Vec<[f64; 2]> = input.iter().map(|&[x, y]| [x * 10.0, y * 10.0]).collect();
+        let coords = external(&input);
+        let coords_scaled = external(&scaled);
+        let indices = |points: &[[f64; 2]], simplified: InternalArray| {
+            input_indices(points, &unsafe { reclaim(simplified) })
+        };
+        for fraction in [1e-8, 1e-6, 1e-4] {
+            let retained = indices(&input, simplify_vw_relative_ffi(coords, fraction));
+            let retained_scaled =
+                indices(&scaled, simplify_vw_relative_ffi(coords_scaled, fraction));
+            assert!(retained.len() > 2 && retained.len() < input.len());
+            assert_eq!(retained, retained_scaled);
+        }
+        // an absolute epsilon removes more points from the smaller line
+        let retained = indices(&input, simplify_visvalingam_ffi(coords, 1e-6));
+        let retained_scaled = indices(&scaled, simplify_visvalingam_ffi(coords_scaled, 1e-6));
+        assert!(retained.len() < retained_scaled.len());
+    }
+    #[test]
+    fn test_ffi_rdp_floor_simplification() {
+        let input = vec![
+            [0.0, 0.0],
+            [5.0, 4.0],
+            [11.0, 5.5],
+            [17.3, 3.2],
+            [27.8, 0.1],
+        ];
+        let ls: LineString<_> = input.into();
+        let coords: ExternalArray = ls.into();
+        let transformed: LineString<_> = simplify_rdp_floor_ffi(coords, 100.0, 3).into();
+        assert_eq!(
+            transformed,
+            vec![[0.0, 0.0], [11.0, 5.5], [27.8, 0.1]].into()
+        );
+        let transformed: LineString<_> = simplify_rdp_floor_ffi(coords, 1.0, 2).into();
+        assert_eq!(
+            transformed,
+            vec![[0.0, 0.0], [5.0, 4.0], [11.0, 5.5], [27.8, 0.1]].into()
+        );
+    }
+    #[test]
+    fn test_ffi_rdp_radial() {
+        let track = [
+            [0.0, 0.0],
+            [5.0, 0.0],
+            [5.1, 0.4],
+            [4.8, -0.4],
+            [5.2, 0.3],
+            [10.0, 0.0],
+        ];
+        let coords = external(&track);
+        let radial: LineString<f64> = simplify_rdp_radial_ffi(coords, 1.0, 0.0).into();
+        assert_eq!(radial, vec![[0.0, 0.0], [5.0, 0.0], [10.0, 0.0]].into());
+        let combined: LineString<f64> = simplify_rdp_radial_ffi(coords, 1.0, 0.2).into();
+        let rdp: LineString<f64> = simplify_rdp_ffi(coords, 0.2).into();
+        assert_eq!(combined, vec![[0.0, 0.0], [10.0, 0.0]].into());
+        assert!(combined.0.len() < rdp.0.len());
+    }
+    #[test]
+    fn test_ffi_snap_grid() {
+        // the same boundary, digitised in opposite directions
+        let a = [[0.02, 0.0], [1.0, 0.97], [2.03, 0.04], [3.0, 2.0]];
+        let b = [[3.04, 1.99], [1.98, 0.0], [0.97, 1.03], [0.0, 0.01]];
+        let forward: Vec<[f64; 2]> =
+            unsafe { reclaim(simplify_snap_grid_ffi(external(&a), 0.5, 1.0)) };
+        let mut backward: Vec<[f64; 2]> =
+            unsafe { reclaim(simplify_snap_grid_ffi(external(&b), 0.5, 1.0)) };
+        backward.reverse();
+        assert_eq!(forward, backward);
+        assert_eq!(
+            forward,
+            vec![[0.0, 0.0], [1.0, 1.0], [2.0, 0.0], [3.0, 2.0]]
+        );
+    }
+    #[test]
+    fn test_ffi_dedupe_coords() {
+        let input = [[0.0, 0.0], [0.0, 0.0], [1.0, 1.0], [1.0, 1.0], [1.0, 1.0]];
+        let coords = external(&input);
+        let deduped: LineString<f64> = dedupe_coords_ffi(coords, 0.0).into();
+        assert_eq!(deduped, vec![[0.0, 0.0], [1.0, 1.0]].into());
+        let collapsed: LineString<f64> = dedupe_coords_ffi(coords, 2.0).into();
+        assert_eq!(collapsed, vec![[0.0, 0.0]].into());
+    }
+    #[test]
+    fn test_ffi_simplify_then_smooth() {
+        let input: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let coords = external(&input);
+        let simplified: LineString<f64> = simplify_rdp_ffi(coords, 0.001).into();
+        let unsmoothed: LineString<f64> = simplify_then_smooth_ffi(coords, 0.001, 0).into();
+        assert_eq!(unsmoothed, simplified);
+        let mut previous = simplified.0.len();
+        for iterations in 1..4 {
+            let smoothed: LineString<f64> =
+                simplify_then_smooth_ffi(coords, 0.001, iterations).into();
+            assert!(smoothed.0.len() > previous);
+            previous = smoothed.0.len();
+            assert_eq!(smoothed.0[0], simplified.0[0]);
+            assert_eq!(smoothed.0.last(), simplified.0.last());
+        }
+    }
+    #[test]
+    fn test_ffi_rdp_scaled() {
+        // a line heading north at 60° latitude, where a degree of longitude is half as long as
+        // a degree of latitude
+        let input = [[0.0, 0.0], [0.0, 1.0], [0.8, 2.0], [0.0, 3.0], [0.0, 4.0]];
+        let coords = external(&input);
+        let uniform: LineString<f64> = simplify_rdp_scaled_ffi(coords, 0.5, 1.0, 1.0).into();
+        let scaled: LineString<f64> = simplify_rdp_scaled_ffi(coords, 0.5, 0.5, 1.0).into();
+        assert_eq!(uniform, simplify_rdp_ffi(coords, 0.5).into());
+        assert_eq!(uniform, vec![[0.0, 0.0], [0.8, 2.0], [0.0, 4.0]].into());
+        // scaled, the eastward excursion is within the tolerance
+        assert_eq!(scaled, vec![[0.0, 0.0], [0.0, 4.0]].into());
+        // scaling by 0.3 and back would move the last point in the 15th decimal place
+        let input = [[0.0, 0.0], [0.45, 0.1], [0.9, 0.0]];
+        assert_ne!((0.9f64 * 0.3 / 0.3).to_bits(), 0.9f64.to_bits());
+        let coords = external(&input);
+        let simplified: Vec<[f64; 2]> =
+            unsafe { reclaim(simplify_rdp_scaled_ffi(coords, 1.0, 0.3, 0.3)) };
+        assert_eq!(simplified.len(), 2);
+        assert_eq!(simplified[0].map(f64::to_bits), input[0].map(f64::to_bits));
+        assert_eq!(simplified[1].map(f64::to_bits), input[2].map(f64::to_bits));
+    }
+    #[test]
+    fn test_ffi_rdp_boundaries() {
+        // a line crossing the tile edge at x = 256
+        let input = [
+            [200.0, 10.0],
+            [230.0, 10.5],
+            [255.5, 9.8],
+            [258.0, 10.2],
+            [290.0, 9.9],
+            [320.0, 10.0],
+        ];
+        let coords = external(&input);
+        let edges = [0.0, 256.0, 512.0];
+        let boundaries = external(&edges);
+        let seamed: LineString<f64> = simplify_rdp_boundaries_ffi(coords, 2.0, boundaries).into();
+        assert_eq!(
+            seamed,
+            vec![[200.0, 10.0], [255.5, 9.8], [320.0, 10.0]].into()
+        );
+        let none = ExternalArray {
+            data: ptr::null(),
+            len: 0,
+        };
+        let plain: LineString<f64> = simplify_rdp_boundaries_ffi(coords, 2.0, none).into();
+        assert_eq!(plain, vec![[200.0, 10.0], [320.0, 10.0]].into());
+    }
+    #[test]
+    fn test_ffi_rdp_vartol() {
+        // two identical wiggles, the first in a region with a high tolerance
+        let input = [
+            [0.0, 0.0],
+            [1.0, 0.5],
+            [2.0, 0.0],
+            [3.0, 0.5],
+            [4.0, 0.0],
+            [5.0, 0.5],
+            [6.0, 0.0],
+            [7.0, 0.5],
+            [8.0, 0.0],
+        ];
+        let coords = external(&input);
+        let levels = [1.0, 1.0, 1.0, 1.0, 0.1, 0.1, 0.1, 0.1, 0.1];
+        let tolerances = external(&levels);
+        let simplified: LineString<f64> = simplify_rdp_vartol_ffi(coords, tolerances).into();
+        assert_eq!(
+            simplified,
+            vec![
+                [0.0, 0.0],
+                [4.0, 0.0],
+                [5.0, 0.5],
+                [6.0, 0.0],
+                [7.0, 0.5],
+                [8.0, 0.0]
+            ]
+            .into()
+        );
+        let short = ExternalArray {
+            data: levels.as_ptr() as *const libc::c_void,
+            len: levels.len() - 1,
+        };
+        let mismatched = simplify_rdp_vartol_ffi(coords, short);
+        assert!(mismatched.data.is_null());
+        drop_float_array(mismatched);
+    }
+    #[test]
+    fn test_ffi_rdp_extrema() {
+        // a sparkline with a narrow spike, which is within the tolerance of the baseline
+        let input = [
+            [0.0, 1.0],
+            [1.0, 1.1],
+            [2.0, 1.0],
+            [2.5, 1.9],
+            [3.0, 1.0],
+            [4.0, 1.05],
+            [5.0, 1.0],
+        ];
+        let coords = external(&input);
+        let apex = [2.5, 1.9].into();
+        let plain: LineString<f64> = simplify_rdp_ffi(coords, 1.0).into();
+        assert!(!plain.0.contains(&apex));
+        let extrema: LineString<f64> = simplify_rdp_extrema_ffi(coords, 1.0).into();
+        assert!(extrema.0.contains(&apex));
+        assert_eq!(extrema, vec![[0.0, 1.0], [2.5, 1.9], [5.0, 1.0]].into());
+    }
+    #[test]
+    fn test_ffi_rdp_timed() {
+        let input = [[0.0, 0.0], [1.0, 0.1], [2.0, 0.0], [3.0, 0.1], [4.0, 0.0]];
+        let coords = external(&input);
+        // the third point is geometrically redundant, but precedes a ten-minute stop
+        let stamps = [
+            1.7e9,
+            1.7e9 + 10.0,
+            1.7e9 + 20.0,
+            1.7e9 + 620.0,
+            1.7e9 + 630.0,
+        ];
+        let times = external(&stamps);
+        let plain: LineString<f64> = simplify_rdp_ffi(coords, 1.0).into();
+        assert_eq!(plain, vec![[0.0, 0.0], [4.0, 0.0]].into());
+        let timed: LineString<f64> = simplify_rdp_timed_ffi(coords, times, 1.0, 300.0).into();
+        assert_eq!(
+            timed,
+            vec![[0.0, 0.0], [2.0, 0.0], [3.0, 0.1], [4.0, 0.0]].into()
+        );
+        let short = ExternalArray {
+            data: stamps.as_ptr() as *const libc::c_void,
+            len: 4,
+        };
+        let mismatched = simplify_rdp_timed_ffi(coords, short, 1.0, 300.0);
+        assert!(mismatched.data.is_null());
+        drop_float_array(mismatched);
+    }
+    #[test]
+    fn test_ffi_rdp_pinned() {
+        let input = [
+            [0.0, 0.0],
+            [1.0, 0.1],
+            [2.0, 0.0],
+            [3.0, 0.1],
+            [4.0, 0.0],
+            [5.0, 0.1],
+            [6.0, 0.0],
+        ];
+        let coords = external(&input);
+        // the third point is a junction, which plain RDP removes
+        let flags: [u8; 7] = [0, 0, 1, 0, 0, 0, 0];
+        let pinned = external(&flags);
+        let plain: LineString<f64> = simplify_rdp_ffi(coords, 1.0).into();
+        assert_eq!(plain, vec![[0.0, 0.0], [6.0, 0.0]].into());
+        let simplified: LineString<f64> = simplify_rdp_pinned_ffi(coords, pinned, 1.0).into();
+        assert_eq!(simplified, vec![[0.0, 0.0], [2.0, 0.0], [6.0, 0.0]].into());
+        let short = ExternalArray {
+            data: flags.as_ptr() as *const libc::c_void,
+            len: 6,
+        };
+        let mismatched = simplify_rdp_pinned_ffi(coords, short, 1.0);
+        assert!(mismatched.data.is_null());
+        drop_float_array(mismatched);
+        assert_eq!(
+            last_error().unwrap(),
+            "the input array has 7 points, but 6 flags were given"
+        );
+        let null = ExternalArray {
+            data: ptr::null(),
+            len: 7,
+        };
+        assert!(simplify_rdp_pinned_ffi(coords, null, 1.0).data.is_null());
+        assert_eq!(
+            last_error().unwrap(),
+            "the input array's data field is a null pointer"
+        );
+    }
+    #[test]
+    fn test_ffi_rdp_target_ratio() {
+        let input: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let coords = external(&input);
+        let half = simplify_rdp_target_ratio_ffi(coords, 0.5);
+        assert!(half.len.abs_diff(input.len() / 2) <= 1);
+        drop_float_array(half);
+        let full: Vec<[f64; 2]> = unsafe { reclaim(simplify_rdp_target_ratio_ffi(coords, 1.0)) };
+        assert_eq!(full, input);
+    }
+    #[test]
+    fn test_ffi_best() {
+        let input = [
+            [0.0, 3.8],
+            [2.0, 4.4],
+            [4.0, 3.1],
+            [6.0, 4.2],
+            [8.0, 0.6],
+            [10.0, 2.1],
+            [12.0, 9.8],
+        ];
+        let coords = external(&input);
+        let rdp: LineString<f64> = simplify_rdp_ffi(coords, 2.0).into();
+        assert_eq!(rdp.0.len(), 4);
+        let best: LineString<f64> = simplify_best_ffi(coords, 2.0).into();
+        assert_eq!(best, vec![[0.0, 3.8], [10.0, 2.1], [12.0, 9.8]].into());
+        // only the endpoints are left, so there's nothing for VW to improve on
+        let best: LineString<f64> = simplify_best_ffi(coords, 10.0).into();
+        assert_eq!(best, vec![[0.0, 3.8], [12.0, 9.8]].into());
+    }
+    #[test]
+    fn test_ffi_rdp_significance() {
+        let input: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let coords = external(&input);
+        let significance: Vec<[f64; 2]> = unsafe { reclaim(rdp_significance_ffi(coords)) };
+        assert_eq!(significance.len(), input.len() - 2);
+        for tolerance in [0.0001, 0.0005, 0.001, 0.005, 0.01] {
+            let mut thresholded = vec![0, input.len() - 1];
+            thresholded.extend(
+                significance
+                    .iter()
+                    .filter(|[_, s]| *s > tolerance)
+                    .map(|[idx, _]| *idx as usize),
+            );
+            thresholded.sort_unstable();
+            let expected: Vec<usize> = unsafe { reclaim(simplify_rdp_idx_ffi(coords, tolerance)) };
+            assert_eq!(thresholded, expected, "{}", tolerance);
+        }
+        let short = ExternalArray {
+            data: input.as_ptr() as *const libc::c_void,
+            len: 2,
+        };
+        let empty = rdp_significance_ffi(short);
+        assert!(empty.data.is_null());
+        drop_float_array(empty);
+    }
+    #[test]
+    fn test_ffi_rdp_corners() {
+        // a gentle bend, then a right-angled corner
+        let input = [[0.0, 0.0], [10.0, 0.5], [20.0, 0.0], [20.0, 3.0]];
+        let coords = external(&input);
+        let simplified: LineString<f64> = simplify_rdp_corners_ffi(coords, 5.0, 120.0).into();
+        assert_eq!(
+            simplified,
+            vec![[0.0, 0.0], [20.0, 0.0], [20.0, 3.0]].into()
+        );
+        let rdp: LineString<f64> = simplify_rdp_ffi(coords, 5.0).into();
+        assert_eq!(rdp, vec![[0.0, 0.0], [20.0, 3.0]].into());
+    }
+    #[test]
+    fn test_ffi_rdp_sweep() {
+        let input = [
+            [0.0, 0.0],
+            [5.0, 4.0],
+            [11.0, 5.5],
+            [17.3, 3.2],
+            [27.8, 0.1],
+        ];
+        let tolerances = [0.1, 1.0, 100.0, 2.0];
+        let counts: Vec<usize> = unsafe {
+            reclaim(simplify_rdp_sweep_ffi(
+                external(&input),
+                external(&tolerances),
+            ))
+        };
+        assert_eq!(counts, vec![5, 4, 2, 3]);
+        let invalid = simplify_rdp_sweep_ffi(
+            external(&input),
+            ExternalArray {
+                data: ptr::null(),
+                len: 0,
+            },
+        );
+        assert!(invalid.data.is_null());
+        drop_usize_array(invalid);
+    }
+    #[test]
+    fn test_ffi_rdp_symmetric_simplification() {
+        // (1, 1) and (3, 1) are equally distant from the baseline
+        let input = [[0.0, 0.0], [1.0, 1.0], [2.0, 0.5], [3.0, 1.0], [4.0, 0.0]];
+        let reversed: Vec<[f64; 2]> = input.iter().rev().copied().collect();
+        let forward: LineString<f64> = simplify_rdp_symmetric_ffi(external(&input), 0.6).into();
+        let mut backward: LineString<f64> =
+            simplify_rdp_symmetric_ffi(external(&reversed), 0.6).into();
+        backward.0.reverse();
+        assert_eq!(forward, backward);
+    }
+    #[test]
+    fn test_ffi_rdp_reverse() {
+        let input: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let forward: Vec<[f64; 2]> =
+            unsafe { reclaim(simplify_rdp_reverse_ffi(external(&input), 0.001, 0)) };
+        let mut backward: Vec<[f64; 2]> =
+            unsafe { reclaim(simplify_rdp_reverse_ffi(external(&input), 0.001, 1)) };
+        assert_eq!(forward, api::simplify_rdp_symmetric(&input, 0.001));
+        // simplifying the reversed input gives the same result
+        let reversed: Vec<[f64; 2]> = input.iter().rev().copied().collect();
+        assert_eq!(backward, api::simplify_rdp_symmetric(&reversed, 0.001));
+        backward.reverse();
+        assert_eq!(forward, backward);
+    }
+    #[test]
+    fn test_ffi_rdp_into_simplification() {
+        let input = [
+            [0.0, 0.0],
+            [5.0, 4.0],
+            [11.0, 5.5],
+            [17.3, 3.2],
+            [27.8, 0.1],
+        ];
+        let output = vec![[0.0, 0.0], [5.0, 4.0], [11.0, 5.5], [27.8, 0.1]];
+        let coords = external(&input);
+        let call = |buf: &mut Vec<[f64; 2]>, written: &mut libc::size_t| unsafe {
+            let out = InternalArray {
+                data: buf.as_mut_ptr() as *mut libc::c_void,
+                len: buf.len(),
+            };
+            simplify_rdp_into_ffi(coords, 1.0, out, written)
+        };
+        // exact fit
+        let mut buf = vec![[-1.0; 2]; 4];
+        let mut written = 0;
+        assert_eq!(
+            call(&mut buf, &mut written),
+            FfiError::Success as libc::c_int
+        );
+        assert_eq!(written, 4);
+        assert_eq!(buf, output);
+        // oversized: the tail is left untouched
+        let mut buf = vec![[-1.0; 2]; 6];
+        let mut written = 0;
+        assert_eq!(
+            call(&mut buf, &mut written),
+            FfiError::Success as libc::c_int
+        );
+        assert_eq!(written, 4);
+        assert_eq!(buf[..4], output[..]);
+        assert_eq!(buf[4..], [[-1.0; 2]; 2]);
+        // too small: nothing is written, but the required length is reported
+        let mut buf = vec![[-1.0; 2]; 3];
+        let mut written = 0;
+        assert_eq!(
+            call(&mut buf, &mut written),
+            FfiError::BufferTooSmall as libc::c_int
+        );
+        assert_eq!(written, 4);
+        assert_eq!(buf, vec![[-1.0; 2]; 3]);
+    }
+    #[test]
+    fn test_ffi_rdp_lod() {
+        let input: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let coords = external(&input);
+        let levels = [0.0001, 0.01, 0.001];
+        let tolerances = external(&levels);
+        let mut offsets = InternalArray::empty();
+        let packed: Vec<[f64; 2]> =
+            unsafe { reclaim(simplify_rdp_lod_ffi(coords, tolerances, &mut offsets)) };
+        let bounds: Vec<usize> = unsafe { reclaim(offsets) };
+        assert_eq!(bounds.len(), 4);
+        assert_eq!(bounds[3], packed.len());
+        for (tolerance, bound) in levels.iter().zip(bounds.windows(2)) {
+            let expected: LineString<f64> = simplify_rdp_ffi(coords, *tolerance).into();
+            let level: LineString<f64> = packed[bound[0]..bound[1]].to_vec().into();
+            assert_eq!(level, expected);
+        }
+
+        let invalid = ExternalArray {
+            data: ptr::null(),
+            len: 0,
+        };
+        let mut offsets = InternalArray::empty();
+        let packed = unsafe { simplify_rdp_lod_ffi(invalid, tolerances, &mut offsets) };
+        assert!(packed.data.is_null() && offsets.data.is_null());
+        drop_float_array(packed);
+        drop_usize_array(offsets);
+    }
+    #[test]
+    fn test_ffi_rdp_write() {
+        let input = [
+            [0.0, 0.0],
+            [5.0, 4.0],
+            [11.0, 5.5],
+            [17.3, 3.2],
+            [27.8, 0.1],
+        ];
+        let output = vec![[0.0, 0.0], [5.0, 4.0], [11.0, 5.5], [27.8, 0.1]];
+        let coords = external(&input);
+        let parse = |bytes: &[u8]| -> Vec<[f64; 2]> {
+            let count = u32::from_le_bytes(bytes[..4].try_into().unwrap()) as usize;
+            assert_eq!(bytes.len(), 4 + 16 * count);
+            bytes[4..]
+                .chunks_exact(16)
+                .map(|point| {
+                    [
+                        f64::from_le_bytes(point[..8].try_into().unwrap()),
+                        f64::from_le_bytes(point[8..].try_into().unwrap()),
+                    ]
+                })
+                .collect()
+        };
+        // exact fit, at an unaligned offset
+        let mut buf = [0xff_u8; 1 + 4 + 16 * 4];
+        let mut written = 0;
+        let error = unsafe {
+            simplify_rdp_write_ffi(
+                coords,
+                1.0,
+                buf[1..].as_mut_ptr(),
+                buf.len() - 1,
+                &mut written,
+            )
+        };
+        assert_eq!(error, FfiError::Success as libc::c_int);
+        assert_eq!(written, 68);
+        assert_eq!(buf[0], 0xff);
+        assert_eq!(buf[1..5], [4, 0, 0, 0]);
+        assert_eq!(parse(&buf[1..]), output);
+        // too small: nothing is written, but the required capacity is reported
+        let mut buf = [0xff_u8; 67];
+        let mut written = 0;
+        let error = unsafe {
+            simplify_rdp_write_ffi(coords, 1.0, buf.as_mut_ptr(), buf.len(), &mut written)
+        };
+        assert_eq!(error, FfiError::BufferTooSmall as libc::c_int);
+        assert_eq!(written, 68);
+        assert!(buf.iter().all(|&byte| byte == 0xff));
+    }
+    #[test]
+    fn test_ffi_rdp_into_invalid() {
+        let mut buf = vec![[0.0; 2]; 4];
+        let out = InternalArray {
+            data: buf.as_mut_ptr() as *mut libc::c_void,
+            len: buf.len(),
+        };
+        let coords = ExternalArray {
+            data: ptr::null(),
+            len: 4,
+        };
+        let mut written = 1;
+        let error = unsafe { simplify_rdp_into_ffi(coords, 1.0, out, &mut written) };
+        assert_eq!(error, FfiError::NullPointer as libc::c_int);
+        assert_eq!(written, 0);
+    }
+    #[test]
+    fn test_ffi_dispatch() {
+        let input = vec![
+            [5.0, 2.0],
+            [3.0, 8.0],
+            [6.0, 20.0],
+            [7.0, 25.0],
+            [10.0, 10.0],
+        ];
+        let ls: LineString<_> = input.clone().into();
+        let cases = [
+            (Algorithm::Rdp, ls.simplify(&30.0)),
+            (Algorithm::Visvalingam, ls.simplify_vw(&30.0)),
+            (
+                Algorithm::VisvalingamPreserve,
+                ls.simplify_vw_preserve(&30.0),
+            ),
+        ];
+        for (algorithm, expected) in cases {
+            let coords = external(&input);
+            let result: LineString<_> = simplify_ffi(coords, 30.0, algorithm as libc::c_int).into();
+            assert_eq!(result, expected, "{:?}", algorithm);
+        }
+    }
+    #[test]
+    fn test_ffi_dispatch_unknown_algorithm() {
+        let input = [[0.0, 0.0], [1.0, 1.0], [2.0, 0.0]];
+        let coords = external(&input);
+        for algorithm in [-1, 3] {
+            let result = simplify_ffi(coords, 1.0, algorithm);
+            assert!(result.data.is_null());
+            assert_eq!(result.len, 0);
+        }
+    }
+    #[test]
+    fn test_ffi_rdp_idx_simplification() {
+        let input = vec![
+            [0.0, 0.0],
+            [5.0, 4.0],
+            [11.0, 5.5],
+            [17.3, 3.2],
+            [27.8, 0.1],
+        ];
+        let ls: LineString<_> = input.into();
+        // let output = vec![[0.0, 0.0], [5.0, 4.0], [11.0, 5.5], [27.8, 0.1]];
+        let output = vec![0, 1, 2, 4];
+        let transformed: Vec<usize> = simplify_rdp_idx_ffi(ls.into(), 1.0).into();
+        assert_eq!(transformed, output);
+    }
+    #[test]
+    fn test_ffi_rdp_removed_idx() {
+        let input: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let coords = external(&input);
+        let retained: Vec<usize> = simplify_rdp_idx_ffi(coords, 0.001).into();
+        let removed: Vec<usize> = simplify_rdp_removed_idx_ffi(coords, 0.001).into();
+        assert!(!removed.is_empty());
+        assert!(removed.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(removed.iter().all(|idx| !retained.contains(idx)));
+        let mut all: Vec<usize> = retained.into_iter().chain(removed).collect();
+        all.sort_unstable();
+        assert_eq!(all, (0..input.len()).collect::<Vec<_>>());
+    }
+    #[test]
+    fn test_ffi_rdp_segment_stats() {
+        let input: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let coords = external(&input);
+        let simplified = simplify_rdp_ffi(coords, 0.001);
+        let counts: Vec<usize> = simplify_rdp_segment_stats_ffi(coords, 0.001).into();
+        assert_eq!(counts.len(), simplified.len - 1);
+        assert_eq!(counts.iter().sum::<usize>(), input.len() - simplified.len);
+        drop_float_array(simplified);
+    }
+    #[test]
+    fn test_ffi_rdp_series() {
+        // hourly readings of a daily cycle, with noise, against time in days
+        let input: Vec<[f64; 2]> = (0..240)
+            .map(|hour| {
+                let t = f64::from(hour) / 24.0;
+                let noise = f64::from(hour * 7 % 11) / 10.0;
+                [t, 20.0 + 5.0 * (t * std::f64::consts::TAU).sin() + noise]
+            })
+            .collect();
+        let coords = external(&input);
+        // the greatest vertical distance of an input point from a simplified series
+        let deviation = |simplified: &[[f64; 2]]| {
+            input
+                .iter()
+                .map(|&point| {
+                    let segment = simplified
+                        .windows(2)
+                        .find(|pair| pair[1][0] >= point[0])
+                        .unwrap();
+                    douglas_peucker::vertical_distance(point, segment[0], segment[1])
+                })
+                .fold(0.0, f64::max)
+        };
+        let series: Vec<[f64; 2]> = unsafe { reclaim(simplify_rdp_series_ffi(coords, 1.0)) };
+        let perpendicular: Vec<[f64; 2]> = unsafe { reclaim(simplify_rdp_ffi(coords, 1.0)) };
+        assert!(series.windows(2).all(|pair| pair[0][0] < pair[1][0]));
+        assert!(deviation(&series) <= 1.0);
+        // the series is steep on this scale, so perpendicular distances are much smaller than
+        // vertical ones, and the perpendicular version strays further from the values
+        assert!(perpendicular.len() < series.len());
+        assert!(deviation(&perpendicular) > 1.0);
+
+        let backwards = [[0.0, 0.0], [2.0, 1.0], [1.0, 2.0]];
+        let coords = external(&backwards);
+        let empty = simplify_rdp_series_ffi(coords, 1.0);
+        assert!(empty.data.is_null());
+        drop_float_array(empty);
+        assert_eq!(
+            last_error().unwrap(),
+            "the x coordinate of point 2 of the input array isn't greater than that of point 1"
+        );
+    }
+    #[test]
+    fn test_ffi_rdp_mask() {
+        let input: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let coords = external(&input);
+        let retained: Vec<usize> = simplify_rdp_idx_ffi(coords, 0.001).into();
+        let mask: Vec<u8> = unsafe { reclaim(simplify_rdp_mask_ffi(coords, 0.001)) };
+        assert_eq!(mask.len(), input.len());
+        assert!(mask.iter().all(|&keep| keep <= 1));
+        let set: Vec<usize> = (0..mask.len()).filter(|&idx| mask[idx] == 1).collect();
+        assert_eq!(set, retained);
+        let invalid = simplify_rdp_mask_ffi(
+            ExternalArray {
+                data: ptr::null(),
+                len: 0,
+            },
+            0.001,
+        );
+        assert!(invalid.data.is_null());
+        drop_u8_array(invalid);
+    }
+    #[test]
+    fn test_ffi_visvalingam_simplification() {
+        let input = vec![
+            [5.0, 2.0],
+            [3.0, 8.0],
+            [6.0, 20.0],
+            [7.0, 25.0],
+            [10.0, 10.0],
+        ];
+        let ls: LineString<_> = input.into();
+        let output = vec![[5.0, 2.0], [7.0, 25.0], [10.0, 10.0]];
+        let transformed: LineString<_> = simplify_visvalingam_ffi(ls.into(), 30.0).into();
+        assert_eq!(transformed, output.into());
+    }
+    #[test]
+    fn test_ffi_visvalingam_idx_simplification() {
+        let input = vec![
+            [5.0, 2.0],
+            [3.0, 8.0],
+            [6.0, 20.0],
+            [7.0, 25.0],
+            [10.0, 10.0],
+        ];
+        let ls: LineString<_> = input.into();
+        // let output = vec![[5.0, 2.0], [7.0, 25.0], [10.0, 10.0]];
+        let output = vec![0, 3, 4];
+        let transformed: Vec<usize> = simplify_visvalingam_idx_ffi(ls.into(), 30.0).into();
+        assert_eq!(transformed, output);
+    }
+    #[test]
+    fn test_ffi_idx_match_geo() {
+        use geo::simplify::SimplifyIdx;
+        use geo::simplify_vw::SimplifyVwIdx;
+
+        // the coordinates are borrowed, rather than copied into a LineString, but the indices are
+        // the same as geo's
+        let input: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let ls: LineString<f64> = input.clone().into();
+        let coords = external(&input);
+        for epsilon in [0.0, 0.00001, 0.001] {
+            let rdp: Vec<usize> = unsafe { reclaim(simplify_rdp_idx_ffi(coords, epsilon)) };
+            assert_eq!(rdp, ls.simplify_idx(&epsilon));
+        }
+        for epsilon in [0.0, 0.0000075, 0.001] {
+            let vw: Vec<usize> = unsafe { reclaim(simplify_visvalingam_idx_ffi(coords, epsilon)) };
+            assert_eq!(vw, ls.simplify_vw_idx(&epsilon));
+        }
+    }
+    #[test]
+    fn test_ffi_visvalingamp_simplification() {
+        let input = vec![
+            [5.0, 2.0],
+            [3.0, 8.0],
+            [6.0, 20.0],
+            [7.0, 25.0],
+            [10.0, 10.0],
+        ];
+        let ls: LineString<_> = input.into();
+        let output = vec![[5.0, 2.0], [7.0, 25.0], [10.0, 10.0]];
+        let transformed: LineString<_> = simplify_visvalingamp_ffi(ls.into(), 30.0).into();
+        assert_eq!(transformed, output.into());
+    }
+    #[test]
+    fn test_ffi_visvalingamp_idx_simplification() {
+        let input = vec![
+            [10., 60.],
+            [135., 68.],
+            [94., 48.],
+            [126., 31.],
+            [280., 19.],
+            [117., 48.],
+            [300., 40.],
+            [301., 10.],
+        ];
+        let ls: LineString<_> = input.into();
+        // let output = vec![[10., 60.], [126., 31.], [280., 19.], [117., 48.], [300., 40.], [301., 10.]];
+        let output = vec![0, 3, 4, 5, 6, 7];
+        let transformed: Vec<usize> = simplify_visvalingamp_idx_ffi(ls.into(), 668.6).into();
+        assert_eq!(transformed, output);
+        // (2, 6) appears twice, and it's the second which is retained
+        let repeated = vec![
+            [0.0, 1.0],
+            [2.0, 6.0],
+            [5.0, 1.0],
+            [2.0, 6.0],
+            [2.0, 5.0],
+            [1.0, 1.0],
+        ];
+        let ls: LineString<_> = repeated.into();
+        let transformed: Vec<usize> = simplify_visvalingamp_idx_ffi(ls.into(), 1.5).into();
+        assert_eq!(transformed, vec![0, 3, 5]);
+    }
+    #[test]
+    fn test_visvalingamp_idx_large() {
+        use geo::simplify_vw::SimplifyVwPreserve;
+        use std::time::Instant;
+        // a long, tangled line, many of whose removals would cause crossings
+        let input: Vec<[f64; 2]> = (0..20_000)
+            .map(|i| {
+                let t = i as f64;
+                [t * 0.01 + 5.0 * (t * 0.37).sin(), 5.0 * (t * 0.53).cos()]
+            })
+            .collect();
+        let epsilon = 0.05;
+        let start = Instant::now();
+        let expected: Vec<[f64; 2]> = LineString::from(input.clone())
+            .simplify_vw_preserve(&epsilon)
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        let geo_elapsed = start.elapsed();
+        let start = Instant::now();
+        let api_idx = api::simplify_vw_preserve_idx(&input, epsilon);
+        let coords = external(&input);
+        let ffi_idx: Vec<usize> = simplify_visvalingamp_idx_ffi(coords, epsilon).into();
+        let elapsed = start.elapsed();
+        assert_eq!(api_idx, ffi_idx);
+        let retained: Vec<[f64; 2]> = api_idx.iter().map(|&idx| input[idx]).collect();
+        assert_eq!(retained, expected);
+        // both calls should take about as long as geo, whose crossing checks are O(log n): a
+        // scan of the whole line for each removal takes dozens of times longer at this size
+        assert!(
+            elapsed < geo_elapsed * 10,
+            "{:?} against geo's {:?}",
+            elapsed,
+            geo_elapsed
+        );
+    }
+    #[test]
+    fn test_ffi_visvalingam_epsilon() {
+        // Pin the retained vertices of every VW function, so that a change in the way geo takes or
+        // interprets its epsilon changes our results loudly. The epsilon is an area, and a point is
+        // removed unless its effective area (here 2, 2 and 1) is strictly greater than it
+        let input = [[0.0, 0.0], [1.0, 2.0], [2.0, 0.0], [3.0, 1.0], [4.0, 0.0]];
+        let input_f32 = input.map(|[x, y]| [x as f32, y as f32]);
+        let cases: [(f64, &[usize]); 6] = [
+            (0.0, &[0, 1, 2, 3, 4]),
+            (0.999, &[0, 1, 2, 3, 4]),
+            (1.0, &[0, 1, 2, 4]),
+            (1.999, &[0, 1, 2, 4]),
+            (2.0, &[0, 4]),
+            (100.0, &[0, 4]),
+        ];
+        let coords = external(&input);
+        let coords_f32 = external(&input_f32);
+        for (epsilon, retained) in cases {
+            let expected: LineString<f64> = retained
+                .iter()
+                .map(|&idx| input[idx])
+                .collect::<Vec<_>>()
+                .into();
+            let expected_f32: LineString<f32> = retained
+                .iter()
+                .map(|&idx| input_f32[idx])
+                .collect::<Vec<_>>()
+                .into();
+            let vw: LineString<f64> = simplify_visvalingam_ffi(coords, epsilon).into();
+            let vwp: LineString<f64> = simplify_visvalingamp_ffi(coords, epsilon).into();
+            let vw_idx: Vec<usize> = simplify_visvalingam_idx_ffi(coords, epsilon).into();
+            let vwp_idx: Vec<usize> = simplify_visvalingamp_idx_ffi(coords, epsilon).into();
+            let vw_f32: LineString<f32> =
+                simplify_visvalingam_ffi_f32(coords_f32, epsilon as f32).into();
+            let vwp_f32: LineString<f32> =
+                simplify_visvalingamp_ffi_f32(coords_f32, epsilon as f32).into();
+            assert_eq!(vw, expected, "{}", epsilon);
+            assert_eq!(vwp, expected, "{}", epsilon);
+            assert_eq!(vw_idx, retained, "{}", epsilon);
+            assert_eq!(vwp_idx, retained, "{}", epsilon);
+            assert_eq!(vw_f32, expected_f32, "{}", epsilon);
+            assert_eq!(vwp_f32, expected_f32, "{}", epsilon);
+        }
+    }
+    #[test]
+    fn test_ffi_visvalingam_simplification_f32() {
+        let input: Vec<[f32; 2]> = vec![
+            [5.0, 2.0],
+            [3.0, 8.0],
+            [6.0, 20.0],
+            [7.0, 25.0],
+            [10.0, 10.0],
+        ];
+        let ls: LineString<f32> = input.into();
+        let output: Vec<[f32; 2]> = vec![[5.0, 2.0], [7.0, 25.0], [10.0, 10.0]];
+        let transformed: LineString<f32> = simplify_visvalingam_ffi_f32(ls.into(), 30.0).into();
+        assert_eq!(transformed, output.into());
+    }
+    #[test]
+    fn test_ffi_visvalingamp_simplification_f32() {
+        let input: Vec<[f32; 2]> = vec![
+            [5.0, 2.0],
+            [3.0, 8.0],
+            [6.0, 20.0],
+            [7.0, 25.0],
+            [10.0, 10.0],
+        ];
+        let ls: LineString<f32> = input.into();
+        let output: Vec<[f32; 2]> = vec![[5.0, 2.0], [7.0, 25.0], [10.0, 10.0]];
+        let transformed: LineString<f32> = simplify_visvalingamp_ffi_f32(ls.into(), 30.0).into();
+        assert_eq!(transformed, output.into());
+    }
+    #[test]
+    fn test_ffi_rdp_3d_simplification() {
+        // the second point lies close to the line in 2D, but far from it in z
+        let input = [
+            [0.0, 0.0, 10.0],
+            [5.0, 0.5, 250.0],
+            [10.0, 0.0, 20.0],
+            [15.0, 5.0, 30.0],
+        ];
+        let arr = external(&input);
+        let transformed: Vec<[f64; 3]> = simplify_rdp_3d_ffi(arr, 1.0).into();
+        assert_eq!(
+            transformed,
+            vec![[0.0, 0.0, 10.0], [10.0, 0.0, 20.0], [15.0, 5.0, 30.0]]
+        );
+        let transformed: Vec<[f64; 3]> = simplify_rdp_3d_z_ffi(arr, 1.0).into();
+        assert_eq!(transformed, input.to_vec());
+        drop_float_array_3d(simplify_rdp_3d_ffi(arr, 1.0));
+    }
+    #[test]
+    fn test_ffi_null_pointer() {
+        let arr = ExternalArray {
+            data: ptr::null(),
+            len: 5,
+        };
+        let result = simplify_rdp_checked_ffi(arr, 1.0);
+        assert_eq!(result.error, FfiError::NullPointer);
+        assert!(result.array.data.is_null());
+        assert_eq!(result.array.len, 0);
+        drop_float_array(result.array);
+        // the unchecked functions return an empty array, too
+        let arr = ExternalArray {
+            data: ptr::null(),
+            len: 5,
+        };
+        let result = simplify_visvalingam_idx_ffi(arr, 1.0);
+        assert!(result.data.is_null());
+        assert_eq!(result.len, 0);
+    }
+    #[test]
+    fn test_ffi_zero_length() {
+        let input: Vec<[f64; 2]> = vec![];
+        let arr = external(&input);
+        let result = simplify_visvalingamp_checked_ffi(arr, 1.0);
+        assert_eq!(result.error, FfiError::ZeroLength);
+        assert!(result.array.data.is_null());
+    }
+    #[test]
+    fn test_ffi_nan_coordinates() {
+        let input = vec![[0.0, 0.0], [5.0, f64::NAN], [27.8, 0.1]];
+        let ls: LineString<_> = input.into();
+        let result = simplify_rdp_idx_checked_ffi(ls.into(), 1.0);
+        assert_eq!(result.error, FfiError::NonFiniteCoordinate);
+        assert!(result.array.data.is_null());
+        assert_eq!(result.array.len, 0);
+    }
+    #[test]
+    fn test_ffi_checked_success() {
+        let input = vec![
+            [0.0, 0.0],
+            [5.0, 4.0],
+            [11.0, 5.5],
+            [17.3, 3.2],
+            [27.8, 0.1],
+        ];
+        let ls: LineString<_> = input.into();
+        let output = vec![[0.0, 0.0], [5.0, 4.0], [11.0, 5.5], [27.8, 0.1]];
+        let result = simplify_rdp_checked_ffi(ls.into(), 1.0);
+        assert_eq!(result.error, FfiError::Success);
+        let transformed: LineString<_> = result.array.into();
+        assert_eq!(transformed, output.into());
+    }
+    #[test]
+    fn test_drop_empty_float_array() {
+        let original = vec![[1.0, 2.0], [3.0, 4.0]];
+        let ls: LineString<_> = original.into();
+        // move into an Array, and leak it
+        let mut arr: InternalArray = ls.into();
+        // zero Array contents
+        arr.data = ptr::null_mut();
+        drop_float_array(arr);
+    }
+}
@@ -0,0 +1,497 @@
+//! Simplification of geometries which are passed across the FFI boundary in serialised form
+
+use std::ffi::{CStr, CString};
+use std::fmt::Write;
+use std::ptr;
+
+use geo::simplify::Simplify;
+use geo::LineString;
+use wkt::{ToWkt, TryFromWkt};
+
+use crate::api::{self, simplify_with};
+use crate::ffi::{guarded, set_last_error};
+#[cfg(feature = "wkb")]
+use crate::ffi::{leak, reclaim};
+use crate::ExternalArray;
+#[cfg(feature = "wkb")]
+use crate::InternalArray;
+
+// Read a borrowed C string, returning None if it's null or not valid UTF-8
+unsafe fn read_cstr<'a>(s: *const libc::c_char) -> Option<&'a str> {
+    if s.is_null() {
+        set_last_error("the input string is a null pointer");
+        return None;
+    }
+    CStr::from_ptr(s)
+        .to_str()
+        .map_err(|e| set_last_error(format!("the input string isn't valid UTF-8: {e}")))
+        .ok()
+}
+
+// Leak a String across the FFI boundary as a C string
+fn into_raw_cstring(s: String) -> *mut libc::c_char {
+    CString::new(s).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// FFI wrapper for RDP, accepting and returning [WKT](https://en.wikipedia.org/wiki/Well-known_text_representation_of_geometry)
+///
+/// Callers must pass two arguments:
+///
+/// - a pointer to a nul-terminated, UTF-8 string containing a WKT `LINESTRING`: `LINESTRING(1.0 2.0, ...)`
+/// - a double-precision `float` for the tolerance
+///
+/// The simplified geometry is returned as a newly allocated, nul-terminated WKT string.
+/// If the input is a null pointer, isn't valid UTF-8, or isn't a valid WKT `LINESTRING`,
+/// a null pointer is returned.
+///
+/// Implementations calling this function **must** call [`drop_cstring`](fn.drop_cstring.html)
+/// with the returned pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub unsafe extern "C" fn simplify_rdp_wkt_ffi(
+    wkt: *const libc::c_char,
+    precision: libc::c_double,
+) -> *mut libc::c_char {
+    guarded(ptr::null_mut(), || {
+        read_cstr(wkt)
+            .and_then(|s| {
+                LineString::<f64>::try_from_wkt_str(s)
+                    .map_err(|e| {
+                        set_last_error(format!("the input isn't a valid WKT LINESTRING: {e}"))
+                    })
+                    .ok()
+            })
+            .map_or(ptr::null_mut(), |ls| {
+                into_raw_cstring(simplify_with(ls, |ls| ls.simplify(&precision)).wkt_string())
+            })
+    })
+}
+
+/// FFI wrapper for RDP, accepting and returning [GeoJSON](https://geojson.org)
+///
+/// Callers must pass two arguments:
+///
+/// - a pointer to a nul-terminated, UTF-8 string containing either a GeoJSON `LineString` geometry,
+///   or a `Feature` whose geometry is a `LineString`
+/// - a double-precision `float` for the tolerance
+///
+/// The result is returned as a newly allocated, nul-terminated GeoJSON string of the same kind as
+/// the input: a `Feature` input retains its properties. If the input is a null pointer, isn't
+/// valid GeoJSON, or doesn't contain a `LineString`, a null pointer is returned.
+///
+/// This function is only available if the `geojson` feature is enabled.
+///
+/// Implementations calling this function **must** call [`drop_cstring`](fn.drop_cstring.html)
+/// with the returned pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[cfg(feature = "geojson")]
+#[no_mangle]
+pub unsafe extern "C" fn simplify_rdp_geojson_ffi(
+    json: *const libc::c_char,
+    precision: libc::c_double,
+) -> *mut libc::c_char {
+    use geojson::{GeoJson, Geometry, Value};
+
+    // Simplify a LineString geometry, rejecting any other geometry type
+    let simplify = |geometry: Geometry| match geometry.value {
+        Value::LineString(_) => {
+            LineString::<f64>::try_from(geometry.value)
+                .ok()
+                .map(|ls| Geometry {
+                    value: Value::from(&simplify_with(ls, |ls| ls.simplify(&precision))),
+                    ..geometry
+                })
+        }
+        _ => {
+            set_last_error(format!(
+                "the input GeoJSON contains a {}, not a LineString",
+                geometry.value.type_name()
+            ));
+            None
+        }
+    };
+    guarded(ptr::null_mut(), || {
+        let parsed = read_cstr(json).and_then(|s| {
+            s.parse::<GeoJson>()
+                .map_err(|e| set_last_error(format!("the input isn't valid GeoJSON: {e}")))
+                .ok()
+        });
+        let simplified = match parsed {
+            Some(GeoJson::Geometry(geometry)) => simplify(geometry).map(GeoJson::from),
+            Some(GeoJson::Feature(mut feature)) => match feature.geometry.take() {
+                Some(geometry) => simplify(geometry).map(|geometry| {
+                    feature.geometry = Some(geometry);
+                    GeoJson::from(feature)
+                }),
+                None => {
+                    set_last_error("the input GeoJSON Feature has no geometry");
+                    None
+                }
+            },
+            Some(GeoJson::FeatureCollection(_)) => {
+                set_last_error("the input GeoJSON is a FeatureCollection, not a LineString");
+                None
+            }
+            None => None,
+        };
+        simplified.map_or(ptr::null_mut(), |gj| into_raw_cstring(gj.to_string()))
+    })
+}
+
+/// FFI wrapper for RDP, accepting and returning [WKB](https://libgeos.org/specifications/wkb/)
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to the bytes of a two-dimensional WKB `LineString`, in either byte order
+///     - `len`, the number of bytes being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+///
+/// The simplified geometry is returned as the bytes of a WKB `LineString`, using the same byte
+/// order as the input. If the input isn't valid WKB, isn't a `LineString`, has Z or M
+/// coordinates, or has a NaN or infinite coordinate, an empty array is returned.
+///
+/// This function is only available if the `wkb` feature is enabled.
+///
+/// Implementations calling this function **must** call [`drop_byte_array`](fn.drop_byte_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[cfg(feature = "wkb")]
+#[no_mangle]
+pub unsafe extern "C" fn simplify_rdp_wkb_ffi(
+    wkb: ExternalArray,
+    precision: libc::c_double,
+) -> InternalArray {
+    use geo_traits::{CoordTrait, Dimensions, GeometryTrait, GeometryType, LineStringTrait};
+    use wkb::writer::{write_line_string, WriteOptions};
+    use wkb::Endianness;
+
+    if wkb.data.is_null() || wkb.len == 0 {
+        set_last_error("the input WKB is empty");
+        return InternalArray::empty();
+    }
+    let bytes = std::slice::from_raw_parts(wkb.data as *const u8, wkb.len);
+    guarded(InternalArray::empty(), || {
+        let parsed = match wkb::reader::read_wkb(bytes) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                set_last_error(format!("the input isn't valid WKB: {e}"));
+                return InternalArray::empty();
+            }
+        };
+        let GeometryType::LineString(line) = parsed.as_type() else {
+            set_last_error("the input WKB isn't a LineString");
+            return InternalArray::empty();
+        };
+        if line.dim() != Dimensions::Xy {
+            set_last_error("the input WKB LineString isn't two-dimensional");
+            return InternalArray::empty();
+        }
+        let coords: Vec<[f64; 2]> = line.coords().map(|c| [c.x(), c.y()]).collect();
+        if let Some(idx) = coords.iter().position(|c| !c.iter().all(|v| v.is_finite())) {
+            set_last_error(format!(
+                "point {idx} of the input WKB has a NaN or infinite coordinate"
+            ));
+            return InternalArray::empty();
+        }
+        // the first byte of any valid WKB geometry is its byte order
+        let options = WriteOptions {
+            endianness: Endianness::try_from(bytes[0]).unwrap_or_default(),
+        };
+        let simplified = LineString::from(api::simplify_rdp(&coords, precision));
+        let mut out = Vec::with_capacity(wkb::writer::line_string_wkb_size(&simplified));
+        match write_line_string(&mut out, &simplified, &options) {
+            Ok(()) => leak(out),
+            Err(e) => {
+                set_last_error(format!(
+                    "the simplified geometry couldn't be written as WKB: {e}"
+                ));
+                InternalArray::empty()
+            }
+        }
+    })
+}
+
+/// FFI wrapper for RDP, returning simplified geometry **coordinates** as CSV text
+///
+/// Callers must pass three arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+/// - a `bool`: if it's true, the text begins with an `x,y` header row
+///
+/// The simplified geometry is returned as a newly allocated, nul-terminated string, with one
+/// `x,y` row per point, each terminated by `\n`. Coordinates are written with as many digits as
+/// are needed to parse them back to the same `double`s. Empty input produces only the header row
+/// (or an empty string, without it). If the input is otherwise invalid, a null pointer is returned.
+///
+/// Implementations calling this function **must** call [`drop_cstring`](fn.drop_cstring.html)
+/// with the returned pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub unsafe extern "C" fn simplify_rdp_csv_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+    header: bool,
+) -> *mut libc::c_char {
+    let mut csv = String::from(if header { "x,y\n" } else { "" });
+    if coords.len == 0 {
+        return into_raw_cstring(csv);
+    }
+    let Ok(points) = coords.validate::<f64, 2>() else {
+        return ptr::null_mut();
+    };
+    guarded(ptr::null_mut(), || {
+        for [x, y] in api::simplify_rdp(points, precision) {
+            // writing to a String can't fail
+            let _ = writeln!(csv, "{x},{y}");
+        }
+        into_raw_cstring(csv)
+    })
+}
+
+/// Free memory which has been allocated across the FFI boundary by:
+/// - simplify_rdp_wkt_ffi
+/// - simplify_rdp_geojson_ffi
+/// - simplify_rdp_csv_ffi
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub unsafe extern "C" fn drop_cstring(s: *mut libc::c_char) {
+    if s.is_null() {
+        return;
+    }
+    drop(CString::from_raw(s));
+}
+
+/// Free memory which has been allocated across the FFI boundary by:
+/// - simplify_rdp_wkb_ffi
+///
+/// This function is only available if the `wkb` feature is enabled.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[cfg(feature = "wkb")]
+#[no_mangle]
+pub unsafe extern "C" fn drop_byte_array(arr: InternalArray) {
+    drop(reclaim::<u8>(arr));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffi::external;
+
+    // Call a C string-returning function, and take ownership of the result
+    fn call<F>(input: &str, f: F) -> Option<String>
+    where
+        F: Fn(*const libc::c_char) -> *mut libc::c_char,
+    {
+        let input = CString::new(input).unwrap();
+        let output = f(input.as_ptr());
+        if output.is_null() {
+            return None;
+        }
+        let result = unsafe { CStr::from_ptr(output) }
+            .to_str()
+            .unwrap()
+            .to_owned();
+        unsafe { drop_cstring(output) };
+        Some(result)
+    }
+
+    #[test]
+    fn test_ffi_rdp_wkt_simplification() {
+        let input = "LINESTRING(0 0,5 4,11 5.5,17.3 3.2,27.8 0.1)";
+        let output = call(input, |s| unsafe { simplify_rdp_wkt_ffi(s, 1.0) }).unwrap();
+        assert_eq!(output, "LINESTRING(0 0,5 4,11 5.5,27.8 0.1)");
+        // the output can be fed back in
+        let again = call(&output, |s| unsafe { simplify_rdp_wkt_ffi(s, 1.0) }).unwrap();
+        assert_eq!(again, output);
+    }
+    #[cfg(feature = "geojson")]
+    #[test]
+    fn test_ffi_rdp_geojson_simplification() {
+        use geojson::{Feature, GeoJson, Value};
+        let input = r#"{
+            "type": "Feature",
+            "properties": {"name": "route"},
+            "geometry": {
+                "type": "LineString",
+                "coordinates": [[0.0, 0.0], [5.0, 4.0], [11.0, 5.5], [17.3, 3.2], [27.8, 0.1]]
+            }
+        }"#;
+        let output = call(input, |s| unsafe { simplify_rdp_geojson_ffi(s, 1.0) }).unwrap();
+        let feature = Feature::try_from(output.parse::<GeoJson>().unwrap()).unwrap();
+        assert_eq!(feature.property("name").unwrap(), "route");
+        assert_eq!(
+            feature.geometry.unwrap().value,
+            Value::LineString(vec![
+                vec![0.0, 0.0],
+                vec![5.0, 4.0],
+                vec![11.0, 5.5],
+                vec![27.8, 0.1]
+            ])
+        );
+        // bare geometries are accepted, too
+        let input =
+            r#"{"type": "LineString", "coordinates": [[0.0, 0.0], [1.0, 0.1], [2.0, 0.0]]}"#;
+        let output = call(input, |s| unsafe { simplify_rdp_geojson_ffi(s, 1.0) }).unwrap();
+        assert_eq!(
+            output.parse::<GeoJson>().unwrap(),
+            r#"{"type": "LineString", "coordinates": [[0.0, 0.0], [2.0, 0.0]]}"#
+                .parse::<GeoJson>()
+                .unwrap()
+        );
+    }
+    #[cfg(feature = "geojson")]
+    #[test]
+    fn test_ffi_rdp_geojson_invalid() {
+        let polygon = r#"{
+            "type": "Polygon",
+            "coordinates": [[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]]
+        }"#;
+        assert!(call(polygon, |s| unsafe { simplify_rdp_geojson_ffi(s, 1.0) }).is_none());
+        let last_error = unsafe { CStr::from_ptr(crate::rdp_last_error_message()) };
+        assert_eq!(
+            last_error.to_str().unwrap(),
+            "the input GeoJSON contains a Polygon, not a LineString"
+        );
+        assert!(call("{\"type\": ", |s| unsafe {
+            simplify_rdp_geojson_ffi(s, 1.0)
+        })
+        .is_none());
+    }
+    // Encode a two-dimensional WKB geometry of the given type code, in either byte order
+    #[cfg(feature = "wkb")]
+    fn to_wkb(kind: u32, coords: &[[f64; 2]], big_endian: bool) -> Vec<u8> {
+        let mut bytes = vec![u8::from(!big_endian)];
+        let mut push = |word: &[u8]| bytes.extend_from_slice(word);
+        let (kind, len) = (kind, coords.len() as u32);
+        if big_endian {
+            push(&kind.to_be_bytes());
+            push(&len.to_be_bytes());
+            coords.iter().flatten().for_each(|v| push(&v.to_be_bytes()));
+        } else {
+            push(&kind.to_le_bytes());
+            push(&len.to_le_bytes());
+            coords.iter().flatten().for_each(|v| push(&v.to_le_bytes()));
+        }
+        bytes
+    }
+    #[cfg(feature = "wkb")]
+    fn call_wkb(input: &[u8], precision: f64) -> Vec<u8> {
+        let wkb = external(input);
+        unsafe { reclaim(simplify_rdp_wkb_ffi(wkb, precision)) }
+    }
+    #[cfg(feature = "wkb")]
+    #[test]
+    fn test_ffi_rdp_wkb() {
+        let input = [
+            [0.0, 0.0],
+            [5.0, 4.0],
+            [11.0, 5.5],
+            [17.3, 3.2],
+            [27.8, 0.1],
+        ];
+        let output = [[0.0, 0.0], [5.0, 4.0], [11.0, 5.5], [27.8, 0.1]];
+        for big_endian in [false, true] {
+            let simplified = call_wkb(&to_wkb(2, &input, big_endian), 1.0);
+            assert_eq!(simplified, to_wkb(2, &output, big_endian));
+        }
+    }
+    #[cfg(feature = "wkb")]
+    #[test]
+    fn test_ffi_rdp_wkb_invalid() {
+        // a Point has no length field
+        let point: Vec<u8> = [
+            &[1][..],
+            &1u32.to_le_bytes(),
+            &1f64.to_le_bytes(),
+            &2f64.to_le_bytes(),
+        ]
+        .concat();
+        assert!(call_wkb(&point, 1.0).is_empty());
+        // a truncated LineString
+        let truncated = to_wkb(2, &[[0.0, 0.0], [1.0, 1.0]], true);
+        assert!(call_wkb(&truncated[..truncated.len() - 1], 1.0).is_empty());
+        assert!(call_wkb(&to_wkb(2, &[[0.0, 0.0], [f64::NAN, 1.0]], false), 1.0).is_empty());
+        assert!(call_wkb(&[], 1.0).is_empty());
+    }
+    #[test]
+    fn test_ffi_rdp_csv() {
+        use crate::simplify_rdp_ffi;
+
+        let input: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let coords = external(&input);
+        let take = |csv: *mut libc::c_char| {
+            let text = unsafe { CStr::from_ptr(csv) }.to_str().unwrap().to_owned();
+            unsafe { drop_cstring(csv) };
+            text
+        };
+        let csv = take(unsafe { simplify_rdp_csv_ffi(coords, 0.001, true) });
+        let mut rows = csv.lines();
+        assert_eq!(rows.next(), Some("x,y"));
+        let parsed: Vec<[f64; 2]> = rows
+            .map(|row| {
+                let (x, y) = row.split_once(',').unwrap();
+                [x.parse().unwrap(), y.parse().unwrap()]
+            })
+            .collect();
+        let expected: LineString<f64> = simplify_rdp_ffi(coords, 0.001).into();
+        assert_eq!(LineString::from(parsed), expected);
+        assert!(csv.ends_with('\n'));
+
+        let short = [[1.0, 2.5], [3.0, 4.0]];
+        let coords = external(&short);
+        let csv = take(unsafe { simplify_rdp_csv_ffi(coords, 1.0, false) });
+        assert_eq!(csv, "1,2.5\n3,4\n");
+        let empty = ExternalArray {
+            data: ptr::null(),
+            len: 0,
+        };
+        assert_eq!(
+            take(unsafe { simplify_rdp_csv_ffi(empty, 1.0, true) }),
+            "x,y\n"
+        );
+        assert_eq!(take(unsafe { simplify_rdp_csv_ffi(empty, 1.0, false) }), "");
+        let invalid = [[f64::NAN, 0.0], [1.0, 1.0]];
+        let coords = external(&invalid);
+        assert!(unsafe { simplify_rdp_csv_ffi(coords, 1.0, true) }.is_null());
+    }
+    #[test]
+    fn test_ffi_rdp_wkt_invalid() {
+        assert!(call("LINESTRING(0 0,5", |s| unsafe {
+            simplify_rdp_wkt_ffi(s, 1.0)
+        })
+        .is_none());
+        let last_error = || unsafe { CStr::from_ptr(crate::rdp_last_error_message()) };
+        assert!(last_error()
+            .to_str()
+            .unwrap()
+            .starts_with("the input isn't a valid WKT LINESTRING"));
+        assert!(call("POINT(1 2)", |s| unsafe { simplify_rdp_wkt_ffi(s, 1.0) }).is_none());
+        assert!(unsafe { simplify_rdp_wkt_ffi(ptr::null(), 1.0) }.is_null());
+        assert_eq!(
+            last_error().to_str().unwrap(),
+            "the input string is a null pointer"
+        );
+    }
+}
@@ -0,0 +1,226 @@
+//! RDP simplification of geographic coordinates, using a tolerance expressed in metres
+
+use geo::{Closest, Coord, HaversineClosestPoint, HaversineDistance, Line, Point};
+
+use crate::douglas_peucker::rdp_indices;
+use crate::ffi::guarded;
+use crate::{ExternalArray, InternalArray};
+
+/// Minimum distance in metres from `point` to the great-circle segment joining `start` and `end`
+///
+/// Coordinates are (longitude, latitude) pairs in degrees. The Earth is treated as a sphere,
+/// so results may differ from a true (ellipsoidal) geodesic distance by up to around 0.5%.
+pub(crate) fn haversine_segment_distance(point: [f64; 2], start: [f64; 2], end: [f64; 2]) -> f64 {
+    let point = Point::from(point);
+    let segment = Line::new(Coord::from(start), Coord::from(end));
+    match segment.haversine_closest_point(&point) {
+        Closest::Intersection(_) => 0.0,
+        Closest::SinglePoint(closest) => point.haversine_distance(&closest),
+        Closest::Indeterminate => point
+            .haversine_distance(&Point::from(start))
+            .min(point.haversine_distance(&Point::from(end))),
+    }
+}
+
+/// The order of the values in each pair of geographic coordinates passed to
+/// [`simplify_rdp_geodesic_ffi`](fn.simplify_rdp_geodesic_ffi.html)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoordOrder {
+    /// (longitude, latitude), as in GeoJSON and WKT
+    LonLat = 0,
+    /// (latitude, longitude), as in many GPS formats
+    LatLon = 1,
+}
+
+impl TryFrom<libc::c_int> for CoordOrder {
+    type Error = libc::c_int;
+
+    fn try_from(value: libc::c_int) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(CoordOrder::LonLat),
+            1 => Ok(CoordOrder::LatLon),
+            _ => Err(value),
+        }
+    }
+}
+
+/// FFI wrapper for RDP on geographic coordinates, returning simplified geometry **coordinates**
+///
+/// Callers must pass three arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates in degrees:
+///       `[[-0.70, 52.22], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance, in **metres**
+/// - an `int` declaring the order of each pair of coordinates, as a [`CoordOrder`](enum.CoordOrder.html):
+///   `0` for (longitude, latitude), or `1` for (latitude, longitude)
+///
+/// Distances between points and segments are computed on a sphere, using the haversine formula.
+/// Longitudes needn't be continuous: a line crossing the antimeridian, from 179.9 to -179.9, is
+/// measured along its short segment, just as if its longitudes had been unwrapped to 180.1.
+/// Nothing about the coordinates reveals their order, so declaring the wrong one isn't detected:
+/// longitudes are treated as latitudes and vice versa, and distances are computed between the
+/// wrong places, producing incorrect results. If `coord_order` isn't a valid `CoordOrder` value, an
+/// empty array is returned. The retained coordinates are copied from the input, in their original
+/// order, so the returned endpoints are bit-identical to the input's.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_geodesic_ffi(
+    coords: ExternalArray,
+    tolerance_m: libc::c_double,
+    coord_order: libc::c_int,
+) -> InternalArray {
+    let Ok(order) = CoordOrder::try_from(coord_order) else {
+        return InternalArray::empty();
+    };
+    match coords.validate::<f64, 2>() {
+        Ok(points) => guarded(InternalArray::empty(), || {
+            let lon_lat = |idx: usize| match order {
+                CoordOrder::LonLat => points[idx],
+                CoordOrder::LatLon => [points[idx][1], points[idx][0]],
+            };
+            let retained: Vec<[f64; 2]> =
+                rdp_indices(points.len(), tolerance_m, |start, end, point| {
+                    haversine_segment_distance(lon_lat(point), lon_lat(start), lon_lat(end))
+                })
+                .into_iter()
+                .map(|idx| points[idx])
+                .collect();
+            geo::LineString::from(retained).into()
+        }),
+        Err(_) => InternalArray::empty(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drop_float_array;
+    use crate::ffi::{external, reclaim};
+    use geo::LineString;
+
+    #[test]
+    fn test_haversine_segment_distance() {
+        // 0.001 degrees of latitude is around 111 metres
+        let distance = haversine_segment_distance([0.5, 0.001], [0.0, 0.0], [1.0, 0.0]);
+        assert!((distance - 111.2).abs() < 0.1);
+        // beyond the end of the segment, the distance is to the nearest endpoint
+        let distance = haversine_segment_distance([2.0, 0.0], [0.0, 0.0], [1.0, 0.0]);
+        let expected = Point::new(2.0, 0.0).haversine_distance(&Point::new(1.0, 0.0));
+        assert!((distance - expected).abs() < 1e-6);
+    }
+    #[test]
+    fn test_ffi_rdp_geodesic_simplification() {
+        // a one-degree arc along a meridian, with two points displaced east of it
+        let input = vec![
+            [10.0, 50.0],
+            [10.0001, 50.25], // around 7 metres from the baseline
+            [10.0, 50.5],
+            [10.003, 50.75], // around 210 metres from the baseline
+            [10.0, 51.0],
+        ];
+        let ls: LineString<_> = input.into();
+        let output = vec![[10.0, 50.0], [10.0, 50.5], [10.003, 50.75], [10.0, 51.0]];
+        let transformed: LineString<_> = simplify_rdp_geodesic_ffi(ls.into(), 20.0, 0).into();
+        assert_eq!(transformed, output.into());
+
+        let coarse: LineString<_> = simplify_rdp_geodesic_ffi(transformed.into(), 500.0, 0).into();
+        assert_eq!(coarse, vec![[10.0, 50.0], [10.0, 51.0]].into());
+        drop_float_array(coarse.into());
+    }
+    #[test]
+    fn test_ffi_rdp_geodesic_coord_order() {
+        // a track running north from Oslo, wandering around 5.5 km east of its route, but 11 km
+        // north of it if the coordinates are read in the wrong order
+        let lon_lat = [
+            [10.75, 59.9],
+            [10.85, 60.4],
+            [10.75, 60.9],
+            [10.75, 61.4],
+            [10.75, 61.9],
+        ];
+        let lat_lon = lon_lat.map(|[lon, lat]| [lat, lon]);
+        let simplify = |input: &[[f64; 2]], coord_order| -> Vec<[f64; 2]> {
+            let coords = external(input);
+            unsafe { reclaim(simplify_rdp_geodesic_ffi(coords, 8000.0, coord_order)) }
+        };
+        let expected = simplify(&lon_lat, CoordOrder::LonLat as libc::c_int);
+        assert!(expected.len() < lon_lat.len());
+        // declaring the order of the coordinates produces the same points, in the input's order
+        let swapped: Vec<[f64; 2]> = simplify(&lat_lon, CoordOrder::LatLon as libc::c_int)
+            .into_iter()
+            .map(|[lat, lon]| [lon, lat])
+            .collect();
+        assert_eq!(swapped, expected);
+        // the wrong declaration isn't detected, but measures distances between the wrong places
+        let mismatched: Vec<[f64; 2]> = simplify(&lat_lon, CoordOrder::LonLat as libc::c_int)
+            .into_iter()
+            .map(|[lat, lon]| [lon, lat])
+            .collect();
+        assert_ne!(mismatched, expected);
+        let invalid = simplify_rdp_geodesic_ffi(external(&lon_lat), 8000.0, 2);
+        assert!(invalid.data.is_null());
+        drop_float_array(invalid);
+    }
+    #[test]
+    fn test_ffi_rdp_geodesic_antimeridian() {
+        // a track near Fiji, crossing 180 degrees with a wobble around 55 metres north of its
+        // route, then turning around 1.1 km north
+        let input = [
+            [179.9, -17.0],
+            [179.95, -16.9995],
+            [-179.98, -17.0],
+            [-179.9, -17.0],
+            [-179.85, -16.99],
+            [-179.8, -17.0],
+        ];
+        let simplify = |input: &[[f64; 2]]| -> Vec<[f64; 2]> {
+            let coords = external(input);
+            unsafe { reclaim(simplify_rdp_geodesic_ffi(coords, 100.0, 0)) }
+        };
+        let simplified = simplify(&input);
+        assert_eq!(
+            simplified,
+            vec![
+                [179.9, -17.0],
+                [-179.9, -17.0],
+                [-179.85, -16.99],
+                [-179.8, -17.0]
+            ]
+        );
+        // the crossing segment is around 21 km long, not most of the way around the Earth
+        let crossing = Point::from(input[0]).haversine_distance(&Point::from(input[3]));
+        assert!((crossing - 21_265.0).abs() < 10.0);
+        assert!(haversine_segment_distance(input[1], input[0], input[3]) < 100.0);
+        // distances are computed on the sphere, so the same track with continuous longitudes
+        // retains the same points
+        let unwrapped = input.map(|[lon, lat]| [if lon < 0.0 { lon + 360.0 } else { lon }, lat]);
+        let rewrapped: Vec<[f64; 2]> = simplify(&unwrapped)
+            .into_iter()
+            .map(|[lon, lat]| [if lon > 180.0 { lon - 360.0 } else { lon }, lat])
+            .collect();
+        assert_eq!(rewrapped, simplified);
+    }
+    #[test]
+    fn test_ffi_rdp_geodesic_endpoints() {
+        let input = [
+            [-0.1275862, 51.5072178],
+            [-0.1280001, 51.5100003],
+            [-0.1419987, 51.5194133],
+        ];
+        let coords = external(&input);
+        let simplified: LineString<f64> = simplify_rdp_geodesic_ffi(coords, 1000.0, 0).into();
+        let bits = |c: Coord| [c.x.to_bits(), c.y.to_bits()];
+        assert_eq!(simplified.0.len(), 2);
+        assert_eq!(bits(simplified.0[0]), input[0].map(f64::to_bits));
+        assert_eq!(bits(simplified.0[1]), input[2].map(f64::to_bits));
+    }
+}
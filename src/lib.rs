@@ -3,389 +3,78 @@
     html_root_url = "https://docs.rs/rdp"
 )]
 //! This crate provides FFI functions for accessing the Ramer–Douglas–Peucker and Visvalingam-Whyatt line simplification algorithms
-
-use std::slice;
-use std::{f64, ptr};
-
-use self::geo::simplify::{Simplify, SimplifyIdx};
-use self::geo::simplify_vw::{SimplifyVw, SimplifyVwIdx, SimplifyVwPreserve};
-use self::geo::LineString;
-use geo::{self, CoordFloat};
-
-/// A C-compatible `struct` originating **outside** Rust
-/// used for passing arrays across the FFI boundary
-#[repr(C)]
-pub struct ExternalArray {
-    pub data: *const libc::c_void,
-    pub len: libc::size_t,
-}
-
-/// A C-compatible `struct` originating **inside** Rust
-/// used for passing arrays across the FFI boundary
-#[repr(C)]
-pub struct InternalArray {
-    pub data: *mut libc::c_void,
-    pub len: libc::size_t,
-}
-
-// Build an InternalArray from a LineString, so it can be leaked across the FFI boundary
-impl<T> From<LineString<T>> for InternalArray
-where
-    T: CoordFloat,
-{
-    fn from(sl: LineString<T>) -> Self {
-        let v: Vec<[T; 2]> = sl.0.iter().map(|p| [p.x, p.y]).collect();
-        let boxed = v.into_boxed_slice();
-        let blen = boxed.len();
-        let rawp = Box::into_raw(boxed);
-        InternalArray {
-            data: rawp as *mut libc::c_void,
-            len: blen as libc::size_t,
-        }
-    }
-}
-
-// Build an ExternalArray from a LineString, so it can be leaked across the FFI boundary
-impl<T> From<LineString<T>> for ExternalArray
-where
-    T: CoordFloat,
-{
-    fn from(sl: LineString<T>) -> Self {
-        let v: Vec<[T; 2]> = sl.0.iter().map(|p| [p.x, p.y]).collect();
-        let boxed = v.into_boxed_slice();
-        let blen = boxed.len();
-        let rawp = Box::into_raw(boxed);
-        ExternalArray {
-            data: rawp as *mut libc::c_void,
-            len: blen as libc::size_t,
-        }
-    }
-}
-
-// Build an InternalArray from a vec of usize, so it can be leaked across the FFI boundary
-impl From<Vec<usize>> for InternalArray {
-    fn from(v: Vec<usize>) -> Self {
-        let boxed = v.into_boxed_slice();
-        let blen = boxed.len();
-        let rawp = Box::into_raw(boxed);
-        InternalArray {
-            data: rawp as *mut libc::c_void,
-            len: blen as libc::size_t,
-        }
-    }
-}
-
-// Build a LineString from an ExternalArray
-impl From<ExternalArray> for LineString<f64> {
-    fn from(arr: ExternalArray) -> Self {
-        // we need to take ownership of this data, so slice -> vec
-        unsafe {
-            let v = slice::from_raw_parts(arr.data as *mut [f64; 2], arr.len).to_vec();
-            v.into()
-        }
-    }
-}
-
-// Build a LineString from an InternalArray
-// Ideally this would be a LineString, but local types blah blah
-impl From<InternalArray> for LineString<f64> {
-    fn from(arr: InternalArray) -> Self {
-        // we originated this data, so pointer-to-slice -> box -> vec
-        unsafe {
-            let p = ptr::slice_from_raw_parts_mut(arr.data as *mut [f64; 2], arr.len);
-            let v = Box::from_raw(p).to_vec();
-            v.into()
-        }
-    }
-}
-
-// Build a Vec of usize from an ExternalArray
-impl From<ExternalArray> for Vec<usize> {
-    fn from(arr: ExternalArray) -> Self {
-        // we need to take ownership of this data, so slice -> vec
-        unsafe { slice::from_raw_parts(arr.data as *mut usize, arr.len).to_vec() }
-    }
-}
-
-// Build a Vec of usize from an InternalArray
-impl From<InternalArray> for Vec<usize> {
-    fn from(arr: InternalArray) -> Self {
-        // we originated this data, so pointer-to-slice -> box -> vec
-        unsafe {
-            let p = ptr::slice_from_raw_parts_mut(arr.data as *mut usize, arr.len);
-            Box::from_raw(p).to_vec()
-        }
-    }
-}
-
-/// FFI wrapper for RDP, returning simplified geometry **coordinates**
-///
-/// Callers must pass two arguments:
-///
-/// - a [Struct](struct.Array.html) with two fields:
-///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
-///     - `len`, the length of the array being passed. Its type must be `size_t`
-/// - a double-precision `float` for the tolerance
-///
-/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
-/// with the returned `Array` pointer, in order to free the memory it allocates.
-///
-/// # Safety
-///
-/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
-#[no_mangle]
-pub extern "C" fn simplify_rdp_ffi(
-    coords: ExternalArray,
-    precision: libc::c_double,
-) -> InternalArray {
-    let ls: LineString<_> = coords.into();
-    ls.simplify(&precision).into()
-}
-
-/// FFI wrapper for RDP, returning simplified geometry **indices**
-///
-/// Callers must pass two arguments:
-///
-/// - a [Struct](struct.Array.html) with two fields:
-///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
-///     - `len`, the length of the array being passed. Its type must be `size_t`
-/// - a double-precision `float` for the tolerance
-///
-/// Implementations calling this function **must** call [`drop_usize_array`](fn.drop_usize_array.html)
-/// with the returned `Array` pointer, in order to free the memory it allocates.
-///
-/// # Safety
-///
-/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
-#[no_mangle]
-pub extern "C" fn simplify_rdp_idx_ffi(
-    coords: ExternalArray,
-    precision: libc::c_double,
-) -> InternalArray {
-    let ls: LineString<_> = coords.into();
-    ls.simplify_idx(&precision).into()
-}
-
-/// FFI wrapper for Visvalingam-Whyatt, returning simplified geometry **coordinates**
-///
-/// Callers must pass two arguments:
-///
-/// - a [Struct](struct.Array.html) with two fields:
-///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
-///     - `len`, the length of the array being passed. Its type must be `size_t`
-/// - a double-precision `float` for the epsilon
-///
-/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
-/// with the returned `Array` pointer, in order to free the memory it allocates.
-///
-/// # Safety
-///
-/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
-#[no_mangle]
-pub extern "C" fn simplify_visvalingam_ffi(
-    coords: ExternalArray,
-    precision: libc::c_double,
-) -> InternalArray {
-    let ls: LineString<_> = coords.into();
-    ls.simplify_vw(&precision).into()
-}
-
-/// FFI wrapper for Visvalingam-Whyatt, returning simplified geometry **indices**
-///
-/// Callers must pass two arguments:
-///
-/// - a [Struct](struct.Array.html) with two fields:
-///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
-///     - `len`, the length of the array being passed. Its type must be `size_t`
-/// - a double-precision `float` for the epsilon
-///
-/// Implementations calling this function **must** call [`drop_usize_array`](fn.drop_usize_array.html)
-/// with the returned `Array` pointer, in order to free the memory it allocates.
-///
-/// # Safety
-///
-/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
-#[no_mangle]
-pub extern "C" fn simplify_visvalingam_idx_ffi(
-    coords: ExternalArray,
-    precision: libc::c_double,
-) -> InternalArray {
-    let ls: LineString<_> = coords.into();
-    ls.simplify_vw_idx(&precision).into()
-}
-
-/// FFI wrapper for topology-preserving Visvalingam-Whyatt, returning simplified geometry **coordinates**.
-///
-/// Callers must pass two arguments:
-///
-/// - a [Struct](struct.Array.html) with two fields:
-///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
-///     - `len`, the length of the array being passed. Its type must be `size_t`
-/// - a double-precision `float` for the epsilon
-///
-/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
-/// with the returned `Array` pointer, in order to free the memory it allocates.
-///
-/// # Safety
-///
-/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
-#[no_mangle]
-pub extern "C" fn simplify_visvalingamp_ffi(
-    coords: ExternalArray,
-    precision: libc::c_double,
-) -> InternalArray {
-    let ls: LineString<_> = coords.into();
-    ls.simplify_vw_preserve(&precision).into()
-}
-
-/// Free memory which has been allocated across the FFI boundary by:
-/// - simplify_rdp_ffi
-/// - simplify_visvalingam_ffi
-/// - simplify_visvalingamp_ffi
-///
-/// # Safety
-///
-/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
-#[no_mangle]
-pub extern "C" fn drop_float_array(arr: InternalArray) {
-    if arr.data.is_null() {
-        return;
-    }
-    unsafe {
-        let p = ptr::slice_from_raw_parts_mut(arr.data as *mut [f64; 2], arr.len);
-        drop(Box::from_raw(p));
-    };
-}
-
-/// Free memory which has been allocated across the FFI boundary by:
-/// - simplify_rdp_idx_ffi
-/// - simplify_visvalingam_idx_ffi
-///
-/// # Safety
-///
-/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
-#[no_mangle]
-pub extern "C" fn drop_usize_array(arr: InternalArray) {
-    if arr.data.is_null() {
-        return;
-    }
-    unsafe {
-        let p = ptr::slice_from_raw_parts_mut(arr.data as *mut usize, arr.len);
-        drop(Box::from_raw(p));
-    };
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    
-    use geo::{LineString, Point};
-
-    use std::ptr;
-    #[test]
-    fn test_linestring_to_array() {
-        let ls: LineString<_> = vec![Point::new(1.0, 2.0), Point::new(3.0, 4.0)].into();
-        let _: InternalArray = ls.into();
-    }
-    #[test]
-    fn test_array_conversion() {
-        let original = vec![
-            [0.0, 0.0],
-            [5.0, 4.0],
-            [11.0, 5.5],
-            [17.3, 3.2],
-            [27.8, 0.1],
-        ];
-        let ls: LineString<_> = original.clone().into();
-        // move into an Array, and leak it
-        let arr: InternalArray = ls.into();
-        // move back into a Vec -- leaked value still needs to be dropped
-        let converted: LineString<_> = arr.into();
-        assert_eq!(converted, original.into());
-        // drop it
-        drop_float_array(converted.into());
-    }
-    #[test]
-    fn test_ffi_rdp_simplification() {
-        let input = vec![
-            [0.0, 0.0],
-            [5.0, 4.0],
-            [11.0, 5.5],
-            [17.3, 3.2],
-            [27.8, 0.1],
-        ];
-        let ls: LineString<_> = input.into();
-        let output = vec![[0.0, 0.0], [5.0, 4.0], [11.0, 5.5], [27.8, 0.1]];
-        let transformed: LineString<_> = simplify_rdp_ffi(ls.into(), 1.0).into();
-        assert_eq!(transformed, output.into());
-    }
-    #[test]
-    fn test_ffi_rdp_idx_simplification() {
-        let input = vec![
-            [0.0, 0.0],
-            [5.0, 4.0],
-            [11.0, 5.5],
-            [17.3, 3.2],
-            [27.8, 0.1],
-        ];
-        let ls: LineString<_> = input.into();
-        // let output = vec![[0.0, 0.0], [5.0, 4.0], [11.0, 5.5], [27.8, 0.1]];
-        let output = vec![0, 1, 2, 4];
-        let transformed: Vec<usize> = simplify_rdp_idx_ffi(ls.into(), 1.0).into();
-        assert_eq!(transformed, output);
-    }
-    #[test]
-    fn test_ffi_visvalingam_simplification() {
-        let input = vec![
-            [5.0, 2.0],
-            [3.0, 8.0],
-            [6.0, 20.0],
-            [7.0, 25.0],
-            [10.0, 10.0],
-        ];
-        let ls: LineString<_> = input.into();
-        let output = vec![[5.0, 2.0], [7.0, 25.0], [10.0, 10.0]];
-        let transformed: LineString<_> = simplify_visvalingam_ffi(ls.into(), 30.0).into();
-        assert_eq!(transformed, output.into());
-    }
-    #[test]
-    fn test_ffi_visvalingam_idx_simplification() {
-        let input = vec![
-            [5.0, 2.0],
-            [3.0, 8.0],
-            [6.0, 20.0],
-            [7.0, 25.0],
-            [10.0, 10.0],
-        ];
-        let ls: LineString<_> = input.into();
-        // let output = vec![[5.0, 2.0], [7.0, 25.0], [10.0, 10.0]];
-        let output = vec![0, 3, 4];
-        let transformed: Vec<usize> = simplify_visvalingam_idx_ffi(ls.into(), 30.0).into();
-        assert_eq!(transformed, output);
-    }
-    #[test]
-    fn test_ffi_visvalingamp_simplification() {
-        let input = vec![
-            [5.0, 2.0],
-            [3.0, 8.0],
-            [6.0, 20.0],
-            [7.0, 25.0],
-            [10.0, 10.0],
-        ];
-        let ls: LineString<_> = input.into();
-        let output = vec![[5.0, 2.0], [7.0, 25.0], [10.0, 10.0]];
-        let transformed: LineString<_> = simplify_visvalingamp_ffi(ls.into(), 30.0).into();
-        assert_eq!(transformed, output.into());
-    }
-    #[test]
-    fn test_drop_empty_float_array() {
-        let original = vec![[1.0, 2.0], [3.0, 4.0]];
-        let ls: LineString<_> = original.into();
-        // move into an Array, and leak it
-        let mut arr: InternalArray = ls.into();
-        // zero Array contents
-        arr.data = ptr::null_mut();
-        drop_float_array(arr);
-    }
-}
+//!
+//! Rust consumers can use the safe wrappers in the [`api`](api/index.html) module instead.
+//!
+//! The FFI functions require the `std` feature, which is enabled by default. Without it, the crate
+//! is `no_std` (but requires `alloc`), and provides the RDP functions in the `api` module.
+//!
+//! Every simplification function treats short input the same way: an empty array is returned for
+//! input containing no points, and input containing one or two points is returned unchanged (or, for
+//! the functions returning indices, `[0]` and `[0, 1]`). The exception is
+//! [`simplify_rdp_ring_ffi`](fn.simplify_rdp_ring_ffi.html), which requires a valid ring.
+//!
+//! A panic during simplification never unwinds across the FFI boundary: it is caught, and the
+//! function returns the same value as it would for invalid input. The `_checked` functions report
+//! it using [`FfiError::Panic`](enum.FfiError.html). A description of the most recent failure on
+//! the calling thread, including the panic's message, is available from
+//! [`rdp_last_error_message`](fn.rdp_last_error_message.html).
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod api;
+#[cfg(feature = "std")]
+mod batch;
+#[cfg(feature = "std")]
+mod config;
+mod douglas_peucker;
+#[cfg(feature = "std")]
+mod ffi;
+#[cfg(feature = "std")]
+mod formats;
+#[cfg(feature = "std")]
+mod geodesic;
+#[cfg(feature = "std")]
+mod pool;
+#[cfg(feature = "std")]
+mod ring;
+#[cfg(feature = "std")]
+mod stream;
+#[cfg(feature = "std")]
+mod visvalingam;
+
+#[cfg(feature = "std")]
+pub use batch::{
+    drop_float_array_batch, drop_multi_float_array, simplify_rdp_batch_ffi,
+    simplify_rdp_batch_progress_ffi, simplify_rdp_idx_packed_ffi, simplify_rdp_multi_ffi,
+    simplify_rdp_shared_ffi, simplify_visvalingam_batch_ffi, ProgressCallback,
+};
+#[cfg(feature = "std")]
+pub use config::{rdp_get_default_precision, rdp_set_default_precision, simplify_rdp_default_ffi};
+#[cfg(feature = "std")]
+pub use ffi::*;
+#[cfg(feature = "geojson")]
+pub use formats::simplify_rdp_geojson_ffi;
+#[cfg(feature = "wkb")]
+pub use formats::{drop_byte_array, simplify_rdp_wkb_ffi};
+#[cfg(feature = "std")]
+pub use formats::{drop_cstring, simplify_rdp_csv_ffi, simplify_rdp_wkt_ffi};
+#[cfg(feature = "std")]
+pub use geodesic::{simplify_rdp_geodesic_ffi, CoordOrder};
+#[cfg(feature = "std")]
+pub use pool::{
+    drop_pooled_float_array, rdp_pool_free, rdp_pool_new, simplify_rdp_pooled_ffi, RdpPool,
+};
+#[cfg(feature = "std")]
+pub use ring::{
+    ring_symdiff_area_ffi, simplify_rdp_polygon_ffi, simplify_rdp_ring_ffi,
+    simplify_ring_area_delta_ffi, simplify_ring_oriented_ffi,
+};
+#[cfg(feature = "std")]
+pub use stream::{rdp_stream_finish, rdp_stream_free, rdp_stream_new, rdp_stream_push, RdpStream};
+#[cfg(feature = "std")]
+pub use visvalingam::{
+    simplify_visvalingam_tiebreak_ffi, simplify_vw_areas_ffi, simplify_vw_weighted_ffi, TieBreak,
+};
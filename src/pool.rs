@@ -0,0 +1,193 @@
+//! A pool of reusable output buffers for RDP simplification
+//!
+//! Each call to [`simplify_rdp_ffi`](../fn.simplify_rdp_ffi.html) allocates a new output array, which
+//! the caller then frees. Callers simplifying many lines in a hot loop can instead create a pool,
+//! and return arrays to it once they're done with them, so their buffers are reused by later calls.
+
+use std::collections::HashMap;
+use std::mem::ManuallyDrop;
+
+use crate::douglas_peucker::{rdp_indices, segment_distance};
+use crate::ffi::guarded;
+use crate::{ExternalArray, InternalArray};
+
+/// A pool of output buffers, used by [`simplify_rdp_pooled_ffi`](fn.simplify_rdp_pooled_ffi.html)
+///
+/// Create one using [`rdp_pool_new`](fn.rdp_pool_new.html). A pool isn't thread-safe: callers must
+/// not use the same pool from multiple threads concurrently.
+#[derive(Default)]
+pub struct RdpPool {
+    // Buffers which have been returned to the pool, ready for reuse
+    free: Vec<Vec<[f64; 2]>>,
+    // The capacity of each buffer which is currently lent out, keyed by its address
+    lent: HashMap<usize, usize>,
+}
+
+impl RdpPool {
+    // Simplify coordinates into a buffer drawn from the pool
+    fn simplify(&mut self, coords: &[[f64; 2]], precision: f64) -> InternalArray {
+        let retained = rdp_indices(coords.len(), precision, |start, end, point| {
+            segment_distance(coords[point], coords[start], coords[end])
+        });
+        let mut buffer = self.free.pop().unwrap_or_default();
+        buffer.clear();
+        buffer.extend(retained.into_iter().map(|idx| coords[idx]));
+        let mut buffer = ManuallyDrop::new(buffer);
+        self.lent
+            .insert(buffer.as_mut_ptr() as usize, buffer.capacity());
+        InternalArray {
+            data: buffer.as_mut_ptr() as *mut libc::c_void,
+            len: buffer.len(),
+        }
+    }
+
+    // Return a buffer to the pool. Arrays which weren't lent by this pool are ignored
+    fn reclaim(&mut self, arr: InternalArray) {
+        if let Some(capacity) = self.lent.remove(&(arr.data as usize)) {
+            let buffer = unsafe { Vec::from_raw_parts(arr.data as *mut [f64; 2], 0, capacity) };
+            self.free.push(buffer);
+        }
+    }
+}
+
+impl Drop for RdpPool {
+    fn drop(&mut self) {
+        for (data, capacity) in self.lent.drain() {
+            drop(unsafe { Vec::from_raw_parts(data as *mut [f64; 2], 0, capacity) });
+        }
+    }
+}
+
+/// Create a new pool of output buffers for [`simplify_rdp_pooled_ffi`](fn.simplify_rdp_pooled_ffi.html)
+///
+/// Implementations calling this function **must** call [`rdp_pool_free`](fn.rdp_pool_free.html)
+/// with the returned pointer, in order to free the memory it allocates.
+#[no_mangle]
+pub extern "C" fn rdp_pool_new() -> *mut RdpPool {
+    Box::into_raw(Box::default())
+}
+
+/// FFI wrapper for RDP, returning simplified geometry **coordinates** in a buffer drawn from a pool
+///
+/// Callers must pass three arguments:
+///
+/// - a pointer returned by [`rdp_pool_new`](fn.rdp_pool_new.html)
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+///
+/// If the pool pointer is null or the input is invalid, an empty array is returned.
+///
+/// Implementations calling this function **must** call
+/// [`drop_pooled_float_array`](fn.drop_pooled_float_array.html) with the same pool and the returned
+/// `Array`, in order to return its buffer to the pool. It **must not** be passed to
+/// [`drop_float_array`](fn.drop_float_array.html).
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses raw pointers which could contain arbitrary data
+#[no_mangle]
+pub unsafe extern "C" fn simplify_rdp_pooled_ffi(
+    pool: *mut RdpPool,
+    coords: ExternalArray,
+    precision: libc::c_double,
+) -> InternalArray {
+    if pool.is_null() {
+        return InternalArray::empty();
+    }
+    match coords.validate::<f64, 2>() {
+        Ok(valid) => guarded(InternalArray::empty(), || {
+            (*pool).simplify(valid, precision)
+        }),
+        Err(_) => InternalArray::empty(),
+    }
+}
+
+/// Return memory which has been allocated across the FFI boundary by:
+/// - simplify_rdp_pooled_ffi
+///
+/// to the pool it was drawn from, so it can be reused.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses raw pointers which could contain arbitrary data
+#[no_mangle]
+pub unsafe extern "C" fn drop_pooled_float_array(pool: *mut RdpPool, arr: InternalArray) {
+    if pool.is_null() || arr.data.is_null() {
+        return;
+    }
+    (*pool).reclaim(arr);
+}
+
+/// Free a pool created by [`rdp_pool_new`](fn.rdp_pool_new.html)
+///
+/// Any arrays drawn from the pool which haven't been returned to it are also freed, and must not be
+/// used afterwards.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub unsafe extern "C" fn rdp_pool_free(pool: *mut RdpPool) {
+    if pool.is_null() {
+        return;
+    }
+    let _ = Box::from_raw(pool);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api;
+    use crate::ffi::{external, read_coords};
+
+    #[test]
+    fn test_pooled_buffers_are_reused() {
+        let long: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let short = [
+            [0.0, 0.0],
+            [5.0, 4.0],
+            [11.0, 5.5],
+            [17.3, 3.2],
+            [27.8, 0.1],
+        ];
+        unsafe {
+            let pool = rdp_pool_new();
+            let first = simplify_rdp_pooled_ffi(pool, external(&long), 0.001);
+            assert_eq!(read_coords(&first), api::simplify_rdp(&long, 0.001));
+            // while the first array is outstanding, a new buffer is used
+            let second = simplify_rdp_pooled_ffi(pool, external(&short), 1.0);
+            assert_ne!(first.data, second.data);
+            assert_eq!(read_coords(&first), api::simplify_rdp(&long, 0.001));
+            assert_eq!(read_coords(&second), api::simplify_rdp(&short, 1.0));
+            // once it's returned, its buffer is reused without stale data leaking into the result
+            let first_data = first.data;
+            drop_pooled_float_array(pool, first);
+            let third = simplify_rdp_pooled_ffi(pool, external(&short), 1.0);
+            assert_eq!(third.data, first_data);
+            assert_eq!(read_coords(&third), api::simplify_rdp(&short, 1.0));
+            assert_eq!(read_coords(&second), api::simplify_rdp(&short, 1.0));
+            drop_pooled_float_array(pool, third);
+            // outstanding arrays are freed along with the pool
+            rdp_pool_free(pool);
+        }
+    }
+    #[test]
+    fn test_pooled_invalid_input() {
+        unsafe {
+            let pool = rdp_pool_new();
+            let invalid = ExternalArray {
+                data: std::ptr::null(),
+                len: 3,
+            };
+            let result = simplify_rdp_pooled_ffi(pool, invalid, 1.0);
+            assert!(result.data.is_null());
+            drop_pooled_float_array(pool, result);
+            let result =
+                simplify_rdp_pooled_ffi(std::ptr::null_mut(), external(&[[0.0, 0.0]]), 1.0);
+            assert!(result.data.is_null());
+            rdp_pool_free(pool);
+        }
+    }
+}
@@ -0,0 +1,535 @@
+//! Simplification of closed rings, such as the exterior of a polygon
+
+use std::{iter, slice};
+
+use geo::simplify::Simplify;
+use geo::{Area, BooleanOps, Intersects, Line, LineString, Polygon, Winding};
+
+use crate::ffi::{guarded, leak};
+use crate::{ExternalArray, InternalArray};
+
+/// The minimum number of points in a valid closed ring
+pub(crate) const RING_MIN_POINTS: usize = 4;
+
+/// Build a closed ring from coordinates, closing it if its first and last coordinates differ
+///
+/// Returns `None` if the closed ring has fewer than [`RING_MIN_POINTS`] points
+pub(crate) fn close_ring(coords: &[[f64; 2]]) -> Option<LineString<f64>> {
+    let mut ring: LineString<f64> = coords.to_vec().into();
+    ring.close();
+    if ring.0.len() < RING_MIN_POINTS {
+        return None;
+    }
+    Some(ring)
+}
+
+/// Simplify a closed ring using RDP
+///
+/// The output is closed, and if simplification would leave fewer than [`RING_MIN_POINTS`]
+/// points, the input is returned unchanged
+pub(crate) fn simplify_ring(ring: &LineString<f64>, epsilon: f64) -> LineString<f64> {
+    let simplified = Polygon::new(ring.clone(), vec![])
+        .simplify(&epsilon)
+        .into_inner()
+        .0;
+    if simplified.0.len() < RING_MIN_POINTS || !simplified.is_closed() {
+        return ring.clone();
+    }
+    simplified
+}
+
+// Validate, close and simplify a ring, producing an empty array if it can't form a valid ring
+fn simplify_ring_array(coords: &ExternalArray, epsilon: f64) -> InternalArray {
+    coords
+        .validate::<f64, 2>()
+        .ok()
+        .and_then(close_ring)
+        .map_or_else(InternalArray::empty, |ring| {
+            guarded(InternalArray::empty(), || {
+                simplify_ring(&ring, epsilon).into()
+            })
+        })
+}
+
+/// FFI wrapper for RDP on a closed ring, returning simplified geometry **coordinates**
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance
+///
+/// The input is treated as a closed ring: if its first and last coordinates differ, it is closed
+/// by appending its first coordinate. The output's first and last coordinates are always identical,
+/// and it always has at least 4 points: if simplification would leave fewer, the (closed) input is
+/// returned. If the closed input has fewer than 4 points, it can't form a valid ring, and an empty
+/// array is returned.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_ring_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+) -> InternalArray {
+    simplify_ring_array(&coords, precision)
+}
+
+/// FFI wrapper for RDP on a closed ring, returning simplified geometry **coordinates** with a given
+/// orientation
+///
+/// Callers must pass three arguments:
+///
+/// - a [Struct](struct.Array.html) describing the ring, in the same way as for
+///   [`simplify_rdp_ring_ffi`](fn.simplify_rdp_ring_ffi.html)
+/// - a double-precision `float` for the tolerance
+/// - an `int`: if it's non-zero, the output is wound counter-clockwise, as OGC requires of exterior
+///   rings. Otherwise, it's wound clockwise, as OGC requires of interior rings
+///
+/// The ring is simplified as by [`simplify_rdp_ring_ffi`](fn.simplify_rdp_ring_ffi.html), then
+/// reversed if its winding order, as determined by geo, doesn't match the requested one. A ring
+/// which encloses no area has no winding order, and is returned as it is. If the input can't form a
+/// valid ring, an empty array is returned.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_ring_oriented_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+    want_ccw: libc::c_int,
+) -> InternalArray {
+    coords
+        .validate::<f64, 2>()
+        .ok()
+        .and_then(close_ring)
+        .map_or_else(InternalArray::empty, |ring| {
+            guarded(InternalArray::empty(), || {
+                let mut simplified = simplify_ring(&ring, precision);
+                if want_ccw != 0 {
+                    simplified.make_ccw_winding();
+                } else {
+                    simplified.make_cw_winding();
+                }
+                simplified.into()
+            })
+        })
+}
+
+/// FFI wrapper for RDP on a closed ring, returning simplified geometry **coordinates** and the
+/// change in area
+///
+/// Callers must pass three arguments:
+///
+/// - a [Struct](struct.Array.html) describing the ring, in the same way as for
+///   [`simplify_rdp_ring_ffi`](fn.simplify_rdp_ring_ffi.html)
+/// - a double-precision `float` for the tolerance
+/// - a pointer to a `double`, into which the area of the simplified ring minus the area of the
+///   (closed) input is written. A null pointer is ignored.
+///
+/// The ring is simplified as by [`simplify_rdp_ring_ffi`](fn.simplify_rdp_ring_ffi.html). Areas are
+/// computed by geo, and are unsigned, so the difference doesn't depend on the ring's orientation:
+/// it's negative if simplification lost area, and positive if it gained area, such as by filling
+/// in a notch. The difference is in the square of the coordinates' units. If the input can't form a
+/// valid ring, an empty array is returned, and NaN is written.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses raw pointers which could contain arbitrary data
+#[no_mangle]
+pub unsafe extern "C" fn simplify_ring_area_delta_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+    area_delta_out: *mut libc::c_double,
+) -> InternalArray {
+    let result = coords
+        .validate::<f64, 2>()
+        .ok()
+        .and_then(close_ring)
+        .and_then(|ring| {
+            guarded(None, || {
+                let simplified = simplify_ring(&ring, precision);
+                let area =
+                    |ring: &LineString<f64>| Polygon::new(ring.clone(), vec![]).unsigned_area();
+                Some((area(&simplified) - area(&ring), simplified))
+            })
+        });
+    let (delta, array) = match result {
+        Some((delta, simplified)) => (delta, simplified.into()),
+        None => (f64::NAN, InternalArray::empty()),
+    };
+    if !area_delta_out.is_null() {
+        *area_delta_out = delta;
+    }
+    array
+}
+
+// Whether any two non-adjacent segments of a closed ring intersect
+fn self_intersects(ring: &LineString<f64>) -> bool {
+    let segments: Vec<Line<f64>> = ring.lines().collect();
+    let last = segments.len() - 1;
+    segments.iter().enumerate().any(|(i, a)| {
+        segments
+            .iter()
+            .enumerate()
+            .skip(i + 2)
+            // the first and last segments meet at the ring's closing point
+            .any(|(j, b)| !(i == 0 && j == last) && a.intersects(b))
+    })
+}
+
+/// Compute the area of the symmetric difference between a closed ring and its RDP simplification
+///
+/// Callers must pass two arguments:
+///
+/// - a [Struct](struct.Array.html) describing the ring, in the same way as for
+///   [`simplify_rdp_ring_ffi`](fn.simplify_rdp_ring_ffi.html)
+/// - a double-precision `float` for the tolerance
+///
+/// The ring is simplified as by [`simplify_rdp_ring_ffi`](fn.simplify_rdp_ring_ffi.html), and the
+/// result is the area covered by exactly one of the (closed) input and the simplified ring, computed
+/// using geo's boolean operations. Unlike the change in area reported by
+/// [`simplify_ring_area_delta_ffi`](fn.simplify_ring_area_delta_ffi.html), gains and losses don't
+/// cancel out, so it measures how far the simplified ring strays from the input. The area is in the
+/// square of the coordinates' units.
+///
+/// Boolean operations require valid polygons, so NaN is returned if either ring intersects itself,
+/// as can happen when simplification moves an edge across another part of the ring. Checking this
+/// compares every pair of segments, so the cost is quadratic in the number of points. NaN is also
+/// returned if the input can't form a valid ring.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn ring_symdiff_area_ffi(
+    coords: ExternalArray,
+    precision: libc::c_double,
+) -> libc::c_double {
+    coords
+        .validate::<f64, 2>()
+        .ok()
+        .and_then(close_ring)
+        .map_or(f64::NAN, |ring| {
+            guarded(f64::NAN, || {
+                let simplified = simplify_ring(&ring, precision);
+                if self_intersects(&ring) || self_intersects(&simplified) {
+                    return f64::NAN;
+                }
+                Polygon::new(ring, vec![])
+                    .xor(&Polygon::new(simplified, vec![]))
+                    .unsigned_area()
+            })
+        })
+}
+
+/// FFI wrapper for RDP on a polygon with holes, returning simplified geometry **coordinates**
+///
+/// Callers must pass three arguments:
+///
+/// - a [Struct](struct.Array.html) describing the exterior ring, in the same way as for
+///   [`simplify_rdp_ring_ffi`](fn.simplify_rdp_ring_ffi.html)
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a pointer to an array of [Structs](struct.ExternalArray.html), each of which
+///       describes an interior ring in the same way as the exterior
+///     - `len`, the number of interior rings. Its type must be `size_t`
+/// - a double-precision `float` for the tolerance, which is applied to every ring
+///
+/// Each ring is simplified as by [`simplify_rdp_ring_ffi`](fn.simplify_rdp_ring_ffi.html), so a
+/// ring which would collapse to fewer than 4 points is returned unchanged. The returned `Array`'s
+/// `data` field points to `len` `InternalArray`s: the exterior, followed by the interior rings in
+/// the same order as the input, so the number of rings is preserved. An interior ring which can't
+/// form a valid ring produces an empty array in its slot. If the exterior can't form a valid ring,
+/// an empty `Array` is returned. A null interior ring pointer is treated as a polygon without holes.
+///
+/// Implementations calling this function **must** call [`drop_multi_float_array`](fn.drop_multi_float_array.html)
+/// with the returned `Array`, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses raw pointers which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_rdp_polygon_ffi(
+    exterior: ExternalArray,
+    interiors: ExternalArray,
+    precision: libc::c_double,
+) -> InternalArray {
+    let exterior = simplify_ring_array(&exterior, precision);
+    if exterior.data.is_null() {
+        return InternalArray::empty();
+    }
+    let interiors = if interiors.data.is_null() {
+        &[]
+    } else {
+        unsafe { slice::from_raw_parts(interiors.data as *const ExternalArray, interiors.len) }
+    };
+    let rings: Vec<InternalArray> = iter::once(exterior)
+        .chain(
+            interiors
+                .iter()
+                .map(|ring| simplify_ring_array(ring, precision)),
+        )
+        .collect();
+    leak(rings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffi::external;
+    use crate::{drop_float_array, drop_multi_float_array};
+
+    #[test]
+    fn test_ffi_rdp_ring_simplification() {
+        // a square with redundant points along two of its edges
+        let input = vec![
+            [0.0, 0.0],
+            [5.0, 0.1],
+            [10.0, 0.0],
+            [10.0, 10.0],
+            [5.0, 10.1],
+            [0.0, 10.0],
+            [0.0, 0.0],
+        ];
+        let ls: LineString<_> = input.into();
+        let output = vec![
+            [0.0, 0.0],
+            [10.0, 0.0],
+            [10.0, 10.0],
+            [0.0, 10.0],
+            [0.0, 0.0],
+        ];
+        let transformed: LineString<_> = simplify_rdp_ring_ffi(ls.into(), 1.0).into();
+        assert_eq!(transformed, output.into());
+    }
+    #[test]
+    fn test_ffi_ring_oriented() {
+        // a clockwise square with a redundant point along its top edge
+        let input = vec![
+            [0.0, 0.0],
+            [0.0, 10.0],
+            [5.0, 10.1],
+            [10.0, 10.0],
+            [10.0, 0.0],
+            [0.0, 0.0],
+        ];
+        let ls: LineString<f64> = input.into();
+        assert!(ls.is_cw());
+        let cw = vec![
+            [0.0, 0.0],
+            [0.0, 10.0],
+            [10.0, 10.0],
+            [10.0, 0.0],
+            [0.0, 0.0],
+        ];
+        let ccw: LineString<f64> = simplify_ring_oriented_ffi(ls.clone().into(), 1.0, 1).into();
+        assert!(ccw.is_ccw());
+        assert!(Polygon::new(ccw.clone(), vec![]).signed_area() > 0.0);
+        let mut expected: LineString<f64> = cw.clone().into();
+        expected.0.reverse();
+        assert_eq!(ccw, expected);
+        let unchanged: LineString<f64> =
+            simplify_ring_oriented_ffi(ls.clone().into(), 1.0, 0).into();
+        assert_eq!(unchanged, cw.into());
+        // a counter-clockwise ring is reversed when a clockwise one is requested
+        let reversed: LineString<f64> =
+            simplify_ring_oriented_ffi(ccw.clone().into(), 1.0, 0).into();
+        assert!(reversed.is_cw());
+        let degenerate: LineString<f64> = vec![[0.0, 0.0], [1.0, 0.0]].into();
+        let empty = simplify_ring_oriented_ffi(degenerate.into(), 1.0, 1);
+        assert!(empty.data.is_null());
+        drop_float_array(empty);
+    }
+    #[test]
+    fn test_ffi_rdp_ring_auto_close() {
+        let input = vec![
+            [0.0, 0.0],
+            [5.0, 0.1],
+            [10.0, 0.0],
+            [10.0, 10.0],
+            [0.0, 10.0],
+        ];
+        let ls: LineString<_> = input.into();
+        let output = vec![
+            [0.0, 0.0],
+            [10.0, 0.0],
+            [10.0, 10.0],
+            [0.0, 10.0],
+            [0.0, 0.0],
+        ];
+        let transformed: LineString<_> = simplify_rdp_ring_ffi(ls.into(), 1.0).into();
+        assert!(transformed.is_closed());
+        assert_eq!(transformed, output.into());
+    }
+    #[test]
+    fn test_ffi_rdp_ring_minimum() {
+        // every point lies within the tolerance of the start point, but the ring can't collapse
+        let input = vec![[0.0, 0.0], [0.5, 0.0], [0.5, 0.5], [0.0, 0.0]];
+        let ls: LineString<_> = input.clone().into();
+        let transformed: LineString<_> = simplify_rdp_ring_ffi(ls.into(), 1.0).into();
+        assert_eq!(transformed, input.into());
+        // two points can't form a valid ring
+        let ls: LineString<_> = vec![[0.0, 0.0], [1.0, 1.0]].into();
+        let transformed = simplify_rdp_ring_ffi(ls.into(), 1.0);
+        assert!(transformed.data.is_null());
+        drop_float_array(transformed);
+    }
+    #[test]
+    fn test_ffi_ring_area_delta() {
+        // a 10 × 10 square with a triangular notch of area 0.5 in its top edge
+        let input = [
+            [0.0, 0.0],
+            [10.0, 0.0],
+            [10.0, 10.0],
+            [6.0, 10.0],
+            [5.0, 9.5],
+            [4.0, 10.0],
+            [0.0, 10.0],
+            [0.0, 0.0],
+        ];
+        let coords = external(&input);
+        let mut delta = 0.0;
+        let simplified: LineString<f64> =
+            unsafe { simplify_ring_area_delta_ffi(coords, 1.0, &mut delta) }.into();
+        assert_eq!(
+            simplified,
+            vec![
+                [0.0, 0.0],
+                [10.0, 0.0],
+                [10.0, 10.0],
+                [0.0, 10.0],
+                [0.0, 0.0]
+            ]
+            .into()
+        );
+        // filling in the notch gains its area
+        assert!((delta - 0.5).abs() < 1e-12);
+        // the change doesn't depend on the ring's orientation
+        let mut reversed = input;
+        reversed.reverse();
+        let coords = external(&reversed);
+        let simplified = unsafe { simplify_ring_area_delta_ffi(coords, 1.0, &mut delta) };
+        assert!((delta - 0.5).abs() < 1e-12);
+        drop_float_array(simplified);
+
+        let ls: LineString<_> = vec![[0.0, 0.0], [1.0, 1.0]].into();
+        let invalid = unsafe { simplify_ring_area_delta_ffi(ls.into(), 1.0, &mut delta) };
+        assert!(invalid.data.is_null());
+        assert!(delta.is_nan());
+        drop_float_array(invalid);
+    }
+    #[test]
+    fn test_ffi_ring_symdiff_area() {
+        // a 10 × 10 square with a triangular notch of area 0.5 in its top edge
+        let input = [
+            [0.0, 0.0],
+            [10.0, 0.0],
+            [10.0, 10.0],
+            [6.0, 10.0],
+            [5.0, 9.5],
+            [4.0, 10.0],
+            [0.0, 10.0],
+            [0.0, 0.0],
+        ];
+        let coords = external(&input);
+        assert!((ring_symdiff_area_ffi(coords, 1.0) - 0.5).abs() < 1e-12);
+        assert_eq!(ring_symdiff_area_ffi(coords, 0.1), 0.0);
+        // a bump of area 2.5 in the top edge, and a slit rising from the bottom edge into it.
+        // Removing the bump moves the top edge below the top of the slit
+        let crossed = [
+            [0.0, 0.0],
+            [4.9, 0.0],
+            [5.0, 10.2],
+            [5.1, 0.0],
+            [10.0, 0.0],
+            [10.0, 10.0],
+            [5.0, 10.5],
+            [0.0, 10.0],
+            [0.0, 0.0],
+        ];
+        let coords = external(&crossed);
+        assert_eq!(ring_symdiff_area_ffi(coords, 0.1), 0.0);
+        assert!(ring_symdiff_area_ffi(coords, 1.0).is_nan());
+
+        let ls: LineString<_> = vec![[0.0, 0.0], [1.0, 1.0]].into();
+        assert!(ring_symdiff_area_ffi(ls.into(), 1.0).is_nan());
+    }
+    #[test]
+    fn test_ffi_rdp_polygon() {
+        // a square with redundant points along two of its edges
+        let exterior: LineString<_> = vec![
+            [0.0, 0.0],
+            [5.0, 0.1],
+            [10.0, 0.0],
+            [10.0, 10.0],
+            [5.0, 10.1],
+            [0.0, 10.0],
+            [0.0, 0.0],
+        ]
+        .into();
+        // a triangular hole, which is too small to simplify without collapsing
+        let hole = vec![[4.0, 4.0], [5.0, 4.0], [4.5, 4.5], [4.0, 4.0]];
+        let interiors: Vec<ExternalArray> = vec![
+            LineString::from(hole.clone()).into(),
+            LineString::from(vec![[1.0, 1.0], [2.0, 2.0]]).into(),
+        ];
+        let result = simplify_rdp_polygon_ffi(exterior.clone().into(), external(&interiors), 1.0);
+        assert_eq!(result.len, 3);
+        let arrays =
+            unsafe { slice::from_raw_parts(result.data as *const InternalArray, result.len) };
+        let rings: Vec<LineString<f64>> = arrays[..2]
+            .iter()
+            .map(|arr| {
+                unsafe { slice::from_raw_parts(arr.data as *const [f64; 2], arr.len) }
+                    .to_vec()
+                    .into()
+            })
+            .collect();
+        assert_eq!(
+            rings[0],
+            vec![
+                [0.0, 0.0],
+                [10.0, 0.0],
+                [10.0, 10.0],
+                [0.0, 10.0],
+                [0.0, 0.0]
+            ]
+            .into()
+        );
+        assert_eq!(rings[1], hole.into());
+        assert!(rings.iter().all(|ring| ring.is_closed()));
+        // an invalid hole keeps its slot
+        assert!(arrays[2].data.is_null());
+        drop_multi_float_array(result);
+
+        let no_holes = simplify_rdp_polygon_ffi(
+            exterior.into(),
+            ExternalArray {
+                data: std::ptr::null(),
+                len: 0,
+            },
+            1.0,
+        );
+        assert_eq!(no_holes.len, 1);
+        drop_multi_float_array(no_holes);
+        let invalid = simplify_rdp_polygon_ffi(
+            LineString::from(vec![[0.0, 0.0], [1.0, 1.0]]).into(),
+            external(&interiors),
+            1.0,
+        );
+        assert!(invalid.data.is_null());
+        drop_multi_float_array(invalid);
+    }
+}
@@ -0,0 +1,266 @@
+//! Streaming RDP simplification, for tracks which are too large to simplify in a single pass
+//!
+//! Points are accumulated in a window. Once the window is full it is simplified, and the retained
+//! points are committed to the output, apart from those in a trailing overlap region. The last
+//! committed point becomes the first point of the next window, so every committed segment still
+//! lies within the tolerance of the input points it replaces.
+//!
+//! Because RDP is a global algorithm, decisions made near a window boundary can differ from those
+//! made by a single pass over the whole line: the single pass may not have retained the point at
+//! which a window was split. The overlap means that, for typical data, only points retained
+//! early in each window are committed, and those decisions rarely depend on points beyond the
+//! window. Larger windows reduce the chance of a difference, at the cost of memory and time.
+
+use crate::ffi::guarded;
+use crate::{api, ExternalArray, FfiError, InternalArray};
+
+/// The default number of points accumulated before a window is simplified
+const DEFAULT_WINDOW: usize = 1024;
+
+/// An in-progress streaming RDP simplification
+///
+/// Create one using [`rdp_stream_new`](fn.rdp_stream_new.html).
+pub struct RdpStream {
+    precision: f64,
+    window: usize,
+    overlap: usize,
+    buffer: Vec<[f64; 2]>,
+    output: Vec<[f64; 2]>,
+}
+
+impl RdpStream {
+    /// Create a stream which simplifies using the given tolerance
+    pub fn new(precision: f64) -> Self {
+        Self::with_window(precision, DEFAULT_WINDOW)
+    }
+
+    /// Create a stream which simplifies once `window` points have been accumulated
+    ///
+    /// Half of each window is carried over into the next one. Windows smaller than 4 points are
+    /// treated as 4 points.
+    pub fn with_window(precision: f64, window: usize) -> Self {
+        let window = window.max(4);
+        RdpStream {
+            precision,
+            window,
+            overlap: window / 2,
+            buffer: Vec::with_capacity(window),
+            output: vec![],
+        }
+    }
+
+    /// Add points to the end of the line
+    pub fn push(&mut self, chunk: &[[f64; 2]]) {
+        self.buffer.extend_from_slice(chunk);
+        while self.buffer.len() >= self.window && self.commit() {}
+    }
+
+    /// Simplify any remaining points, and return the simplified line
+    ///
+    /// The stream is left empty, and can be reused.
+    pub fn finish(&mut self) -> Vec<[f64; 2]> {
+        let buffer = std::mem::take(&mut self.buffer);
+        let mut output = std::mem::take(&mut self.output);
+        if buffer.len() <= 2 {
+            output.extend(buffer);
+        } else {
+            output.extend(api::simplify_rdp(&buffer, self.precision));
+        }
+        output
+    }
+
+    // Simplify the current window, and commit the retained points which precede the overlap region.
+    // Returns false if nothing could be committed.
+    fn commit(&mut self) -> bool {
+        let retained = api::simplify_rdp_idx(&self.buffer, self.precision);
+        let limit = self.buffer.len() - self.overlap;
+        let mut split = retained
+            .iter()
+            .copied()
+            .take_while(|&idx| idx <= limit)
+            .last()
+            .unwrap_or(0);
+        if split == 0 {
+            // Every point in the window is within the tolerance of a single segment. Wait for
+            // more points, unless the window has grown too large, in which case split at its end
+            if self.buffer.len() < 2 * self.window {
+                return false;
+            }
+            split = self.buffer.len() - 1;
+        }
+        self.output.extend(
+            retained
+                .iter()
+                .take_while(|&&idx| idx < split)
+                .map(|&idx| self.buffer[idx]),
+        );
+        self.buffer.drain(..split);
+        true
+    }
+}
+
+/// Create a new streaming RDP simplification
+///
+/// Callers must pass a double-precision `float` for the tolerance.
+///
+/// Points are added using [`rdp_stream_push`](fn.rdp_stream_push.html), and the simplified line is
+/// retrieved using [`rdp_stream_finish`](fn.rdp_stream_finish.html). Decisions made at the boundaries
+/// of the stream's internal windows may differ from those made when simplifying the whole line
+/// using [`simplify_rdp_ffi`](fn.simplify_rdp_ffi.html), but every input point remains within the
+/// tolerance of the output.
+///
+/// Implementations calling this function **must** call [`rdp_stream_free`](fn.rdp_stream_free.html)
+/// with the returned pointer, in order to free the memory it allocates.
+#[no_mangle]
+pub extern "C" fn rdp_stream_new(precision: libc::c_double) -> *mut RdpStream {
+    Box::into_raw(Box::new(RdpStream::new(precision)))
+}
+
+/// Add a chunk of points to a streaming RDP simplification
+///
+/// Callers must pass two arguments:
+///
+/// - a pointer returned by [`rdp_stream_new`](fn.rdp_stream_new.html)
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+///
+/// The return value is an [`FfiError`](enum.FfiError.html) code. If it is anything other than
+/// `Success`, the chunk has not been added.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses raw pointers which could contain arbitrary data
+#[no_mangle]
+pub unsafe extern "C" fn rdp_stream_push(
+    stream: *mut RdpStream,
+    chunk: ExternalArray,
+) -> libc::c_int {
+    if stream.is_null() {
+        return FfiError::NullPointer as libc::c_int;
+    }
+    match chunk.validate::<f64, 2>() {
+        Ok(valid) => guarded(FfiError::Panic as libc::c_int, || {
+            (*stream).push(valid);
+            FfiError::Success as libc::c_int
+        }),
+        Err(error) => error as libc::c_int,
+    }
+}
+
+/// Finish a streaming RDP simplification, returning simplified geometry **coordinates**
+///
+/// Callers must pass a pointer returned by [`rdp_stream_new`](fn.rdp_stream_new.html). The stream is
+/// left empty, and can be reused. If the pointer is null, an empty array is returned.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub unsafe extern "C" fn rdp_stream_finish(stream: *mut RdpStream) -> InternalArray {
+    if stream.is_null() {
+        return InternalArray::empty();
+    }
+    guarded(InternalArray::empty(), || (*stream).finish().into())
+}
+
+/// Free a streaming RDP simplification created by [`rdp_stream_new`](fn.rdp_stream_new.html)
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub unsafe extern "C" fn rdp_stream_free(stream: *mut RdpStream) {
+    if stream.is_null() {
+        return;
+    }
+    let _ = Box::from_raw(stream);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::douglas_peucker::segment_distance;
+    use crate::drop_float_array;
+    use crate::ffi::external;
+    use geo::LineString;
+    use std::ptr;
+
+    #[test]
+    fn test_stream_matches_single_pass() {
+        let route: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        for precision in [0.0001, 0.001, 0.01] {
+            // the route fits in a single default-sized window
+            let mut stream = RdpStream::new(precision);
+            for chunk in route.chunks(50) {
+                stream.push(chunk);
+            }
+            assert_eq!(stream.finish(), api::simplify_rdp(&route, precision));
+        }
+    }
+    #[test]
+    fn test_stream_windowed() {
+        let route: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        for precision in [0.0001, 0.001, 0.01] {
+            let mut stream = RdpStream::with_window(precision, 128);
+            for chunk in route.chunks(50) {
+                stream.push(chunk);
+            }
+            let chunked = stream.finish();
+            let single = api::simplify_rdp(&route, precision);
+            assert_eq!(chunked.first(), single.first());
+            assert_eq!(chunked.last(), single.last());
+            // boundary decisions may differ, but not by much
+            let difference = chunked.len().abs_diff(single.len());
+            assert!(
+                difference * 10 <= single.len(),
+                "{} vs {}",
+                chunked.len(),
+                single.len()
+            );
+            // and every input point is still within the tolerance of the output
+            for point in &route {
+                let nearest = chunked
+                    .windows(2)
+                    .map(|seg| segment_distance(*point, seg[0], seg[1]))
+                    .fold(f64::INFINITY, f64::min);
+                assert!(nearest <= precision, "{:?} is {} away", point, nearest);
+            }
+        }
+    }
+    #[test]
+    fn test_stream_ffi() {
+        let input = [
+            [0.0, 0.0],
+            [5.0, 4.0],
+            [11.0, 5.5],
+            [17.3, 3.2],
+            [27.8, 0.1],
+        ];
+        unsafe {
+            let stream = rdp_stream_new(1.0);
+            for chunk in input.chunks(2) {
+                let chunk = external(chunk);
+                assert_eq!(rdp_stream_push(stream, chunk), 0);
+            }
+            let empty = ExternalArray {
+                data: ptr::null(),
+                len: 0,
+            };
+            assert_eq!(
+                rdp_stream_push(stream, empty),
+                FfiError::NullPointer as libc::c_int
+            );
+            let output: LineString<f64> = rdp_stream_finish(stream).into();
+            assert_eq!(
+                output,
+                vec![[0.0, 0.0], [5.0, 4.0], [11.0, 5.5], [27.8, 0.1]].into()
+            );
+            drop_float_array(output.into());
+            rdp_stream_free(stream);
+        }
+    }
+}
@@ -0,0 +1,642 @@
+//! Effective areas computed by the Visvalingam-Whyatt algorithm
+//!
+//! geo's `SimplifyVw` doesn't expose the triangle areas it ranks points by. This module repeats its
+//! elimination, without stopping at a tolerance, and records the area at which each point is
+//! removed. As in Visvalingam and Whyatt's paper, a point's effective area is never less than that
+//! of a point eliminated before it, so the areas can be thresholded directly: simplifying with an
+//! epsilon retains exactly the points whose effective area is greater than it.
+//!
+//! The same elimination, with each triangle's area multiplied by a per-point weight, implements a
+//! weighted variant of the algorithm, in which points with higher weights resist removal.
+//!
+//! geo leaves the order in which points with equal areas are removed to its heap. The same
+//! elimination, with ties broken by a [`TieBreak`] policy, makes that order deterministic.
+//!
+//! geo's topology-preserving variant only returns coordinates, which can't be mapped back to input
+//! indices when the input repeats a coordinate. The same elimination, checking each removal for
+//! crossings as geo does, returns its indices directly.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use geo::{Area, Coord, Intersects, Line, Triangle};
+use rstar::{RTree, RTreeObject, AABB};
+
+use crate::ffi::{guarded, leak};
+use crate::{ExternalArray, InternalArray};
+
+/// How Visvalingam-Whyatt chooses which of several points whose triangles have equal areas to
+/// remove first, when calling [`simplify_visvalingam_tiebreak_ffi`](fn.simplify_visvalingam_tiebreak_ffi.html)
+///
+/// A point becomes a candidate for removal at the start of simplification, and again each time
+/// one of its neighbours is removed.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreak {
+    /// The point which became a candidate earliest
+    First = 0,
+    /// The point which became a candidate most recently
+    Last = 1,
+    /// The point which appears earliest in the input
+    LowestIndex = 2,
+}
+
+impl TryFrom<libc::c_int> for TieBreak {
+    type Error = libc::c_int;
+
+    fn try_from(value: libc::c_int) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(TieBreak::First),
+            1 => Ok(TieBreak::Last),
+            2 => Ok(TieBreak::LowestIndex),
+            _ => Err(value),
+        }
+    }
+}
+
+// A candidate for removal, ranked by the area of the triangle it forms with its neighbours, and
+// then by `tie`
+struct Candidate {
+    area: f64,
+    tie: usize,
+    current: usize,
+    left: usize,
+    right: usize,
+}
+
+// These impls give us a min-heap. Without a tie-break policy, `tie` is always 0, so candidates are
+// ordered exactly as geo's are
+impl Ord for Candidate {
+    fn cmp(&self, other: &Candidate) -> Ordering {
+        other
+            .area
+            .partial_cmp(&self.area)
+            .unwrap()
+            .then_with(|| other.tie.cmp(&self.tie))
+    }
+}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Candidate) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Candidate) -> bool {
+        self.area == other.area && self.tie == other.tie
+    }
+}
+
+fn area(coords: &[[f64; 2]], left: usize, current: usize, right: usize) -> f64 {
+    let coord = |idx: usize| Coord::from(coords[idx]);
+    Triangle::new(coord(left), coord(current), coord(right)).unsigned_area()
+}
+
+/// Compute the effective area of each interior point of a line, in input order
+///
+/// The endpoints are always retained, so they have no entry: the result has two fewer elements
+/// than the input, and is empty for input with fewer than three points. Coordinates must be finite.
+pub(crate) fn effective_areas(coords: &[[f64; 2]]) -> Vec<f64> {
+    weighted_effective_areas(coords, |_| 1.0)
+}
+
+/// Compute the indices of the points retained by Visvalingam-Whyatt
+///
+/// The results are identical to those of geo's `SimplifyVwIdx`, but the coordinates are borrowed
+/// rather than copied into a `LineString`. As with geo, elimination stops at the first triangle
+/// whose area is greater than `epsilon`. Coordinates must be finite.
+pub(crate) fn vw_indices(coords: &[[f64; 2]], epsilon: f64) -> Vec<usize> {
+    retained_indices(coords, epsilon, None)
+}
+
+/// As [`vw_indices`], but points whose triangles have equal areas are removed in the order given
+/// by `tie_break`, rather than in an unspecified order
+pub(crate) fn vw_indices_tie_break(
+    coords: &[[f64; 2]],
+    epsilon: f64,
+    tie_break: TieBreak,
+) -> Vec<usize> {
+    retained_indices(coords, epsilon, Some(tie_break))
+}
+
+/// Compute the indices of the points retained by topology-preserving Visvalingam-Whyatt
+///
+/// The retained points are identical to those of geo's `SimplifyVwPreserve`, but they're
+/// identified by index, so repeated coordinates are told apart. Coordinates must be finite.
+pub(crate) fn vw_preserve_indices(coords: &[[f64; 2]], epsilon: f64) -> Vec<usize> {
+    let len = coords.len();
+    if len < 3 || epsilon <= 0.0 {
+        return (0..len).collect();
+    }
+    eliminate(coords, |_| 1.0, epsilon, None, true, |_, _| {})
+        .into_iter()
+        .enumerate()
+        .filter(|&(_, neighbours)| neighbours != REMOVED)
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+fn retained_indices(coords: &[[f64; 2]], epsilon: f64, tie_break: Option<TieBreak>) -> Vec<usize> {
+    let len = coords.len();
+    if len < 3 {
+        return (0..len).collect();
+    }
+    eliminate(coords, |_| 1.0, epsilon, tie_break, false, |_, _| {})
+        .into_iter()
+        .enumerate()
+        .filter(|&(_, neighbours)| neighbours != REMOVED)
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+// As effective_areas, but each point's triangle area is multiplied by `weight(index)`
+fn weighted_effective_areas<W>(coords: &[[f64; 2]], weight: W) -> Vec<f64>
+where
+    W: Fn(usize) -> f64,
+{
+    let len = coords.len();
+    if len < 3 {
+        return vec![];
+    }
+    let mut areas = vec![0.0; len];
+    eliminate(coords, weight, f64::INFINITY, None, false, |idx, area| {
+        areas[idx] = area
+    });
+    areas.truncate(len - 1);
+    areas.remove(0);
+    areas
+}
+
+// The neighbours of a point which has been eliminated
+const REMOVED: (usize, usize) = (0, 0);
+
+// A segment of the retained line, between the points at two indices
+#[derive(PartialEq)]
+struct Segment {
+    start: usize,
+    end: usize,
+    envelope: AABB<[f64; 2]>,
+}
+
+impl Segment {
+    fn new(coords: &[[f64; 2]], start: usize, end: usize) -> Segment {
+        Segment {
+            start,
+            end,
+            envelope: AABB::from_corners(coords[start], coords[end]),
+        }
+    }
+
+    fn line(&self, coords: &[[f64; 2]]) -> Line<f64> {
+        Line::new(coords[self.start], coords[self.end])
+    }
+}
+
+impl RTreeObject for Segment {
+    type Envelope = AABB<[f64; 2]>;
+
+    fn envelope(&self) -> Self::Envelope {
+        self.envelope
+    }
+}
+
+// Whether the segment which would join `left` and `right` if `current` were removed crosses a
+// segment of the retained line. As in geo, only segments within the triangle's bounding box are
+// checked, and segments sharing an endpoint coordinate with the new segment don't count, which
+// rules out the two segments it replaces
+fn removal_crosses(
+    coords: &[[f64; 2]],
+    segments: &RTree<Segment>,
+    left: usize,
+    current: usize,
+    right: usize,
+) -> bool {
+    let (start, end) = (Coord::from(coords[left]), Coord::from(coords[right]));
+    let replacement = Line::new(start, end);
+    let bounds = AABB::from_points(&[coords[left], coords[current], coords[right]]);
+    segments
+        .locate_in_envelope_intersecting(&bounds)
+        .map(|segment| segment.line(coords))
+        .any(|segment| {
+            ![start, end].contains(&segment.start)
+                && ![start, end].contains(&segment.end)
+                && replacement.intersects(&segment)
+        })
+}
+
+// Eliminate points until the smallest weighted triangle area is greater than `limit`, calling
+// `removed` with the index and effective area of each eliminated point. Equal areas are ordered
+// by `tie_break`, if given. If `preserve` is set, a removal which makes the line cross itself is
+// followed by the removal of the preceding point, as in geo's `SimplifyVwPreserve`. Returns the
+// retained neighbours of each point, which are REMOVED for eliminated points. `coords` must have
+// at least three points
+fn eliminate<W, R>(
+    coords: &[[f64; 2]],
+    weight: W,
+    limit: f64,
+    tie_break: Option<TieBreak>,
+    preserve: bool,
+    mut removed: R,
+) -> Vec<(usize, usize)>
+where
+    W: Fn(usize) -> f64,
+    R: FnMut(usize, f64),
+{
+    let len = coords.len();
+    // Retained neighbours of each point, as a linked list of indices. The first point's left
+    // neighbour and the last point's right neighbour are out of bounds
+    let mut adjacent: Vec<(usize, usize)> =
+        (0..len).map(|idx| (idx.wrapping_sub(1), idx + 1)).collect();
+    // The number of candidates created so far
+    let mut created = 0;
+    let mut candidate = |left: usize, current: usize, right: usize| {
+        created += 1;
+        Candidate {
+            area: area(coords, left, current, right) * weight(current),
+            tie: match tie_break {
+                None => 0,
+                Some(TieBreak::First) => created,
+                Some(TieBreak::Last) => usize::MAX - created,
+                Some(TieBreak::LowestIndex) => current,
+            },
+            current,
+            left,
+            right,
+        }
+    };
+    let mut heap: BinaryHeap<Candidate> = (1..len - 1)
+        .map(|current| candidate(current - 1, current, current + 1))
+        .collect();
+    // The segments of the retained line, which are only needed to check for crossings
+    let mut segments = preserve.then(|| {
+        RTree::bulk_load(
+            (1..len)
+                .map(|end| Segment::new(coords, end - 1, end))
+                .collect(),
+        )
+    });
+    let mut largest = f64::NEG_INFINITY;
+    let mut retained = len;
+    while let Some(smallest) = heap.pop() {
+        // The heap is ordered by area, so every remaining triangle is larger too
+        if smallest.area > limit {
+            break;
+        }
+        // geo's limits for a line: a line needs two points, and removing the point preceding a
+        // crossing mustn't leave fewer
+        if preserve && retained <= 2 {
+            break;
+        }
+        // A neighbour of this point has been removed since this candidate was created
+        if adjacent[smallest.current] != (smallest.left, smallest.right) {
+            continue;
+        }
+        let (left, current, right) = (smallest.left, smallest.current, smallest.right);
+        let crosses = segments
+            .as_ref()
+            .is_some_and(|segments| removal_crosses(coords, segments, left, current, right));
+        if crosses && retained <= 4 {
+            break;
+        }
+        retained -= 1;
+        largest = largest.max(smallest.area);
+        removed(current, largest);
+        if let Some(segments) = segments.as_mut() {
+            segments.remove(&Segment::new(coords, left, current));
+            segments.remove(&Segment::new(coords, current, right));
+            segments.insert(Segment::new(coords, left, right));
+        }
+        let (ll, _) = adjacent[left];
+        let (_, rr) = adjacent[right];
+        adjacent[left] = (ll, right);
+        adjacent[right] = (left, rr);
+        adjacent[smallest.current] = REMOVED;
+        for (a, current, b) in [(ll, left, right), (left, right, rr)] {
+            if a >= len || b >= len {
+                continue;
+            }
+            let mut next = candidate(a, current, b);
+            // The point preceding a removal which caused a crossing is removed next
+            if crosses && current < smallest.current {
+                next.area = -limit;
+            }
+            heap.push(next);
+        }
+    }
+    adjacent
+}
+
+/// Compute the effective Visvalingam-Whyatt area of each interior point of a geometry
+///
+/// Callers must pass a [Struct](struct.Array.html) with two fields:
+///
+/// - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+/// - `len`, the length of the array being passed. Its type must be `size_t`
+///
+/// The result is an array of double-precision `float`s, containing the effective area of each
+/// point apart from the first and last, in input order. The endpoints are always retained, so they
+/// have no entry. Simplifying with an epsilon using [`simplify_visvalingam_idx_ffi`](fn.simplify_visvalingam_idx_ffi.html)
+/// retains the endpoints, and the points whose effective area is greater than the epsilon,
+/// so the areas can be used to choose an epsilon empirically.
+///
+/// If the input is invalid, or has fewer than three points, an empty array is returned.
+///
+/// Implementations calling this function **must** call [`drop_double_array`](fn.drop_double_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_vw_areas_ffi(coords: ExternalArray) -> InternalArray {
+    match coords.validate::<f64, 2>() {
+        Ok(points) if points.len() >= 3 => {
+            guarded(InternalArray::empty(), || leak(effective_areas(points)))
+        }
+        _ => InternalArray::empty(),
+    }
+}
+
+/// Simplify a line using a weighted variant of the Visvalingam-Whyatt algorithm
+///
+/// Each point's triangle area is multiplied by its weight before points are ranked for removal, so
+/// points with higher weights resist removal. `weights` must have the same length as `coords`; the
+/// endpoints' weights are ignored, as they are always retained. Lines with fewer than three points
+/// are returned unchanged.
+pub(crate) fn simplify_vw_weighted(
+    coords: &[[f64; 2]],
+    weights: &[f64],
+    epsilon: f64,
+) -> Vec<[f64; 2]> {
+    if coords.len() < 3 {
+        return coords.to_vec();
+    }
+    let areas = weighted_effective_areas(coords, |idx| weights[idx]);
+    let mut retained = vec![coords[0]];
+    retained.extend(
+        areas
+            .iter()
+            .zip(&coords[1..])
+            .filter(|(&area, _)| area > epsilon)
+            .map(|(_, &coord)| coord),
+    );
+    retained.push(coords[coords.len() - 1]);
+    retained
+}
+
+/// FFI wrapper for weighted Visvalingam-Whyatt, returning simplified geometry **coordinates**
+///
+/// Callers must pass three arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of double-precision `float` weights, one per point
+///     - `len`, the number of weights, which must equal the number of points
+/// - a double-precision `float` for the epsilon
+///
+/// Each point's triangle area is multiplied by its weight before it is compared with the epsilon
+/// and with the areas of other points, so a point with a high weight (such as a labelled bend) is
+/// retained even if its area is small. A weight of 1 leaves a point's area unchanged, so weighting
+/// every point by 1 produces the same result as [`simplify_visvalingam_ffi`](fn.simplify_visvalingam_ffi.html).
+/// Weights should be non-negative. The endpoints' weights are ignored, as they are always retained.
+///
+/// If either input is invalid, or the number of weights doesn't equal the number of points, an
+/// empty array is returned.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses raw pointers which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_vw_weighted_ffi(
+    coords: ExternalArray,
+    weights: ExternalArray,
+    epsilon: libc::c_double,
+) -> InternalArray {
+    match (coords.validate::<f64, 2>(), weights.validate::<f64, 1>()) {
+        (Ok(points), Ok(weights)) if points.len() == weights.len() => {
+            guarded(InternalArray::empty(), || {
+                let weights: Vec<f64> = weights.iter().map(|[w]| *w).collect();
+                simplify_vw_weighted(points, &weights, epsilon).into()
+            })
+        }
+        _ => InternalArray::empty(),
+    }
+}
+
+/// FFI wrapper for Visvalingam-Whyatt with a tie-break policy, returning simplified geometry **coordinates**
+///
+/// Callers must pass three arguments:
+///
+/// - a [Struct](struct.Array.html) with two fields:
+///     - `data`, a void pointer to an array of floating-point point coordinates: `[[1.0, 2.0], ...]`
+///     - `len`, the length of the array being passed. Its type must be `size_t`
+/// - a double-precision `float` for the epsilon
+/// - an `int` selecting the [`TieBreak`](enum.TieBreak.html) policy: first (0), last (1), or
+///   lowest index (2)
+///
+/// Points are removed in order of increasing triangle area, as in [`simplify_visvalingam_ffi`](fn.simplify_visvalingam_ffi.html),
+/// which removes points whose areas are equal in an unspecified order. Lines often contain several
+/// points with equal (usually zero) areas, such as repeated points, and removing one can change its
+/// neighbours' areas, so the order affects which are retained. Here, it's chosen by the policy, so
+/// results are deterministic. If `tie_break` isn't a valid `TieBreak` value, an empty array is returned.
+///
+/// Implementations calling this function **must** call [`drop_float_array`](fn.drop_float_array.html)
+/// with the returned `Array` pointer, in order to free the memory it allocates.
+///
+/// # Safety
+///
+/// This function is unsafe because it accesses a raw pointer which could contain arbitrary data
+#[no_mangle]
+pub extern "C" fn simplify_visvalingam_tiebreak_ffi(
+    coords: ExternalArray,
+    epsilon: libc::c_double,
+    tie_break: libc::c_int,
+) -> InternalArray {
+    let Ok(tie_break) = TieBreak::try_from(tie_break) else {
+        return InternalArray::empty();
+    };
+    match coords.validate::<f64, 2>() {
+        Ok(points) => guarded(InternalArray::empty(), || {
+            let retained: Vec<[f64; 2]> = vw_indices_tie_break(points, epsilon, tie_break)
+                .into_iter()
+                .map(|idx| points[idx])
+                .collect();
+            geo::LineString::from(retained).into()
+        }),
+        Err(_) => InternalArray::empty(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffi::{external, reclaim};
+    use crate::{drop_double_array, drop_float_array};
+    use geo::simplify_vw::{SimplifyVwIdx, SimplifyVwPreserve};
+    use geo::LineString;
+
+    #[test]
+    fn test_effective_areas() {
+        let coords = [[0.0, 0.0], [1.0, 2.0], [2.0, 0.0], [3.0, 1.0], [4.0, 0.0]];
+        // The initial triangles at (1, 2), (2, 0) and (3, 1) have areas 2, 1.5 and 1.
+        // (3, 1) is removed first, so (2, 0) forms a triangle of area 3 with (1, 2) and (4, 0).
+        // (1, 2) is removed next, with area 2, leaving (2, 0) on a straight line between (0, 0)
+        // and (4, 0): its area of 0 is raised to 2, as it's removed after (1, 2)
+        assert_eq!(effective_areas(&coords), vec![2.0, 2.0, 1.0]);
+        assert!(effective_areas(&coords[..2]).is_empty());
+    }
+    #[test]
+    fn test_effective_areas_match_geo() {
+        let coords: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let ls: LineString<f64> = coords.clone().into();
+        let areas = effective_areas(&coords);
+        for epsilon in [0.0, 0.0000001, 0.0000075, 0.00001, 0.001] {
+            let mut thresholded = vec![0];
+            thresholded.extend(
+                areas
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &area)| area > epsilon)
+                    .map(|(idx, _)| idx + 1),
+            );
+            thresholded.push(coords.len() - 1);
+            assert_eq!(thresholded, ls.simplify_vw_idx(&epsilon), "{}", epsilon);
+        }
+    }
+    #[test]
+    fn test_vw_indices_match_geo() {
+        let coords: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let ls: LineString<f64> = coords.clone().into();
+        for epsilon in [-1.0, 0.0, 0.0000001, 0.0000075, 0.00001, 0.001, 1.0] {
+            assert_eq!(
+                vw_indices(&coords, epsilon),
+                ls.simplify_vw_idx(&epsilon),
+                "{}",
+                epsilon
+            );
+        }
+        assert_eq!(vw_indices(&coords[..2], 1.0), vec![0, 1]);
+        assert!(vw_indices(&[], 1.0).is_empty());
+    }
+    #[test]
+    fn test_vw_preserve_indices_match_geo() {
+        let coords: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let ls: LineString<f64> = coords.clone().into();
+        for epsilon in [-1.0, 0.0, 0.0000001, 0.0000075, 0.00001, 0.001, 1.0] {
+            let retained: Vec<[f64; 2]> = vw_preserve_indices(&coords, epsilon)
+                .into_iter()
+                .map(|idx| coords[idx])
+                .collect();
+            let expected: Vec<[f64; 2]> = ls
+                .simplify_vw_preserve(&epsilon)
+                .into_iter()
+                .map(Into::into)
+                .collect();
+            assert_eq!(retained, expected, "{}", epsilon);
+        }
+        assert_eq!(vw_preserve_indices(&coords[..2], 1.0), vec![0, 1]);
+        assert!(vw_preserve_indices(&[], 1.0).is_empty());
+    }
+    #[test]
+    fn test_vw_indices_tie_break() {
+        // Points 1 to 3 are repeated, so each has an area of 0. Removing one leaves its neighbours'
+        // areas at 0, until only one repeat is left, which has an area of 1 and is retained
+        let coords = [[0.0, 0.0], [1.0, 1.0], [1.0, 1.0], [1.0, 1.0], [2.0, 0.0]];
+        // 1 is removed first, then 3, as 2 becomes a candidate again after it
+        assert_eq!(
+            vw_indices_tie_break(&coords, 0.5, TieBreak::First),
+            vec![0, 2, 4]
+        );
+        // 3 is removed first, then 2, which became a candidate again when 3 was removed
+        assert_eq!(
+            vw_indices_tie_break(&coords, 0.5, TieBreak::Last),
+            vec![0, 1, 4]
+        );
+        assert_eq!(
+            vw_indices_tie_break(&coords, 0.5, TieBreak::LowestIndex),
+            vec![0, 3, 4]
+        );
+        // without ties, every policy matches geo
+        let route: Vec<[f64; 2]> = include!("../src/mk_route.rs");
+        let ls: LineString<f64> = route.clone().into();
+        for tie_break in [TieBreak::First, TieBreak::Last, TieBreak::LowestIndex] {
+            assert_eq!(
+                vw_indices_tie_break(&route, 0.0000075, tie_break),
+                ls.simplify_vw_idx(&0.0000075)
+            );
+        }
+    }
+    #[test]
+    fn test_ffi_vw_tiebreak() {
+        let coords = [[0.0, 0.0], [1.0, 1.0], [1.0, 1.0], [1.0, 1.0], [2.0, 0.0]];
+        let arr = external(&coords);
+        let simplified: LineString<f64> = simplify_visvalingam_tiebreak_ffi(arr, 0.5, 2).into();
+        assert_eq!(simplified, vec![[0.0, 0.0], [1.0, 1.0], [2.0, 0.0]].into());
+        let invalid = simplify_visvalingam_tiebreak_ffi(arr, 0.5, 3);
+        assert!(invalid.data.is_null());
+        drop_float_array(invalid);
+    }
+    #[test]
+    fn test_ffi_vw_areas() {
+        let coords = [[0.0, 0.0], [1.0, 2.0], [2.0, 0.0], [3.0, 1.0], [4.0, 0.0]];
+        let arr = external(&coords);
+        let areas: Vec<f64> = unsafe { reclaim(simplify_vw_areas_ffi(arr)) };
+        assert_eq!(areas, vec![2.0, 2.0, 1.0]);
+        let short = ExternalArray {
+            data: coords.as_ptr() as *const libc::c_void,
+            len: 2,
+        };
+        let empty = simplify_vw_areas_ffi(short);
+        assert!(empty.data.is_null());
+        drop_double_array(empty);
+    }
+    #[test]
+    fn test_vw_weighted() {
+        let coords = [[0.0, 0.0], [1.0, 2.0], [2.0, 0.0], [3.0, 1.0], [4.0, 0.0]];
+        // unit weights are equivalent to unweighted VW
+        for epsilon in [0.5, 1.0, 1.5, 2.5] {
+            let ls: LineString<f64> = coords.to_vec().into();
+            let expected: Vec<[f64; 2]> = ls
+                .simplify_vw_idx(&epsilon)
+                .into_iter()
+                .map(|idx| coords[idx])
+                .collect();
+            assert_eq!(simplify_vw_weighted(&coords, &[1.0; 5], epsilon), expected);
+        }
+        // (3, 1) has the smallest area, so unweighted VW drops it first. Weighting it by 10 means
+        // it outlasts the other interior points, and is the only one retained with an epsilon of 3.5
+        let weights = [1.0, 1.0, 1.0, 10.0, 1.0];
+        assert_eq!(
+            simplify_vw_weighted(&coords, &[1.0; 5], 1.5),
+            vec![[0.0, 0.0], [1.0, 2.0], [2.0, 0.0], [4.0, 0.0]]
+        );
+        assert_eq!(
+            simplify_vw_weighted(&coords, &weights, 3.5),
+            vec![[0.0, 0.0], [3.0, 1.0], [4.0, 0.0]]
+        );
+    }
+    #[test]
+    fn test_ffi_vw_weighted() {
+        let coords = [[0.0, 0.0], [1.0, 2.0], [2.0, 0.0], [3.0, 1.0], [4.0, 0.0]];
+        let weights = [1.0, 1.0, 1.0, 10.0, 1.0];
+        let arr = |data: *const libc::c_void, len| ExternalArray { data, len };
+        let points = arr(coords.as_ptr() as *const libc::c_void, coords.len());
+        let simplified: LineString<f64> =
+            simplify_vw_weighted_ffi(points, arr(weights.as_ptr() as *const libc::c_void, 5), 3.5)
+                .into();
+        assert_eq!(simplified, vec![[0.0, 0.0], [3.0, 1.0], [4.0, 0.0]].into());
+        // the number of weights must match the number of points
+        let mismatched =
+            simplify_vw_weighted_ffi(points, arr(weights.as_ptr() as *const libc::c_void, 4), 3.5);
+        assert!(mismatched.data.is_null());
+        drop_float_array(mismatched);
+    }
+}
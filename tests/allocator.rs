@@ -0,0 +1,104 @@
+// The allocator is global, and can only be set before the first allocation, so it's tested in its
+// own process, in a single test
+#![cfg(feature = "std")]
+
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+
+use rdp::{
+    drop_double_array, drop_float_array, drop_float_array_batch, rdp_set_allocator,
+    simplify_rdp_batch_ffi, simplify_rdp_ffi, simplify_vw_areas_ffi, ExternalArray,
+};
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static FREED: AtomicUsize = AtomicUsize::new(0);
+
+// Every block has the same size, and the most recently freed one is handed out by the next
+// allocation, so the test can control which arrays share an address
+const BLOCK_SIZE: usize = 1024;
+static RECYCLED: AtomicPtr<libc::c_void> = AtomicPtr::new(ptr::null_mut());
+
+extern "C" fn counting_alloc(size: libc::size_t) -> *mut libc::c_void {
+    ALLOCATED.fetch_add(1, Ordering::SeqCst);
+    assert!(size <= BLOCK_SIZE);
+    let recycled = RECYCLED.swap(ptr::null_mut(), Ordering::SeqCst);
+    if recycled.is_null() {
+        unsafe { libc::malloc(BLOCK_SIZE) }
+    } else {
+        recycled
+    }
+}
+
+extern "C" fn counting_free(data: *mut libc::c_void) {
+    FREED.fetch_add(1, Ordering::SeqCst);
+    let previous = RECYCLED.swap(data, Ordering::SeqCst);
+    unsafe { libc::free(previous) }
+}
+
+fn counts() -> (usize, usize) {
+    (
+        ALLOCATED.load(Ordering::SeqCst),
+        FREED.load(Ordering::SeqCst),
+    )
+}
+
+#[test]
+fn test_host_allocator() {
+    assert_eq!(rdp_set_allocator(Some(counting_alloc), None), 0);
+    assert_eq!(
+        rdp_set_allocator(Some(counting_alloc), Some(counting_free)),
+        1
+    );
+    // it can only be set once
+    assert_eq!(
+        rdp_set_allocator(Some(counting_alloc), Some(counting_free)),
+        0
+    );
+
+    let input = [
+        [0.0, 0.0],
+        [5.0, 4.0],
+        [11.0, 5.5],
+        [17.3, 3.2],
+        [27.8, 0.1],
+    ];
+    let coords = ExternalArray {
+        data: input.as_ptr() as *const libc::c_void,
+        len: input.len(),
+    };
+    let simplified = simplify_rdp_ffi(coords, 1.0);
+    assert_eq!(counts(), (1, 0));
+    let output = unsafe { std::slice::from_raw_parts(simplified.data as *const [f64; 2], 4) };
+    assert_eq!(output, [[0.0, 0.0], [5.0, 4.0], [11.0, 5.5], [27.8, 0.1]]);
+    drop_float_array(simplified);
+    assert_eq!(counts(), (1, 1));
+
+    // the host can free arrays itself, and the address can then be reused for an array of another
+    // type, which is freed by its own drop function
+    let simplified = simplify_rdp_ffi(coords, 1.0);
+    counting_free(simplified.data);
+    assert_eq!(counts(), (2, 2));
+    let areas = simplify_vw_areas_ffi(coords);
+    assert_eq!(areas.data, simplified.data);
+    drop_double_array(areas);
+    assert_eq!(counts(), (3, 3));
+
+    // nothing is allocated for an empty array
+    let empty = simplify_rdp_ffi(
+        ExternalArray {
+            data: input.as_ptr() as *const libc::c_void,
+            len: 0,
+        },
+        1.0,
+    );
+    assert!(empty.data.is_null());
+    drop_float_array(empty);
+    assert_eq!(counts(), (3, 3));
+
+    // a batch of two arrays, and the array holding them
+    let batch = [coords, coords];
+    let simplified = unsafe { simplify_rdp_batch_ffi(batch.as_ptr(), batch.len(), 1.0) };
+    assert_eq!(counts(), (6, 3));
+    unsafe { drop_float_array_batch(simplified, batch.len()) };
+    assert_eq!(counts(), (6, 6));
+}